@@ -60,6 +60,13 @@ pub trait BootloaderClient {
         id: String,
         value: String,
     ) -> ClientResult<BoxFuture<Result<(), Error>>>;
+    /// Sets the flavour of the installed kernel (e.g., "rt").
+    ///
+    /// * `flavor`: kernel flavour, `None` to use the default entry.
+    async fn set_kernel_flavor(
+        &mut self,
+        flavor: Option<String>,
+    ) -> ClientResult<BoxFuture<Result<(), Error>>>;
 }
 
 // full config used on dbus which beside public config passes
@@ -70,6 +77,8 @@ struct FullConfig {
     #[serde(flatten)]
     config: Config,
     kernel_args: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kernel_flavor: Option<String>,
 }
 
 pub type ClientResult<T> = Result<T, Error>;
@@ -140,4 +149,12 @@ impl BootloaderClient for Client {
         self.full_config.kernel_args.insert(id, value);
         self.send_config().await
     }
+
+    async fn set_kernel_flavor(
+        &mut self,
+        flavor: Option<String>,
+    ) -> ClientResult<BoxFuture<Result<(), Error>>> {
+        self.full_config.kernel_flavor = flavor;
+        self.send_config().await
+    }
 }
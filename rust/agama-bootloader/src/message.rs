@@ -69,6 +69,23 @@ impl SetKernelArg {
     }
 }
 
+/// Sets the flavour of the installed kernel.
+///
+/// It is used to make the entry for that kernel the default one.
+pub struct SetKernelFlavor {
+    pub flavor: Option<String>,
+}
+
+impl Message for SetKernelFlavor {
+    type Reply = ();
+}
+
+impl SetKernelFlavor {
+    pub fn new(flavor: Option<String>) -> Self {
+        Self { flavor }
+    }
+}
+
 #[derive(Clone)]
 pub struct SetLocale {
     pub locale: String,
@@ -139,6 +139,16 @@ impl MessageHandler<message::SetKernelArg> for Service {
     }
 }
 
+#[async_trait]
+impl MessageHandler<message::SetKernelFlavor> for Service {
+    async fn handle(&mut self, message: message::SetKernelFlavor) -> Result<(), Error> {
+        if let Err(err) = self.client.set_kernel_flavor(message.flavor).await {
+            tracing::error!("Failed to set the kernel flavor {:?}", err);
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl MessageHandler<message::SetLocale> for Service {
     async fn handle(&mut self, _message: message::SetLocale) -> Result<(), Error> {
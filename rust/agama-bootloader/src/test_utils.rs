@@ -35,6 +35,7 @@ use crate::{
 #[derive(Default, Clone)]
 pub struct TestClientState {
     pub config: Config,
+    pub kernel_flavor: Option<String>,
 }
 
 /// Storage test client.
@@ -104,6 +105,15 @@ impl BootloaderClient for TestClient {
     ) -> Result<BoxFuture<Result<(), Error>>, Error> {
         Ok(Box::pin(async { Ok(()) }))
     }
+
+    async fn set_kernel_flavor(
+        &mut self,
+        flavor: Option<String>,
+    ) -> Result<BoxFuture<Result<(), Error>>, Error> {
+        let mut state = self.state.lock().await;
+        state.kernel_flavor = flavor;
+        Ok(Box::pin(async { Ok(()) }))
+    }
 }
 
 /// Starts a testing storage service.
@@ -69,6 +69,18 @@ pub fn build_logs_cmd() -> Command {
     crate::logs::build_logs_cmd()
 }
 
+pub fn build_software_cmd() -> Command {
+    crate::software::build_software_cmd()
+}
+
+pub fn build_network_cmd() -> Command {
+    crate::network::build_network_cmd()
+}
+
+pub fn build_users_cmd() -> Command {
+    crate::users::build_users_cmd()
+}
+
 pub fn build_auth_cmd() -> Command {
     crate::auth::build_auth_cmd()
 }
@@ -46,7 +46,9 @@ use url::Url;
 use crate::{
     auth::run as run_auth_cmd, auth_tokens_file::AuthTokensFile, commands::Format,
     config::run as run_config_cmd, error::CliError, events::run as run_events_cmd,
-    logs::run as run_logs_cmd, questions::run as run_questions_cmd,
+    logs::run as run_logs_cmd, network::run as run_network_cmd,
+    questions::run as run_questions_cmd, software::run as run_software_cmd,
+    users::run as run_users_cmd,
 };
 
 mod auth;
@@ -60,8 +62,11 @@ mod error;
 mod events;
 mod logs;
 mod monitor;
+mod network;
 mod questions;
+mod software;
 mod status;
+mod users;
 
 use context::InstallationContext;
 use status::StatusReport;
@@ -139,6 +144,9 @@ pub fn build_cli() -> Command {
         .subcommand(crate::commands::build_install_cmd())
         .subcommand(crate::commands::build_questions_cmd())
         .subcommand(crate::commands::build_logs_cmd())
+        .subcommand(crate::commands::build_software_cmd())
+        .subcommand(crate::commands::build_network_cmd())
+        .subcommand(crate::commands::build_users_cmd())
         .subcommand(crate::commands::build_auth_cmd())
         .subcommand(crate::commands::build_download_cmd())
         .subcommand(crate::commands::build_finish_cmd())
@@ -399,6 +407,15 @@ pub async fn run_command(matches: ArgMatches) -> anyhow::Result<()> {
             let client = build_http_client(api_url, opts.insecure, true).await?;
             run_logs_cmd(client, sub_matches).await?
         }
+        Some(("software", sub_matches)) => {
+            let client = build_http_client(api_url, opts.insecure, true).await?;
+            run_software_cmd(client, sub_matches).await?
+        }
+        Some(("network", sub_matches)) => {
+            let client = build_http_client(api_url, opts.insecure, true).await?;
+            run_network_cmd(client, sub_matches).await?
+        }
+        Some(("users", sub_matches)) => run_users_cmd(sub_matches).await?,
         Some(("download", sub_matches)) => {
             let url = sub_matches.get_one::<String>("url").unwrap().clone();
             let destination = sub_matches
@@ -92,15 +92,22 @@ impl<'a> Widget for Progress<'a> {
         ])
         .areas(content_area);
 
-        let percent = if self.progress.size > 0 {
-            ((self.progress.index as f64 / self.progress.size as f64) * 100.0) as u16
-        } else {
-            0
+        let (percent, label) = match (self.progress.total_bytes, self.progress.done_bytes) {
+            (Some(total), Some(done)) if total > 0 => {
+                let percent = ((done as f64 / total as f64) * 100.0).min(100.0) as u16;
+                (percent, bytes_label(self.progress))
+            }
+            _ if self.progress.size > 0 => {
+                let percent =
+                    ((self.progress.index as f64 / self.progress.size as f64) * 100.0) as u16;
+                (percent, String::new())
+            }
+            _ => (0, String::new()),
         };
 
         Gauge::default()
             .percent(percent)
-            .label("")
+            .label(label)
             .render(gauge_area, buf);
 
         Line::from(format!(
@@ -110,3 +117,69 @@ impl<'a> Widget for Progress<'a> {
         .render(details_area, buf);
     }
 }
+
+/// Builds the label of the byte-level progress (e.g., "120.5 MiB / 1.2 GiB, 10.0 MiB/s, 01:52").
+fn bytes_label(progress: &api::Progress) -> String {
+    let mut label = format!(
+        "{} / {}",
+        format_bytes(progress.done_bytes.unwrap_or_default()),
+        format_bytes(progress.total_bytes.unwrap_or_default())
+    );
+    if let Some(bytes_per_second) = progress.bytes_per_second {
+        label.push_str(&format!(", {}/s", format_bytes(bytes_per_second)));
+    }
+    if let Some(eta) = progress.eta {
+        label.push_str(&format!(", {}", format_duration(eta)));
+    }
+    label
+}
+
+/// Formats an amount of bytes using binary units.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next_unit in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{:.1} {}", value, unit)
+}
+
+/// Formats a duration in seconds as "hh:mm:ss" or "mm:ss".
+fn format_duration(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, format_duration};
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(10 * 1024 * 1024), "10.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(59), "00:59");
+        assert_eq!(format_duration(112), "01:52");
+        assert_eq!(format_duration(3723), "01:02:03");
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+use agama_lib::{http::BaseHTTPClient, network::http_client::NetworkHTTPClient};
+use agama_utils::make_long;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use gettextrs::gettext;
+
+pub fn build_network_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama network
+    let about = gettext("Inspect the network connectivity");
+    Command::new("network")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .about(&about)
+        .subcommand(build_diagnose_cmd())
+}
+
+fn build_diagnose_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama network diagnose
+    let about = gettext("Diagnose the access to remote servers");
+    let long_about = make_long(
+        &about,
+        &gettext(
+            // TRANSLATORS: CLI help for: agama network diagnose (details)
+            "\
+        For each URL, it resolves the host name, connects to the host, performs the TLS \
+        handshake (showing a summary of the certificate) and sends an HTTP HEAD request, \
+        going through the configured proxy if needed. The results of each step are written \
+        in JSON format.",
+        ),
+    );
+    Command::new("diagnose")
+        .about(&about)
+        .long_about(long_about)
+        .arg(
+            Arg::new("urls")
+                .value_name("URL")
+                .action(ArgAction::Append)
+                // TRANSLATORS: CLI help for: agama network diagnose <URL>
+                .help(gettext(
+                    "URLs to diagnose. If not given, the repositories and the registration server are diagnosed",
+                )),
+        )
+}
+
+async fn diagnose(client: NetworkHTTPClient, urls: Vec<String>) -> anyhow::Result<()> {
+    let results = client.diagnose(&urls).await?;
+    println!("{}", serde_json::to_string_pretty(&results)?);
+    Ok(())
+}
+
+pub async fn run(client: BaseHTTPClient, sub_matches: &ArgMatches) -> anyhow::Result<()> {
+    let client = NetworkHTTPClient::new(client);
+    match sub_matches.subcommand() {
+        Some(("diagnose", matches)) => {
+            let urls = matches
+                .get_many::<String>("urls")
+                .unwrap_or_default()
+                .cloned()
+                .collect();
+            diagnose(client, urls).await
+        }
+        _ => Ok(()),
+    }
+}
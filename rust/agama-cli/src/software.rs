@@ -0,0 +1,213 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+use std::path::PathBuf;
+
+use agama_lib::{http::BaseHTTPClient, software::http_client::SoftwareHTTPClient};
+use agama_utils::{api::files::FileSource, make_long};
+use anyhow::anyhow;
+use chrono::DateTime;
+use clap::{value_parser, Arg, ArgMatches, Command};
+use fluent_uri::UriRef;
+use gettextrs::gettext;
+
+pub fn build_software_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama software
+    let about = gettext("Inspect the software selection");
+    Command::new("software")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .about(&about)
+        .subcommand(build_export_manifest_cmd())
+        .subcommand(build_testcase_cmd())
+        .subcommand(build_keys_cmd())
+}
+
+fn build_export_manifest_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama software export-manifest
+    let about = gettext("Export the resolved list of packages to install");
+    let long_about = make_long(
+        &about,
+        &gettext(
+            // TRANSLATORS: CLI help for: agama software export-manifest (details)
+            "\
+        The manifest lists every package selected for installation, including its version, \
+        architecture, repository and checksum, in JSON format. It can be used later as the \
+        \"software.manifest\" value of a profile to install exactly the same set of packages.",
+        ),
+    );
+    Command::new("export-manifest")
+        .about(&about)
+        .long_about(long_about)
+        .arg(
+            Arg::new("destination")
+                .value_name("DESTINATION")
+                .value_parser(value_parser!(PathBuf))
+                // TRANSLATORS: CLI help for: agama software export-manifest <DESTINATION>
+                .help(gettext(
+                    "File to write the manifest to. If not given, it is written to the standard output",
+                )),
+        )
+}
+
+fn build_testcase_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama software testcase
+    let about = gettext("Create a solver testcase for the current software selection");
+    let long_about = make_long(
+        &about,
+        &gettext(
+            // TRANSLATORS: CLI help for: agama software testcase (details)
+            "\
+        The testcase contains the repositories metadata, the selection and the solver \
+        settings, so the current software proposal can be reproduced when reporting a bug.",
+        ),
+    );
+    Command::new("testcase")
+        .about(&about)
+        .long_about(long_about)
+        .arg(
+            Arg::new("destination")
+                .value_name("DESTINATION")
+                .required(true)
+                .value_parser(value_parser!(PathBuf))
+                // TRANSLATORS: CLI help for: agama software testcase <DESTINATION>
+                .help(gettext("File to write the testcase to (a gzipped tarball)")),
+        )
+}
+
+fn build_keys_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama software keys
+    let about = gettext("Manage the GPG keys used to verify the repositories");
+    Command::new("keys")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .about(&about)
+        .subcommand(
+            // TRANSLATORS: CLI help for: agama software keys list
+            Command::new("list").about(gettext("List the known GPG keys")),
+        )
+        .subcommand(
+            Command::new("import")
+                // TRANSLATORS: CLI help for: agama software keys import
+                .about(gettext("Import a GPG key into the trusted keyring"))
+                .arg(
+                    Arg::new("source")
+                        .value_name("FILE_OR_URL")
+                        .required(true)
+                        // TRANSLATORS: CLI help for: agama software keys import <FILE_OR_URL>
+                        .help(gettext(
+                            "Local file containing the armored key or URL to fetch it from",
+                        )),
+                ),
+        )
+        .subcommand(
+            Command::new("remove")
+                // TRANSLATORS: CLI help for: agama software keys remove
+                .about(gettext("Remove a GPG key from the keyring"))
+                .arg(
+                    Arg::new("id")
+                        .value_name("ID")
+                        .required(true)
+                        // TRANSLATORS: CLI help for: agama software keys remove <ID>
+                        .help(gettext("Key ID")),
+                ),
+        )
+}
+
+async fn list_keys(client: SoftwareHTTPClient) -> anyhow::Result<()> {
+    for key in client.gpg_keys().await? {
+        let expires = key
+            .expires
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or("-".to_string());
+        let trust = if key.trusted { "trusted" } else { "untrusted" };
+        println!("{} {} {} {}", key.id, expires, trust, key.name);
+        println!("    {}", key.fingerprint);
+        if !key.trusted_by.is_empty() {
+            println!("    {}", key.trusted_by.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// Builds the key source from a local file or a URL.
+fn key_source(source: &str) -> anyhow::Result<FileSource> {
+    let path = PathBuf::from(source);
+    if path.exists() {
+        let content = std::fs::read_to_string(path)?;
+        return Ok(FileSource::Text { content });
+    }
+
+    let url = UriRef::parse(source.to_string()).map_err(|(e, _)| e)?;
+    if !url.has_scheme() {
+        return Err(anyhow!(
+            "{source} is neither an existing file nor an absolute URL"
+        ));
+    }
+    Ok(FileSource::Remote { url })
+}
+
+async fn export_manifest(
+    client: SoftwareHTTPClient,
+    destination: Option<&PathBuf>,
+) -> anyhow::Result<()> {
+    let manifest = client.manifest().await?;
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    match destination {
+        Some(path) => std::fs::write(path, manifest_json)?,
+        None => println!("{}", manifest_json),
+    }
+    Ok(())
+}
+
+pub async fn run(client: BaseHTTPClient, sub_matches: &ArgMatches) -> anyhow::Result<()> {
+    let client = SoftwareHTTPClient::new(client);
+    match sub_matches.subcommand() {
+        Some(("export-manifest", matches)) => {
+            let destination = matches.get_one::<PathBuf>("destination");
+            export_manifest(client, destination).await
+        }
+        Some(("testcase", matches)) => {
+            // unwrap OK: the destination is required
+            let destination = matches.get_one::<PathBuf>("destination").unwrap();
+            client.solver_testcase(destination).await?;
+            println!("{}", destination.display());
+            Ok(())
+        }
+        Some(("keys", matches)) => match matches.subcommand() {
+            Some(("list", _)) => list_keys(client).await,
+            Some(("import", matches)) => {
+                // unwrap OK: the source is required
+                let source = matches.get_one::<String>("source").unwrap();
+                client.import_gpg_key(&key_source(source)?).await?;
+                Ok(())
+            }
+            Some(("remove", matches)) => {
+                // unwrap OK: the id is required
+                let id = matches.get_one::<String>("id").unwrap();
+                client.remove_gpg_key(id).await?;
+                Ok(())
+            }
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
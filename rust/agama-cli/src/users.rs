@@ -0,0 +1,92 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+use std::io::{self, IsTerminal};
+
+use agama_utils::{make_long, password};
+use clap::{ArgMatches, Command};
+use gettextrs::gettext;
+use inquire::Password;
+
+use crate::error::CliError;
+
+pub fn build_users_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama users
+    let about = gettext("Manage the users configuration");
+    Command::new("users")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .about(&about)
+        .subcommand(build_hash_password_cmd())
+}
+
+fn build_hash_password_cmd() -> Command {
+    // TRANSLATORS: CLI help for: agama users hash-password
+    let about = gettext("Hash a password to use it in a profile");
+    let long_about = make_long(
+        &about,
+        &gettext(
+            // TRANSLATORS: CLI help for: agama users hash-password (details)
+            "\
+        The password is read from the standard input if it is not a terminal; otherwise, \
+        it is asked interactively. The hash is written to the standard output and it can be \
+        used as a hashed password in the users configuration.",
+        ),
+    );
+    Command::new("hash-password")
+        .about(&about)
+        .long_about(long_about)
+}
+
+/// Reads the password to hash.
+///
+/// It reads the password from stdin if available; otherwise, it asks the
+/// user.
+fn read_password() -> Result<String, CliError> {
+    let stdin = io::stdin();
+
+    let password = if stdin.is_terminal() {
+        Password::new(&gettext("Password to hash:"))
+            .prompt()
+            .map_err(CliError::InteractivePassword)?
+    } else {
+        let mut buffer = String::new();
+        stdin
+            .read_line(&mut buffer)
+            .map_err(CliError::StdinPassword)?;
+        buffer.trim_end_matches(['\r', '\n']).to_string()
+    };
+    Ok(password)
+}
+
+/// Hashes the password locally, so it is not sent to the server.
+async fn hash_password() -> anyhow::Result<()> {
+    let password = read_password()?;
+    let hash = password::hash_password(&password).await?;
+    println!("{hash}");
+    Ok(())
+}
+
+pub async fn run(sub_matches: &ArgMatches) -> anyhow::Result<()> {
+    match sub_matches.subcommand() {
+        Some(("hash-password", _)) => hash_password().await,
+        _ => Ok(()),
+    }
+}
@@ -50,8 +50,10 @@ pub use agama_utils::issue;
 pub mod logs;
 pub mod manager;
 pub mod monitor;
+pub mod network;
 pub mod profile;
 pub mod questions;
+pub mod software;
 pub mod utils;
 pub use agama_utils::{dbus, openapi};
 
@@ -0,0 +1,60 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+use agama_utils::api::network::UrlDiagnostics;
+use serde::Serialize;
+
+use crate::http::{BaseHTTPClient, BaseHTTPClientError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkHTTPClientError {
+    #[error(transparent)]
+    HTTP(#[from] BaseHTTPClientError),
+}
+
+#[derive(Serialize)]
+struct DiagnoseParams<'a> {
+    urls: &'a [String],
+}
+
+pub struct NetworkHTTPClient {
+    client: BaseHTTPClient,
+}
+
+impl NetworkHTTPClient {
+    pub fn new(client: BaseHTTPClient) -> Self {
+        Self { client }
+    }
+
+    /// Diagnoses the access to the given URLs.
+    ///
+    /// * `urls`: URLs to diagnose. If empty, the server diagnoses the repositories and the
+    ///   registration server.
+    pub async fn diagnose(
+        &self,
+        urls: &[String],
+    ) -> Result<Vec<UrlDiagnostics>, NetworkHTTPClientError> {
+        let params = DiagnoseParams { urls };
+        Ok(self
+            .client
+            .post("/private/network_diagnostics", &params)
+            .await?)
+    }
+}
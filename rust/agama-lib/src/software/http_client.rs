@@ -0,0 +1,87 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+use agama_utils::api::{
+    files::FileSource,
+    software::{GpgKey, Manifest},
+};
+use std::{fs, io::Cursor, os::unix::fs::OpenOptionsExt, path::Path};
+
+use crate::http::{BaseHTTPClient, BaseHTTPClientError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SoftwareHTTPClientError {
+    #[error(transparent)]
+    HTTP(#[from] BaseHTTPClientError),
+    #[error("Cannot store the solver testcase: {0}")]
+    CannotStoreTestcase(#[from] std::io::Error),
+}
+
+pub struct SoftwareHTTPClient {
+    client: BaseHTTPClient,
+}
+
+impl SoftwareHTTPClient {
+    pub fn new(client: BaseHTTPClient) -> Self {
+        Self { client }
+    }
+
+    /// Returns the manifest of the packages selected for installation.
+    pub async fn manifest(&self) -> Result<Manifest, SoftwareHTTPClientError> {
+        Ok(self.client.get("/private/software_manifest").await?)
+    }
+
+    /// Creates a solver testcase for the current selection and stores it in the given file.
+    ///
+    /// * `destination`: path of the archive (a gzipped tarball).
+    pub async fn solver_testcase(&self, destination: &Path) -> Result<(), SoftwareHTTPClientError> {
+        let response = self.client.get_raw("/private/solver_testcase").await?;
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .mode(0o600)
+            .open(destination)?;
+        let mut content = Cursor::new(response.bytes().await.map_err(BaseHTTPClientError::HTTP)?);
+        std::io::copy(&mut content, &mut file)?;
+        Ok(())
+    }
+
+    /// Returns the GPG keys known by the package manager.
+    pub async fn gpg_keys(&self) -> Result<Vec<GpgKey>, SoftwareHTTPClientError> {
+        Ok(self.client.get("/private/gpg_keys").await?)
+    }
+
+    /// Imports a GPG key into the trusted keyring.
+    ///
+    /// * `source`: armored key or URL to fetch it from.
+    pub async fn import_gpg_key(&self, source: &FileSource) -> Result<(), SoftwareHTTPClientError> {
+        Ok(self.client.post_void("/private/gpg_keys", source).await?)
+    }
+
+    /// Removes a GPG key from the keyring.
+    ///
+    /// * `id`: key ID.
+    pub async fn remove_gpg_key(&self, id: &str) -> Result<(), SoftwareHTTPClientError> {
+        let path = format!("/private/gpg_keys/{id}");
+        Ok(self.client.delete_void(&path).await?)
+    }
+}
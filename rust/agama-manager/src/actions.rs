@@ -28,7 +28,10 @@ use crate::{
 use agama_network::NetworkSystemClient;
 use agama_utils::{
     actor::{Handler, MessageHandler},
-    api::{files::scripts::ScriptsGroup, status::Stage, Config, FinishMethod, Scope},
+    api::{
+        files::scripts::ScriptsGroup, status::Stage, storage::MountPoint,
+        users::config::PasswordPolicy, Config, FinishMethod, Scope,
+    },
     issue,
     message::GetResolvables,
     products::ProductSpec,
@@ -38,6 +41,19 @@ use gettextrs::gettext;
 use std::{process::Command, sync::Arc};
 use tokio::sync::RwLock;
 
+/// Fails if the users configuration could not be written, according to its strictness.
+///
+/// * `report`: result of writing the users configuration.
+fn check_users_report(report: &users::InstallReport) -> Result<(), TaskError> {
+    if report.is_fatal() {
+        return Err(TaskError::from_error(users::service::Error::InstallFailed(
+            report.summary(),
+        )));
+    }
+
+    Ok(())
+}
+
 /// Implements the installation process.
 ///
 /// This action runs on a separate Tokio task to prevent the manager from blocking.
@@ -202,7 +218,7 @@ impl InstallAction {
                         .call(hostname::message::Install)
                         .await
                         .map_err(TaskError::from_error)?;
-                    users
+                    let users_report = users
                         .call(users::message::Install)
                         .await
                         .map_err(TaskError::from_error)?;
@@ -227,7 +243,9 @@ impl InstallAction {
                         .await
                         .map_err(TaskError::from_error)?;
 
-                    Ok(())
+                    // the users failures are checked at the end, so the rest of the
+                    // configuration is written anyway
+                    check_users_report(&users_report)
                 })
                 .await
         };
@@ -442,11 +460,13 @@ impl SetConfigAction {
         .await;
 
         // Users settings
+        let password_policy = Self::product_password_policy(product.as_ref()).await;
         self.spawn_config_task(
             Scope::Users,
             &gettext("Storing users settings"),
             self.users.clone(),
-            users::message::SetConfig::new(config.users.clone()),
+            users::message::SetConfig::new(config.users.clone())
+                .with_product_policy(password_policy),
             &[pre_scripts_task],
         )
         .await;
@@ -518,6 +538,14 @@ impl SetConfigAction {
         Ok(())
     }
 
+    /// Returns the password policy defined by the product, if any.
+    async fn product_password_policy(
+        product: Option<&Arc<RwLock<ProductSpec>>>,
+    ) -> Option<PasswordPolicy> {
+        let product = product?;
+        product.read().await.users.password_policy.clone()
+    }
+
     /// Helper to spawn a task for a config message call
     ///
     /// The task name is automatically derived from the scope by converting to lowercase.
@@ -636,7 +664,8 @@ impl SetConfigAction {
                         .update_config(network_config)
                         .await
                         .map_err(TaskError::from_error)?;
-                    handler.apply().await.map_err(TaskError::from_error)?;
+                    let diff = handler.apply().await.map_err(TaskError::from_error)?;
+                    tracing::info!("Network configuration applied: {diff:?}");
                     Ok(())
                 })
                 .await,
@@ -683,6 +712,13 @@ impl SetConfigAction {
     async fn set_bootloader_config(&self, config: &Config, dependencies: &[TaskId]) -> TaskId {
         let handler = self.bootloader.clone();
         let bootloader_config = config.bootloader.clone();
+        let kernel_flavor = config
+            .software
+            .as_ref()
+            .and_then(|c| c.software.as_ref())
+            .and_then(|c| c.kernel.as_ref())
+            .and_then(|c| c.flavor)
+            .map(|f| f.id().to_string());
         self.task_manager
             .task(
                 "bootloader_config",
@@ -691,6 +727,10 @@ impl SetConfigAction {
             )
             .depends_on(dependencies)
             .run(|| async move {
+                handler
+                    .call(bootloader::message::SetKernelFlavor::new(kernel_flavor))
+                    .await
+                    .map_err(TaskError::from_error)?;
                 let future = handler
                     .call(bootloader::message::SetConfig::new(bootloader_config))
                     .await
@@ -724,6 +764,7 @@ impl SetConfigAction {
             )
             .depends_on(dependencies)
             .run(move || async move {
+                Self::set_mount_points(&software_handler, &storage_handler).await;
                 Self::set_resolvables_for(&software_handler, "access", access_handler).await;
                 Self::set_resolvables_for(&software_handler, "files", files_handler).await;
                 Self::set_resolvables_for(&software_handler, "ntp", ntp_handler).await;
@@ -781,6 +822,31 @@ impl SetConfigAction {
             .await
     }
 
+    /// Passes the mount points of the storage proposal to the software service.
+    ///
+    /// They are used to check whether the selected software fits in the file systems.
+    async fn set_mount_points(
+        software: &Handler<software::Service>,
+        storage: &Handler<storage::Service>,
+    ) {
+        let mount_points = match storage.call(storage::message::GetProposal).await {
+            Ok(proposal) => proposal
+                .map(|p| MountPoint::from_proposal(&p))
+                .unwrap_or_default(),
+            Err(error) => {
+                tracing::error!("Failed to get the storage proposal: {error}");
+                vec![]
+            }
+        };
+
+        let result = software
+            .call(software::message::SetMountPoints::new(mount_points))
+            .await;
+        if let Err(error) = result {
+            tracing::error!("Failed to set the mount points: {error}");
+        }
+    }
+
     async fn set_resolvables_for<T>(
         software: &Handler<software::Service>,
         id: &str,
@@ -848,3 +914,99 @@ impl FinishAction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agama_utils::api::{
+        users::config::{RootUserConfig, Strictness, UserPassword},
+        Event,
+    };
+    use tokio::sync::{broadcast, Mutex};
+
+    /// Users model which cannot write any account.
+    struct FailingUsersModel;
+
+    #[async_trait::async_trait]
+    impl users::ModelAdapter for FailingUsersModel {
+        async fn install(
+            &self,
+            config: &agama_utils::api::users::Config,
+        ) -> Result<users::InstallReport, users::service::Error> {
+            let mut report = users::InstallReport::new(config.strictness.unwrap_or_default());
+            report.add_failure("root", "chpasswd failed");
+            Ok(report)
+        }
+    }
+
+    async fn start_users_service(events: broadcast::Sender<Event>) -> Handler<users::Service> {
+        let issues = issue::Service::starter(events.clone()).start();
+        let access = agama_access::Service::starter(events.clone())
+            .start()
+            .unwrap();
+        users::Service::starter(events, issues, access)
+            .with_model(FailingUsersModel)
+            .start()
+            .await
+            .unwrap()
+    }
+
+    // Runs a "configure" task which writes the users configuration and returns whether it failed.
+    async fn configure_users(strictness: Strictness) -> bool {
+        let (events, _events_rx) = broadcast::channel::<Event>(16);
+        let users = start_users_service(events.clone()).await;
+        let config = agama_utils::api::users::Config {
+            root: Some(RootUserConfig {
+                password: Some(UserPassword::hashed("$6$root")),
+                ..Default::default()
+            }),
+            strictness: Some(strictness),
+            ..Default::default()
+        };
+        users
+            .call(users::message::SetConfig::new(Some(config)))
+            .await
+            .unwrap();
+
+        let task_manager = TaskManager::new(events);
+        let config_task = task_manager
+            .task("configure", Scope::Manager, "Configuring the system")
+            .run(|| async move {
+                let report = users
+                    .call(users::message::Install)
+                    .await
+                    .map_err(TaskError::from_error)?;
+                check_users_report(&report)
+            })
+            .await;
+
+        let failed = Arc::new(Mutex::new(None));
+        let failed_clone = Arc::clone(&failed);
+        task_manager
+            .task("finish", Scope::Manager, "Finishing the installation")
+            .depends_on(&[config_task])
+            .run_always(move |failed_tasks| async move {
+                *failed_clone.lock().await = Some(failed_tasks);
+                Ok(())
+            })
+            .await;
+
+        loop {
+            if let Some(failed_tasks) = failed.lock().await.as_ref() {
+                return failed_tasks.contains(&config_task);
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fatal_users_report_fails_configure_task() {
+        assert!(configure_users(Strictness::Login).await);
+        assert!(configure_users(Strictness::Strict).await);
+    }
+
+    #[tokio::test]
+    async fn test_lenient_users_report_does_not_fail_configure_task() {
+        assert!(!configure_users(Strictness::Lenient).await);
+    }
+}
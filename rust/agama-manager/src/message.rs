@@ -18,6 +18,7 @@
 // To contact SUSE LLC about this file by physical or electronic mail, you may
 // find current contact information at www.suse.com.
 
+use agama_network::{model::NetworkStateDiff, DiagnosticsTarget};
 use agama_utils::{
     actor::Message,
     api::{
@@ -167,3 +168,34 @@ impl SolveStorageModel {
 impl Message for SolveStorageModel {
     type Reply = Option<Value>;
 }
+
+/// Gets the URLs to diagnose, including the proxy to go through.
+///
+/// If no URL is given, it returns the repositories and the registration server. The diagnostics
+/// themselves are not run by the manager as they might take a while (see
+/// [agama_network::diagnose_all]).
+#[derive(Debug)]
+pub struct GetDiagnosticsTargets {
+    pub urls: Vec<String>,
+}
+
+impl GetDiagnosticsTargets {
+    pub fn new(urls: Vec<String>) -> Self {
+        Self { urls }
+    }
+}
+
+impl Message for GetDiagnosticsTargets {
+    type Reply = Vec<DiagnosticsTarget>;
+}
+
+/// Applies the current network configuration.
+///
+/// It returns the changes that were made. If the system is not reachable after applying them,
+/// the changes are rolled back.
+#[derive(Debug)]
+pub struct ApplyNetwork;
+
+impl Message for ApplyNetwork {
+    type Reply = NetworkStateDiff;
+}
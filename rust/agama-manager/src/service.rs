@@ -31,6 +31,7 @@ use agama_utils::{
     api::{
         self, event,
         manager::{self, LicenseContent},
+        software::ProductConfig,
         status::Stage,
         Action, Config, Event, Issue, IssueMap, Proposal, Scope, Status, SystemInfo,
     },
@@ -43,10 +44,13 @@ use async_trait::async_trait;
 use merge::Merge;
 use network::NetworkSystemClient;
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use strum::VariantArray;
 use tokio::sync::{broadcast, RwLock};
 
+/// Registration server to use when the configuration does not set one.
+const DEFAULT_REGISTRATION_URL: &str = "https://scc.suse.com";
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
@@ -345,7 +349,7 @@ impl Starter {
         let network = match self.network {
             Some(network) => network,
             None => {
-                network::Service::starter(self.events.clone(), progress.clone())
+                network::Service::starter(self.events.clone(), progress.clone(), issues.clone())
                     .start()
                     .await?
             }
@@ -476,7 +480,8 @@ impl Service {
     // Configure the network according to defaults
     async fn network_default(&mut self) -> Result<(), Error> {
         self.network.propose_default().await?;
-        self.network.apply().await?;
+        let diff = self.network.apply().await?;
+        tracing::info!("Default network configuration applied: {diff:?}");
         Ok(())
     }
 
@@ -500,10 +505,32 @@ impl Service {
         self.set_config(config).await
     }
 
+    /// Updates the registration settings and applies the resulting configuration.
+    ///
+    /// The software service deregisters the system (and registers it again, if needed) when the
+    /// registration settings change, removing the services and recalculating the proposal.
+    ///
+    /// * `update`: function to update the product settings.
+    async fn update_registration<F>(&mut self, update: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut ProductConfig),
+    {
+        let mut config = self.config.clone();
+        let product = config
+            .software
+            .get_or_insert_default()
+            .product
+            .get_or_insert_default();
+        update(product);
+        self.set_config(config).await
+    }
+
     async fn set_config(&mut self, config: Config) -> Result<(), Error> {
         self.set_product(&config)?;
         let old_config = self.config.clone();
-        self.config = config;
+        // The users service keeps the passwords (hashed), so they are not cached here.
+        self.config = config.clone();
+        self.config.users = config.users.as_ref().map(|u| u.redacted());
 
         let action = SetConfigAction {
             bootloader: self.bootloader.clone(),
@@ -525,10 +552,7 @@ impl Service {
             task_manager: self.task_manager.clone(),
         };
 
-        if let Err(error) = action
-            .run(self.product.clone(), self.config.clone(), old_config)
-            .await
-        {
+        if let Err(error) = action.run(self.product.clone(), config, old_config).await {
             tracing::error!("Failed to set the configuration: {error}");
         }
 
@@ -628,6 +652,56 @@ impl Service {
         Ok(!is_busy)
     }
 
+    /// Returns the URLs to diagnose by default.
+    ///
+    /// They include the repositories (from the product and from the configuration) and the
+    /// registration server. Local repositories (e.g., "dvd:/") are left out.
+    async fn diagnostics_urls(&self) -> Vec<String> {
+        let mut urls = vec![];
+        let mut registration = false;
+        if let Some(product) = &self.product {
+            let product = product.read().await;
+            urls.extend(
+                product
+                    .software
+                    .repositories()
+                    .iter()
+                    .map(|r| r.url.clone()),
+            );
+            registration = product.registration;
+        }
+
+        let software_config = self.config.software.as_ref();
+        if let Some(software) = software_config.and_then(|c| c.software.as_ref()) {
+            let repositories = software
+                .extra_repositories
+                .iter()
+                .chain(software.update_repositories.iter())
+                .flatten();
+            urls.extend(repositories.map(|r| r.url.clone()));
+        }
+
+        let registration_url = software_config
+            .and_then(|c| c.product.as_ref())
+            .and_then(|p| p.registration_url.as_ref());
+        if let Some(url) = registration_url {
+            urls.push(url.to_string());
+        } else if registration {
+            urls.push(DEFAULT_REGISTRATION_URL.to_string());
+        }
+
+        let arch = Arch::current().map(|a| a.to_string()).unwrap_or_default();
+        let mut unique = vec![];
+        for url in urls {
+            let url = url.replace("$basearch", &arch);
+            if (url.starts_with("http://") || url.starts_with("https://")) && !unique.contains(&url)
+            {
+                unique.push(url);
+            }
+        }
+        unique
+    }
+
     /// Returns the product configuration.
     ///
     /// When the software is busy, the configuration will not include the product
@@ -869,6 +943,28 @@ impl MessageHandler<message::RunAction> for Service {
                 let only = only.unwrap_or_else(|| Scope::VARIANTS.to_vec());
                 self.probe(&only).await?;
             }
+            Action::Deregister => {
+                checks::check_stage(&self.progress, Stage::Configuring).await?;
+                self.update_registration(|product| product.clear_registration())
+                    .await?;
+            }
+            Action::DeregisterAddon { id } => {
+                checks::check_stage(&self.progress, Stage::Configuring).await?;
+                self.update_registration(|product| {
+                    if !product.remove_addon(&id) {
+                        tracing::info!("The add-on {id} is not in the registration settings");
+                    }
+                })
+                .await?;
+            }
+            Action::SwitchRegistrationServer { url, code } => {
+                checks::check_stage(&self.progress, Stage::Configuring).await?;
+                self.update_registration(|product| {
+                    product.registration_url = url;
+                    product.registration_code = code;
+                })
+                .await?;
+            }
             Action::Install => {
                 let ipmi = ipmi::Ipmi::default();
 
@@ -960,6 +1056,102 @@ impl MessageHandler<software::message::SetResolvables> for Service {
     }
 }
 
+#[async_trait]
+impl MessageHandler<software::message::GetManifest> for Service {
+    /// It returns the manifest of the packages selected for installation.
+    async fn handle(
+        &mut self,
+        message: software::message::GetManifest,
+    ) -> Result<api::software::Manifest, Error> {
+        Ok(self.software.call(message).await?)
+    }
+}
+
+#[async_trait]
+impl MessageHandler<software::message::CreateSolverTestcase> for Service {
+    /// It creates a solver testcase and returns the path to the archive.
+    ///
+    /// The software service is busy during the installation, so it is only possible to create
+    /// the testcase while configuring the system.
+    async fn handle(
+        &mut self,
+        message: software::message::CreateSolverTestcase,
+    ) -> Result<PathBuf, Error> {
+        checks::check_stage(&self.progress, Stage::Configuring).await?;
+        Ok(self.software.call(message).await?)
+    }
+}
+
+#[async_trait]
+impl MessageHandler<software::message::ListGpgKeys> for Service {
+    /// It returns the GPG keys known by the package manager.
+    async fn handle(
+        &mut self,
+        message: software::message::ListGpgKeys,
+    ) -> Result<Vec<api::software::GpgKey>, Error> {
+        checks::check_stage(&self.progress, Stage::Configuring).await?;
+        Ok(self.software.call(message).await?)
+    }
+}
+
+#[async_trait]
+impl MessageHandler<software::message::ImportGpgKey> for Service {
+    /// It imports a GPG key into the trusted keyring.
+    async fn handle(&mut self, message: software::message::ImportGpgKey) -> Result<(), Error> {
+        checks::check_stage(&self.progress, Stage::Configuring).await?;
+        Ok(self.software.call(message).await?)
+    }
+}
+
+#[async_trait]
+impl MessageHandler<software::message::RemoveGpgKey> for Service {
+    /// It removes a GPG key from the keyring.
+    async fn handle(&mut self, message: software::message::RemoveGpgKey) -> Result<(), Error> {
+        checks::check_stage(&self.progress, Stage::Configuring).await?;
+        Ok(self.software.call(message).await?)
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::ApplyNetwork> for Service {
+    /// It applies the network configuration and returns the changes.
+    async fn handle(
+        &mut self,
+        _message: message::ApplyNetwork,
+    ) -> Result<network::model::NetworkStateDiff, Error> {
+        checks::check_stage(&self.progress, Stage::Configuring).await?;
+        Ok(self.network.apply().await?)
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::GetDiagnosticsTargets> for Service {
+    /// It returns the URLs to diagnose and the proxy to go through for each of them.
+    async fn handle(
+        &mut self,
+        message: message::GetDiagnosticsTargets,
+    ) -> Result<Vec<network::DiagnosticsTarget>, Error> {
+        let urls = if message.urls.is_empty() {
+            self.diagnostics_urls().await
+        } else {
+            message.urls
+        };
+        let proxy = proxy::model::ProxyConfig::read().ok().flatten();
+
+        let targets = urls
+            .into_iter()
+            .map(|url| {
+                let proxy = proxy
+                    .as_ref()
+                    .and_then(|p| p.proxy_for(&url))
+                    .map(|p| p.url.clone());
+                network::DiagnosticsTarget { url, proxy }
+            })
+            .collect();
+        Ok(targets)
+    }
+}
+
 #[async_trait]
 impl MessageHandler<users::message::CheckPassword> for Service {
     async fn handle(
@@ -65,7 +65,7 @@ pub async fn start_service(events: event::Sender, dbus: zbus::Connection) -> Han
         .with_iscsi(iscsi)
         .with_s390(s390)
         .with_bootloader(start_bootloader_service(issues.clone(), dbus.clone()).await)
-        .with_network(start_network_service(events.clone(), progress.clone()).await)
+        .with_network(start_network_service(events.clone(), progress.clone(), issues.clone()).await)
         .with_proxy(start_proxy_service(events.clone()).await)
         .with_security(security.clone())
         .with_software(start_software_service(events, issues, l10n, progress, questions).await)
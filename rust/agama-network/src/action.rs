@@ -18,7 +18,7 @@
 // To contact SUSE LLC about this file by physical or electronic mail, you may
 // find current contact information at www.suse.com.
 
-use crate::model::{Connection, GeneralState};
+use crate::model::{Connection, GeneralState, NetworkStateDiff};
 use crate::types::{AccessPoint, ConnectionState, Device, Proposal, SystemInfo};
 use agama_utils::api::network::Config;
 use tokio::sync::oneshot;
@@ -84,7 +84,7 @@ pub enum Action {
     /// Remove the connection with the given UUID.
     RemoveConnection(Uuid),
     /// Apply the current configuration.
-    Apply(Responder<Result<(), NetworkAdapterError>>),
+    Apply(Responder<Result<NetworkStateDiff, NetworkAdapterError>>),
     /// Sets the locale
     SetLocale(String),
 }
@@ -18,7 +18,10 @@
 // To contact SUSE LLC about this file by physical or electronic mail, you may
 // find current contact information at www.suse.com.
 
-use crate::{model::StateConfig, Action, NetworkState};
+use crate::{
+    model::{NetworkStateDiff, StateConfig},
+    Action, NetworkState,
+};
 use agama_utils::{api::event::Event, issue, progress};
 use async_trait::async_trait;
 use thiserror::Error;
@@ -32,6 +35,10 @@ pub enum NetworkAdapterError {
     Write(anyhow::Error),
     #[error("Checkpoint handling error: {0}")]
     Checkpoint(anyhow::Error), // only relevant for adapters that implement a checkpoint mechanism
+    #[error(
+        "The network configuration was rolled back because the system was not reachable anymore"
+    )]
+    ConnectivityLost,
     #[error("The network watcher cannot run: {0}")]
     Watcher(anyhow::Error),
     #[error("Wrong signal arguments")]
@@ -50,7 +57,8 @@ pub enum NetworkAdapterError {
 #[async_trait]
 pub trait Adapter {
     async fn read(&self, config: StateConfig) -> Result<NetworkState, NetworkAdapterError>;
-    async fn write(&self, network: &NetworkState) -> Result<(), NetworkAdapterError>;
+    /// Writes the given network state and returns the changes that were applied.
+    async fn write(&self, network: &NetworkState) -> Result<NetworkStateDiff, NetworkAdapterError>;
     /// Returns the watcher, which is responsible for listening for network changes.
     fn watcher(&self) -> Option<Box<dyn Watcher + Send>> {
         None
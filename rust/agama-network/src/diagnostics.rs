@@ -0,0 +1,634 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Connectivity diagnostics.
+//!
+//! It finds out, step by step, whether a URL is reachable: it resolves the host name, connects
+//! to the host, performs the TLS handshake (HTTPS only) and sends an HTTP HEAD request. When a
+//! proxy is given, the name resolution and the connection refer to the proxy and, for HTTPS
+//! URLs, a tunnel is requested before the handshake.
+//!
+//! The TLS handshake trusts the system certificates and the ones imported by Agama (see
+//! [agama_security::CERTIFICATES_DIR]).
+
+use crate::types::{DiagnosticStep, DiagnosticStepKind, UrlDiagnostics};
+use agama_security::{certificate::Certificate, CERTIFICATES_DIR};
+use futures_util::future::join_all;
+use openssl::{
+    base64,
+    ssl::{HandshakeError, SslConnector, SslMethod, SslStream},
+    x509::{X509VerifyResult, X509},
+};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    path::Path,
+    time::{Duration, Instant},
+};
+use url::{Position, Url};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// URL to diagnose and the proxy to go through, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticsTarget {
+    pub url: String,
+    pub proxy: Option<String>,
+}
+
+/// Diagnoses the access to the given targets concurrently.
+///
+/// The results are returned in the same order as the targets.
+///
+/// * `targets`: URLs to diagnose.
+pub async fn diagnose_all(targets: &[DiagnosticsTarget]) -> Vec<UrlDiagnostics> {
+    let tasks = targets.iter().map(|t| diagnose(&t.url, t.proxy.as_deref()));
+    join_all(tasks).await
+}
+
+/// Diagnoses the access to the given URL.
+///
+/// The checks use blocking sockets, so they run in a separate thread.
+///
+/// * `url`: URL to diagnose (only HTTP and HTTPS URLs are supported).
+/// * `proxy`: URL of the proxy to go through, if any.
+pub async fn diagnose(url: &str, proxy: Option<&str>) -> UrlDiagnostics {
+    let task_url = url.to_string();
+    let proxy = proxy.map(ToString::to_string);
+    let task = tokio::task::spawn_blocking(move || run(&task_url, proxy.as_deref()));
+    task.await.unwrap_or_else(|e| {
+        let mut result = UrlDiagnostics::new(url);
+        result.error = Some(e.to_string());
+        result
+    })
+}
+
+fn run(url: &str, proxy: Option<&str>) -> UrlDiagnostics {
+    let mut result = UrlDiagnostics::new(url);
+    if let Err(error) = run_steps(&mut result, url, proxy) {
+        result.error = Some(error);
+    }
+    result.success = result.error.is_none() && result.steps.iter().all(|s| s.success);
+    result
+}
+
+/// Runs the steps until one of them fails.
+fn run_steps(result: &mut UrlDiagnostics, url: &str, proxy: Option<&str>) -> Result<(), String> {
+    let target = Endpoint::parse(url)?;
+    let proxy = proxy.map(Endpoint::parse_proxy).transpose()?;
+    result.proxy = proxy.as_ref().map(Endpoint::redacted);
+
+    let steps = &mut result.steps;
+    let peer = proxy.as_ref().unwrap_or(&target);
+    let Some(addresses) = run_step(steps, DiagnosticStepKind::Dns, |s| resolve(peer, s)) else {
+        return Ok(());
+    };
+    let Some(mut stream) = run_step(steps, DiagnosticStepKind::Tcp, |s| connect(&addresses, s))
+    else {
+        return Ok(());
+    };
+
+    if !target.tls {
+        run_step(steps, DiagnosticStepKind::Http, |s| {
+            head(&mut stream, &target, proxy.as_ref(), s)
+        });
+        return Ok(());
+    }
+
+    if let Some(proxy) = &proxy {
+        let tunnel = run_step(steps, DiagnosticStepKind::Tunnel, |s| {
+            tunnel(&mut stream, &target, proxy, s)
+        });
+        if tunnel.is_none() {
+            return Ok(());
+        }
+    }
+
+    let Some(mut stream) = run_step(steps, DiagnosticStepKind::Tls, |s| {
+        handshake(stream, &target, s)
+    }) else {
+        return Ok(());
+    };
+    run_step(steps, DiagnosticStepKind::Http, |s| {
+        head(&mut stream, &target, None, s)
+    });
+    Ok(())
+}
+
+/// Runs a step and records its result.
+///
+/// It returns the value produced by the step or `None` if it failed.
+fn run_step<T>(
+    steps: &mut Vec<DiagnosticStep>,
+    kind: DiagnosticStepKind,
+    function: impl FnOnce(&mut DiagnosticStep) -> Result<T, String>,
+) -> Option<T> {
+    let mut step = DiagnosticStep::new(kind);
+    let start = Instant::now();
+    let result = function(&mut step);
+    step.duration_ms = start.elapsed().as_millis() as u64;
+
+    let value = match result {
+        Ok(value) => {
+            step.success = true;
+            Some(value)
+        }
+        Err(error) => {
+            step.error = Some(error);
+            None
+        }
+    };
+    steps.push(step);
+    value
+}
+
+fn resolve(endpoint: &Endpoint, step: &mut DiagnosticStep) -> Result<Vec<SocketAddr>, String> {
+    let addresses: Vec<_> = (endpoint.host.as_str(), endpoint.port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .collect();
+    step.addresses = Some(addresses.iter().map(SocketAddr::ip).collect());
+    if addresses.is_empty() {
+        return Err(format!("No addresses found for {}", endpoint.host));
+    }
+    Ok(addresses)
+}
+
+/// Connects to the first reachable address.
+fn connect(addresses: &[SocketAddr], step: &mut DiagnosticStep) -> Result<TcpStream, String> {
+    let mut errors = vec![];
+    for address in addresses {
+        match TcpStream::connect_timeout(address, CONNECT_TIMEOUT) {
+            Ok(stream) => {
+                step.address = Some(*address);
+                stream
+                    .set_read_timeout(Some(IO_TIMEOUT))
+                    .and_then(|_| stream.set_write_timeout(Some(IO_TIMEOUT)))
+                    .map_err(|e| e.to_string())?;
+                return Ok(stream);
+            }
+            Err(error) => errors.push(format!("{address}: {error}")),
+        }
+    }
+    Err(errors.join("; "))
+}
+
+/// Asks the proxy to open a tunnel to the target (HTTP CONNECT).
+fn tunnel(
+    stream: &mut TcpStream,
+    target: &Endpoint,
+    proxy: &Endpoint,
+    step: &mut DiagnosticStep,
+) -> Result<(), String> {
+    let authority = target.authority();
+    let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+    if let Some(credentials) = proxy.credentials() {
+        request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let status = read_status(stream)?;
+    step.status = Some(status);
+    if !(200..300).contains(&status) {
+        return Err(format!(
+            "The proxy refused to open the tunnel (status {status})"
+        ));
+    }
+    Ok(())
+}
+
+/// Performs the TLS handshake, verifying the certificate of the target.
+fn handshake(
+    stream: TcpStream,
+    target: &Endpoint,
+    step: &mut DiagnosticStep,
+) -> Result<SslStream<TcpStream>, String> {
+    let mut builder = SslConnector::builder(SslMethod::tls()).map_err(|e| e.to_string())?;
+    for certificate in load_certificates(Path::new(CERTIFICATES_DIR)) {
+        // the certificate might be in the system store already
+        if let Err(error) = builder.cert_store_mut().add_cert(certificate) {
+            tracing::debug!("Could not add an imported certificate: {error}");
+        }
+    }
+    let connector = builder.build();
+    match connector.connect(&target.host, stream) {
+        Ok(stream) => {
+            step.certificate = certificate_summary(stream.ssl().peer_certificate());
+            Ok(stream)
+        }
+        Err(HandshakeError::Failure(stream)) => {
+            let ssl = stream.ssl();
+            step.certificate = certificate_summary(ssl.peer_certificate());
+            let verify_result = ssl.verify_result();
+            if verify_result != X509VerifyResult::OK {
+                return Err(format!(
+                    "Certificate verification failed: {}",
+                    verify_result.error_string()
+                ));
+            }
+            Err(stream.error().to_string())
+        }
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Loads the certificates (*.pem) from the given directory.
+///
+/// The files that cannot be read are ignored.
+///
+/// * `dir`: directory containing the certificates.
+fn load_certificates(dir: &Path) -> Vec<X509> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut certificates = vec![];
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().is_none_or(|e| e != "pem") {
+            continue;
+        }
+        match fs::read(&path).map(|c| X509::stack_from_pem(&c)) {
+            Ok(Ok(stack)) => certificates.extend(stack),
+            Ok(Err(error)) => tracing::warn!("Invalid certificate {}: {error}", path.display()),
+            Err(error) => tracing::warn!("Could not read {}: {error}", path.display()),
+        }
+    }
+    certificates
+}
+
+fn certificate_summary(x509: Option<X509>) -> Option<HashMap<String, String>> {
+    x509.map(|c| Certificate::new(c).to_data())
+}
+
+/// Sends an HTTP HEAD request and checks the response status.
+///
+/// * `proxy`: proxy to send the request to. It only applies to plain HTTP, HTTPS requests
+///   go through a tunnel.
+fn head<S: Read + Write>(
+    stream: &mut S,
+    target: &Endpoint,
+    proxy: Option<&Endpoint>,
+    step: &mut DiagnosticStep,
+) -> Result<(), String> {
+    let path = match proxy {
+        Some(_) => target.redacted(),
+        None => target.url[Position::BeforePath..Position::AfterQuery].to_string(),
+    };
+    let mut request = format!(
+        "HEAD {path} HTTP/1.1\r\nHost: {}\r\nUser-Agent: agama\r\nAccept: */*\r\nConnection: close\r\n",
+        target.host_header()
+    );
+    if let Some(credentials) = target.credentials() {
+        request.push_str(&format!("Authorization: Basic {credentials}\r\n"));
+    }
+    if let Some(credentials) = proxy.and_then(Endpoint::credentials) {
+        request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let status = read_status(stream)?;
+    step.status = Some(status);
+    if status >= 400 {
+        return Err(format!("The server answered with status {status}"));
+    }
+    Ok(())
+}
+
+/// Reads the head of an HTTP response and returns its status code.
+///
+/// It reads byte by byte to not consume anything beyond the head, as the stream is reused
+/// for the TLS handshake after opening a tunnel.
+fn read_status(reader: &mut impl Read) -> Result<u16, String> {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HEAD_SIZE {
+            return Err("The response head is too long".to_string());
+        }
+        match reader.read(&mut byte) {
+            Ok(0) => break,
+            Ok(_) => head.push(byte[0]),
+            Err(error) => return Err(error.to_string()),
+        }
+    }
+
+    if head.is_empty() {
+        return Err("The connection was closed without a response".to_string());
+    }
+    parse_status(&String::from_utf8_lossy(&head))
+}
+
+fn parse_status(head: &str) -> Result<u16, String> {
+    let line = head.lines().next().unwrap_or_default();
+    let mut parts = line.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some(version), Some(code)) if version.starts_with("HTTP/") => code
+            .parse()
+            .map_err(|_| format!("Invalid HTTP status line: {line}")),
+        _ => Err(format!("Invalid HTTP status line: {line}")),
+    }
+}
+
+/// Host to connect to, as given by a URL.
+struct Endpoint {
+    url: Url,
+    host: String,
+    port: u16,
+    tls: bool,
+}
+
+impl Endpoint {
+    fn parse(url: &str) -> Result<Self, String> {
+        let url = Url::parse(url).map_err(|e| format!("Invalid URL {url}: {e}"))?;
+        let tls = match url.scheme() {
+            "http" => false,
+            "https" => true,
+            scheme => return Err(format!("Unsupported URL scheme: {scheme}")),
+        };
+        let Some(host) = url.host_str() else {
+            return Err(format!("Missing host in URL {url}"));
+        };
+        let host = host.trim_matches(['[', ']']).to_string();
+        // unwrap OK: HTTP and HTTPS have a known default port
+        let port = url.port_or_known_default().unwrap();
+        Ok(Self {
+            url,
+            host,
+            port,
+            tls,
+        })
+    }
+
+    /// Parses the URL of a proxy.
+    ///
+    /// The scheme is optional (e.g., "proxy.example.com:3128") and only HTTP proxies are
+    /// supported.
+    fn parse_proxy(url: &str) -> Result<Self, String> {
+        let proxy = if url.contains("://") {
+            Self::parse(url)?
+        } else {
+            Self::parse(&format!("http://{url}"))?
+        };
+        if proxy.tls {
+            return Err(format!("Unsupported proxy {}", proxy.redacted()));
+        }
+        Ok(proxy)
+    }
+
+    /// Host and port, as used in a CONNECT request.
+    fn authority(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    /// Value of the "Host" header.
+    fn host_header(&self) -> String {
+        // unwrap OK: the host was checked when parsing the URL
+        let host = self.url.host_str().unwrap();
+        match self.url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        }
+    }
+
+    /// Credentials included in the URL, encoded for the basic authentication scheme.
+    fn credentials(&self) -> Option<String> {
+        if self.url.username().is_empty() {
+            return None;
+        }
+        let credentials = format!(
+            "{}:{}",
+            self.url.username(),
+            self.url.password().unwrap_or_default()
+        );
+        Some(base64::encode_block(credentials.as_bytes()))
+    }
+
+    /// URL without the credentials and the fragment.
+    fn redacted(&self) -> String {
+        let mut url = self.url.clone();
+        // the URL has a host, so setting the credentials cannot fail
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+        url.set_fragment(None);
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Cursor},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serves a single request, answering with the given status line.
+    ///
+    /// It returns the address to connect to and a channel to get the request line.
+    fn serve_once(status_line: &'static str) -> (SocketAddr, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+            }
+            sender.send(request_line.trim_end().to_string()).unwrap();
+            write!(stream, "{status_line}\r\nContent-Length: 0\r\n\r\n").unwrap();
+        });
+        (address, receiver)
+    }
+
+    fn kinds(result: &UrlDiagnostics) -> Vec<DiagnosticStepKind> {
+        result.steps.iter().map(|s| s.kind).collect()
+    }
+
+    #[test]
+    fn test_diagnose_http() {
+        let (address, receiver) = serve_once("HTTP/1.1 200 OK");
+        let url = format!("http://{address}/repo/oss?arch=x86_64");
+
+        let result = run(&url, None);
+        assert!(result.success);
+        assert_eq!(result.proxy, None);
+        assert_eq!(
+            kinds(&result),
+            vec![
+                DiagnosticStepKind::Dns,
+                DiagnosticStepKind::Tcp,
+                DiagnosticStepKind::Http
+            ]
+        );
+        assert_eq!(result.steps[0].addresses, Some(vec![address.ip()]));
+        assert_eq!(result.steps[1].address, Some(address));
+        assert_eq!(result.steps[2].status, Some(200));
+        assert_eq!(
+            receiver.recv().unwrap(),
+            "HEAD /repo/oss?arch=x86_64 HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_diagnose_http_error() {
+        let (address, _receiver) = serve_once("HTTP/1.1 404 Not Found");
+        let url = format!("http://{address}/missing");
+
+        let result = run(&url, None);
+        assert!(!result.success);
+        let http = result.steps.last().unwrap();
+        assert_eq!(http.kind, DiagnosticStepKind::Http);
+        assert_eq!(http.status, Some(404));
+        assert!(http.error.is_some());
+    }
+
+    #[test]
+    fn test_diagnose_through_proxy() {
+        let (address, receiver) = serve_once("HTTP/1.1 200 OK");
+        let proxy = format!("http://user:secret@{address}");
+
+        let result = run("http://download.example.com/repo", Some(&proxy));
+        assert!(result.success);
+        assert_eq!(result.proxy, Some(format!("http://{address}/")));
+        assert_eq!(result.steps[1].address, Some(address));
+        assert_eq!(
+            receiver.recv().unwrap(),
+            "HEAD http://download.example.com/repo HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_diagnose_tunnel_refused() {
+        let (address, receiver) = serve_once("HTTP/1.1 407 Proxy Authentication Required");
+        let proxy = address.to_string();
+
+        let result = run("https://download.example.com/repo", Some(&proxy));
+        assert!(!result.success);
+        assert_eq!(
+            kinds(&result),
+            vec![
+                DiagnosticStepKind::Dns,
+                DiagnosticStepKind::Tcp,
+                DiagnosticStepKind::Tunnel
+            ]
+        );
+        assert_eq!(result.steps[2].status, Some(407));
+        assert_eq!(
+            receiver.recv().unwrap(),
+            "CONNECT download.example.com:443 HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_diagnose_connection_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let result = run(&format!("http://{address}/"), None);
+        assert!(!result.success);
+        assert_eq!(
+            kinds(&result),
+            vec![DiagnosticStepKind::Dns, DiagnosticStepKind::Tcp]
+        );
+        assert!(result.steps[1].error.is_some());
+    }
+
+    #[test]
+    fn test_diagnose_unsupported_url() {
+        let result = run("dvd:/?devices=/dev/sr0", None);
+        assert!(!result.success);
+        assert!(result.steps.is_empty());
+        assert_eq!(
+            result.error,
+            Some("Unsupported URL scheme: dvd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_status() {
+        let mut response = Cursor::new(b"HTTP/1.1 200 Connection established\r\n\r\nTLS".to_vec());
+        assert_eq!(read_status(&mut response), Ok(200));
+        let mut rest = String::new();
+        response.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "TLS");
+
+        let mut response = Cursor::new(b"".to_vec());
+        assert!(read_status(&mut response).is_err());
+
+        let mut response = Cursor::new(b"SSH-2.0-OpenSSH_9.6\r\n".to_vec());
+        assert!(read_status(&mut response).is_err());
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let endpoint = Endpoint::parse("https://user:pass@[::1]:8443/path#top").unwrap();
+        assert_eq!(endpoint.host, "::1");
+        assert_eq!(endpoint.authority(), "[::1]:8443");
+        assert_eq!(endpoint.host_header(), "[::1]:8443");
+        assert_eq!(endpoint.redacted(), "https://[::1]:8443/path");
+        assert_eq!(endpoint.credentials(), Some("dXNlcjpwYXNz".to_string()));
+
+        let endpoint = Endpoint::parse("http://example.com/").unwrap();
+        assert_eq!(endpoint.authority(), "example.com:80");
+        assert_eq!(endpoint.host_header(), "example.com");
+        assert_eq!(endpoint.credentials(), None);
+
+        let proxy = Endpoint::parse_proxy("proxy.example.com:3128").unwrap();
+        assert_eq!(proxy.authority(), "proxy.example.com:3128");
+        assert!(Endpoint::parse_proxy("https://proxy.example.com").is_err());
+    }
+
+    #[test]
+    fn test_load_certificates() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/share/test.pem");
+        fs::copy(&source, dir.path().join("registration.pem")).unwrap();
+        fs::write(dir.path().join("invalid.pem"), "not a certificate").unwrap();
+        fs::write(dir.path().join("README"), "ignored").unwrap();
+
+        let certificates = load_certificates(dir.path());
+        assert_eq!(certificates.len(), 1);
+
+        assert!(load_certificates(&dir.path().join("missing")).is_empty());
+    }
+}
@@ -58,6 +58,10 @@ pub enum NetworkStateError {
     AdapterError(String),
     #[error("Invalid bond mode '{0}'")]
     InvalidBondMode(String),
+    #[error("Invalid MACVLAN mode '{0}'")]
+    InvalidMacvlanMode(String),
+    #[error("Invalid IPVLAN mode '{0}'")]
+    InvalidIpvlanMode(String),
     #[error("Invalid bond options")]
     InvalidBondOptions,
     #[error("Not a controller connection: '{0}'")]
@@ -84,6 +88,8 @@ pub enum NetworkStateError {
     InvalidWirelessBand(String),
     #[error("Invalid bssid: '{0}'")]
     InvalidBssid(String),
+    #[error("Invalid routing rule with priority {0}: {1}")]
+    InvalidRoutingRule(u32, String),
     #[error("I/O error: '{0}'")]
     IoError(String),
 }
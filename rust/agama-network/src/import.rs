@@ -0,0 +1,151 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Import of existing network configurations.
+//!
+//! This module converts the network configuration written in other formats (nmstate YAML
+//! documents or NetworkManager keyfiles) into [NetworkConnection] settings. The settings that
+//! cannot be represented are collected so they can be reported to the user.
+
+mod keyfile;
+mod nmstate;
+
+use crate::types::{ImportFormat, ImportSettings, NetworkConnection};
+use agama_utils::api::files::FileSourceError;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    str,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ImportError {
+    #[error("Could not read the network configuration: {0}")]
+    FileSource(#[from] FileSourceError),
+    #[error("I/O error: {0}")]
+    IO(#[from] io::Error),
+    #[error("Invalid nmstate document: {0}")]
+    Nmstate(#[from] serde_yaml::Error),
+    #[error("Could not extract the keyfiles: {0}")]
+    Extract(String),
+    #[error("Invalid keyfile '{0}': {1}")]
+    Keyfile(String, String),
+    #[error("The import task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
+
+/// Network connections imported from an existing configuration.
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportedConfig {
+    /// Imported connections.
+    pub connections: Vec<NetworkConnection>,
+    /// Settings that are not supported and were ignored.
+    pub unsupported: Vec<String>,
+}
+
+/// Imports the network configuration without blocking the async runtime.
+///
+/// The import writes temporary files and might run `tar`, so it runs in a blocking task.
+///
+/// * `settings`: format and source of the configuration.
+pub async fn import_async(settings: ImportSettings) -> Result<ImportedConfig, ImportError> {
+    tokio::task::spawn_blocking(move || import(&settings)).await?
+}
+
+/// Imports the network configuration described by the given settings.
+///
+/// * `settings`: format and source of the configuration.
+pub fn import(settings: &ImportSettings) -> Result<ImportedConfig, ImportError> {
+    let workdir = tempfile::TempDir::with_prefix("agama-network-import-")?;
+    let path = workdir.path().join("source");
+    settings.source.write(&path, 0o600)?;
+
+    match settings.format {
+        ImportFormat::Nmstate => {
+            let content = fs::read_to_string(&path)?;
+            nmstate::import(&content)
+        }
+        ImportFormat::Keyfiles => import_keyfiles(&path, workdir.path()),
+    }
+}
+
+/// Imports the keyfiles from the given file.
+///
+/// The file can be a single keyfile or a tarball containing several of them.
+///
+/// * `path`: file to import.
+/// * `workdir`: directory to extract the tarball to.
+fn import_keyfiles(path: &Path, workdir: &Path) -> Result<ImportedConfig, ImportError> {
+    let content = fs::read(path)?;
+    if let Ok(text) = str::from_utf8(&content) {
+        if keyfile::is_keyfile(text) {
+            return keyfile::import(&[("source".to_string(), text.to_string())]);
+        }
+    }
+
+    let dir = workdir.join("keyfiles");
+    fs::create_dir(&dir)?;
+    let output = Command::new("tar")
+        .args(["--no-same-owner", "--no-same-permissions", "-xf"])
+        .arg(path)
+        .arg("-C")
+        .arg(&dir)
+        .output()?;
+    if !output.status.success() {
+        return Err(ImportError::Extract(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let mut paths = vec![];
+    find_keyfiles(&dir, &mut paths)?;
+    paths.sort();
+
+    let mut keyfiles = vec![];
+    for path in paths {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        keyfiles.push((name, fs::read_to_string(&path)?));
+    }
+    keyfile::import(&keyfiles)
+}
+
+/// Finds the keyfiles (*.nmconnection) in the given directory and its subdirectories.
+///
+/// Symbolic links are ignored.
+///
+/// * `dir`: directory to search.
+/// * `paths`: where to add the found keyfiles.
+fn find_keyfiles(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            find_keyfiles(&path, paths)?;
+        } else if file_type.is_file() && path.extension().is_some_and(|e| e == "nmconnection") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
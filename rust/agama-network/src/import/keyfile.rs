@@ -0,0 +1,681 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Conversion of NetworkManager keyfiles (*.nmconnection) into connection settings.
+
+use super::{ImportError, ImportedConfig};
+use crate::types::{
+    BondSettings, BridgeSettings, MacvlanSettings, MatchSettings, NetworkConnection, VlanSettings,
+    VrfSettings, WirelessSettings,
+};
+use cidr::IpInet;
+use std::{net::IpAddr, str::FromStr};
+
+/// Keys from the "connection" section that do not affect the imported connection.
+const IGNORED_CONNECTION_KEYS: [&str; 4] = ["slave-type", "port-type", "timestamp", "permissions"];
+
+/// Section of a keyfile.
+struct Section {
+    name: String,
+    keys: Vec<(String, String)>,
+}
+
+/// Connection read from a keyfile, including the information to find its controller.
+struct KeyfileConnection {
+    file: String,
+    uuid: Option<String>,
+    controller: Option<String>,
+    connection: NetworkConnection,
+}
+
+/// Whether the given text looks like a keyfile.
+///
+/// * `text`: text to check.
+pub fn is_keyfile(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with(';'))
+        .is_some_and(|l| l.starts_with('['))
+}
+
+/// Imports the given keyfiles.
+///
+/// * `keyfiles`: list of keyfiles, as pairs of file name and content.
+pub fn import(keyfiles: &[(String, String)]) -> Result<ImportedConfig, ImportError> {
+    let mut imported = ImportedConfig::default();
+    let mut conns = vec![];
+
+    for (file, content) in keyfiles {
+        let sections = parse(file, content)?;
+        let mut reader = Reader {
+            file,
+            unsupported: &mut imported.unsupported,
+        };
+        if let Some(conn) = reader.read(&sections)? {
+            conns.push(conn);
+        }
+    }
+
+    set_controllers(&mut conns, &mut imported.unsupported);
+    imported.connections = conns.into_iter().map(|c| c.connection).collect();
+    Ok(imported)
+}
+
+/// Parses the content of a keyfile.
+///
+/// Keys with an empty value are ignored.
+///
+/// * `file`: file name.
+/// * `content`: file content.
+fn parse(file: &str, content: &str) -> Result<Vec<Section>, ImportError> {
+    let mut sections: Vec<Section> = vec![];
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push(Section {
+                name: name.trim().to_string(),
+                keys: vec![],
+            });
+            continue;
+        }
+
+        let (Some(section), Some((key, value))) = (sections.last_mut(), line.split_once('='))
+        else {
+            return Err(ImportError::Keyfile(
+                file.to_string(),
+                format!("unexpected line '{line}'"),
+            ));
+        };
+
+        let value = value.trim();
+        if !value.is_empty() {
+            section
+                .keys
+                .push((key.trim().to_string(), value.to_string()));
+        }
+    }
+
+    Ok(sections)
+}
+
+/// Adds the ports to their controllers.
+///
+/// The controller can be referenced by its UUID, its interface name or its ID.
+///
+/// * `conns`: imported connections.
+/// * `unsupported`: where to report the controllers that could not be found.
+fn set_controllers(conns: &mut [KeyfileConnection], unsupported: &mut Vec<String>) {
+    for i in 0..conns.len() {
+        let Some(reference) = conns[i].controller.clone() else {
+            continue;
+        };
+        let port = &conns[i].connection;
+        let port_name = port.interface.clone().unwrap_or_else(|| port.id.clone());
+        let file = conns[i].file.clone();
+
+        let controller = conns.iter_mut().find(|c| {
+            c.uuid.as_ref() == Some(&reference)
+                || c.connection.interface.as_ref() == Some(&reference)
+                || c.connection.id == reference
+        });
+
+        let ports = controller.and_then(|c| {
+            let conn = &mut c.connection;
+            if let Some(bond) = conn.bond.as_mut() {
+                Some(&mut bond.ports)
+            } else if let Some(bridge) = conn.bridge.as_mut() {
+                Some(&mut bridge.ports)
+            } else {
+                conn.vrf.as_mut().map(|v| &mut v.ports)
+            }
+        });
+
+        match ports {
+            Some(ports) => ports.push(port_name),
+            None => unsupported.push(format!("{file}: controller '{reference}'")),
+        }
+    }
+}
+
+/// Splits a list of values (e.g., "8.8.8.8;1.1.1.1;").
+///
+/// * `value`: value to split.
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(';').map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Converts the content of a keyfile into connection settings.
+struct Reader<'a> {
+    file: &'a str,
+    unsupported: &'a mut Vec<String>,
+}
+
+impl Reader<'_> {
+    /// Reads the connection from the given sections.
+    ///
+    /// It returns `None` if the connection type is not supported.
+    ///
+    /// * `sections`: keyfile sections.
+    fn read(&mut self, sections: &[Section]) -> Result<Option<KeyfileConnection>, ImportError> {
+        let Some(section) = sections.iter().find(|s| s.name == "connection") else {
+            return Err(self.error("missing [connection] section"));
+        };
+
+        let mut conn = NetworkConnection::default();
+        let mut kind = None;
+        let mut uuid = None;
+        let mut controller = None;
+
+        for (key, value) in &section.keys {
+            match key.as_str() {
+                "id" => conn.id = value.clone(),
+                "uuid" => uuid = Some(value.clone()),
+                "type" => kind = Some(value.as_str()),
+                "interface-name" => conn.interface = Some(value.clone()),
+                "autoconnect" => conn.autoconnect = Some(self.parse_bool(section, key, value)?),
+                "master" | "controller" => controller = Some(value.clone()),
+                key if IGNORED_CONNECTION_KEYS.contains(&key) => {}
+                _ => self.unsupported(section, key),
+            }
+        }
+
+        if conn.id.is_empty() {
+            conn.id = self.file.trim_end_matches(".nmconnection").to_string();
+        }
+
+        match kind {
+            Some("ethernet" | "802-3-ethernet") => {}
+            Some("wifi" | "802-11-wireless") => conn.wireless = Some(WirelessSettings::default()),
+            Some("bond") => conn.bond = Some(BondSettings::default()),
+            Some("bridge") => conn.bridge = Some(BridgeSettings::default()),
+            Some("vlan") => {
+                conn.vlan = Some(VlanSettings {
+                    parent: String::new(),
+                    id: 0,
+                    protocol: None,
+                    flags: None,
+                })
+            }
+            Some("vrf") => conn.vrf = Some(VrfSettings::default()),
+            Some("macvlan") => conn.macvlan = Some(MacvlanSettings::default()),
+            Some(other) => {
+                self.unsupported
+                    .push(format!("{}: connection type '{}'", self.file, other));
+                return Ok(None);
+            }
+            None => return Err(self.error("missing connection type")),
+        }
+
+        for section in sections {
+            match section.name.as_str() {
+                "connection" => {}
+                "ethernet" | "802-3-ethernet" => self.read_ethernet(&mut conn, section)?,
+                "wifi" | "802-11-wireless" => self.read_wireless(&mut conn, section)?,
+                "wifi-security" | "802-11-wireless-security" => {
+                    self.read_wireless_security(&mut conn, section)
+                }
+                "bond" => self.read_bond(&mut conn, section),
+                "bridge" => self.read_bridge(&mut conn, section)?,
+                "vlan" => self.read_vlan(&mut conn, section)?,
+                "vrf" => self.read_vrf(&mut conn, section)?,
+                "macvlan" => self.read_macvlan(&mut conn, section),
+                "ipv4" | "ipv6" => self.read_ip(&mut conn, section)?,
+                "match" => self.read_match(&mut conn, section),
+                _ => self.unsupported_section(section),
+            }
+        }
+
+        Ok(Some(KeyfileConnection {
+            file: self.file.to_string(),
+            uuid,
+            controller,
+            connection: conn,
+        }))
+    }
+
+    /// Reads the settings which are common to all the devices.
+    ///
+    /// It returns false if the key is unknown.
+    fn read_device_key(
+        &self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+        key: &str,
+        value: &str,
+    ) -> Result<bool, ImportError> {
+        match key {
+            "mac-address" => conn.mac_address = Some(value.to_string()),
+            "cloned-mac-address" => conn.custom_mac_address = Some(value.to_string()),
+            "mtu" => conn.mtu = self.parse(section, key, value)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn read_ethernet(
+        &mut self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+    ) -> Result<(), ImportError> {
+        for (key, value) in &section.keys {
+            if !self.read_device_key(conn, section, key, value)? {
+                self.unsupported(section, key);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_wireless(
+        &mut self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+    ) -> Result<(), ImportError> {
+        for (key, value) in &section.keys {
+            if self.read_device_key(conn, section, key, value)? {
+                continue;
+            }
+
+            let Some(wireless) = conn.wireless.as_mut() else {
+                self.unsupported(section, key);
+                continue;
+            };
+
+            match key.as_str() {
+                "ssid" => wireless.ssid = value.clone(),
+                "mode" => wireless.mode = value.clone(),
+                "band" => wireless.band = Some(value.clone()),
+                "bssid" => wireless.bssid = Some(value.clone()),
+                "channel" => wireless.channel = self.parse(section, key, value)?,
+                "hidden" => wireless.hidden = self.parse_bool(section, key, value)?,
+                _ => self.unsupported(section, key),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_wireless_security(&mut self, conn: &mut NetworkConnection, section: &Section) {
+        for (key, value) in &section.keys {
+            let Some(wireless) = conn.wireless.as_mut() else {
+                self.unsupported(section, key);
+                continue;
+            };
+
+            match key.as_str() {
+                "key-mgmt" => wireless.security = value.clone(),
+                "psk" => wireless.password = Some(value.clone()),
+                _ => self.unsupported(section, key),
+            }
+        }
+    }
+
+    fn read_bond(&mut self, conn: &mut NetworkConnection, section: &Section) {
+        let Some(bond) = conn.bond.as_mut() else {
+            self.unsupported_section(section);
+            return;
+        };
+
+        let mut options = vec![];
+        for (key, value) in &section.keys {
+            if key == "mode" {
+                bond.mode = value.clone();
+            } else {
+                options.push(format!("{key}={value}"));
+            }
+        }
+
+        if !options.is_empty() {
+            bond.options = Some(options.join(" "));
+        }
+    }
+
+    fn read_bridge(
+        &mut self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+    ) -> Result<(), ImportError> {
+        let Some(bridge) = conn.bridge.as_mut() else {
+            self.unsupported_section(section);
+            return Ok(());
+        };
+
+        for (key, value) in &section.keys {
+            match key.as_str() {
+                "stp" => bridge.stp = Some(self.parse_bool(section, key, value)?),
+                "priority" => bridge.priority = Some(self.parse(section, key, value)?),
+                "forward-delay" => bridge.forward_delay = Some(self.parse(section, key, value)?),
+                "hello-time" => bridge.hello_time = Some(self.parse(section, key, value)?),
+                "max-age" => bridge.max_age = Some(self.parse(section, key, value)?),
+                _ => self.unsupported(section, key),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_vlan(
+        &mut self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+    ) -> Result<(), ImportError> {
+        let Some(vlan) = conn.vlan.as_mut() else {
+            self.unsupported_section(section);
+            return Ok(());
+        };
+
+        for (key, value) in &section.keys {
+            match key.as_str() {
+                "id" => vlan.id = self.parse(section, key, value)?,
+                "parent" => vlan.parent = value.clone(),
+                "protocol" => vlan.protocol = Some(value.clone()),
+                _ => self.unsupported(section, key),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_vrf(
+        &mut self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+    ) -> Result<(), ImportError> {
+        let Some(vrf) = conn.vrf.as_mut() else {
+            self.unsupported_section(section);
+            return Ok(());
+        };
+
+        for (key, value) in &section.keys {
+            match key.as_str() {
+                "table" => vrf.table = self.parse(section, key, value)?,
+                _ => self.unsupported(section, key),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_macvlan(&mut self, conn: &mut NetworkConnection, section: &Section) {
+        let Some(macvlan) = conn.macvlan.as_mut() else {
+            self.unsupported_section(section);
+            return;
+        };
+
+        for (key, value) in &section.keys {
+            match key.as_str() {
+                "parent" => macvlan.parent = value.clone(),
+                "mode" => {
+                    let mode = match value.as_str() {
+                        "1" => "vepa",
+                        "2" => "bridge",
+                        "3" => "private",
+                        "4" => "passthru",
+                        "5" => "source",
+                        other => other,
+                    };
+                    macvlan.mode = Some(mode.to_string());
+                }
+                _ => self.unsupported(section, key),
+            }
+        }
+    }
+
+    fn read_ip(
+        &mut self,
+        conn: &mut NetworkConnection,
+        section: &Section,
+    ) -> Result<(), ImportError> {
+        let ipv6 = section.name == "ipv6";
+
+        for (key, value) in &section.keys {
+            match key.as_str() {
+                "method" if ipv6 => conn.method6 = Some(self.parse(section, key, value)?),
+                "method" => conn.method4 = Some(self.parse(section, key, value)?),
+                "gateway" => set_gateway(conn, self.parse(section, key, value)?),
+                "dns" => {
+                    for server in split_list(value) {
+                        conn.nameservers.push(self.parse(section, key, server)?);
+                    }
+                }
+                "dns-search" => conn
+                    .dns_searchlist
+                    .extend(split_list(value).map(str::to_string)),
+                "ignore-auto-dns" => {
+                    conn.ignore_auto_dns = Some(self.parse_bool(section, key, value)?)
+                }
+                key if is_address_key(key) => {
+                    for item in split_list(value) {
+                        let mut parts = item.split(',');
+                        let address = parts.next().unwrap_or_default();
+                        conn.addresses
+                            .push(self.parse::<IpInet>(section, key, address)?);
+                        if let Some(gateway) = parts.next() {
+                            set_gateway(conn, self.parse(section, key, gateway)?);
+                        }
+                    }
+                }
+                _ => self.unsupported(section, key),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_match(&mut self, conn: &mut NetworkConnection, section: &Section) {
+        let mut settings = MatchSettings::default();
+        for (key, value) in &section.keys {
+            let values = split_list(value).map(str::to_string);
+            match key.as_str() {
+                "interface-name" => settings.interface.extend(values),
+                "driver" => settings.driver.extend(values),
+                "path" => settings.path.extend(values),
+                "kernel-command-line" => settings.kernel.extend(values),
+                _ => self.unsupported(section, key),
+            }
+        }
+
+        if !settings.is_empty() {
+            conn.match_settings = Some(settings);
+        }
+    }
+
+    fn parse<T: FromStr>(
+        &self,
+        section: &Section,
+        key: &str,
+        value: &str,
+    ) -> Result<T, ImportError> {
+        value
+            .parse()
+            .map_err(|_| self.invalid_value(section, key, value))
+    }
+
+    fn parse_bool(&self, section: &Section, key: &str, value: &str) -> Result<bool, ImportError> {
+        match value {
+            "true" | "yes" | "1" => Ok(true),
+            "false" | "no" | "0" => Ok(false),
+            _ => Err(self.invalid_value(section, key, value)),
+        }
+    }
+
+    fn unsupported(&mut self, section: &Section, key: &str) {
+        self.unsupported
+            .push(format!("{}: {}.{}", self.file, section.name, key));
+    }
+
+    fn unsupported_section(&mut self, section: &Section) {
+        for (key, _) in &section.keys {
+            self.unsupported(section, key);
+        }
+    }
+
+    fn invalid_value(&self, section: &Section, key: &str, value: &str) -> ImportError {
+        self.error(&format!(
+            "invalid value '{}' for {}.{}",
+            value, section.name, key
+        ))
+    }
+
+    fn error(&self, message: &str) -> ImportError {
+        ImportError::Keyfile(self.file.to_string(), message.to_string())
+    }
+}
+
+/// Sets the IPv4 or the IPv6 gateway, depending on the address family.
+///
+/// * `conn`: connection to update.
+/// * `gateway`: gateway address.
+fn set_gateway(conn: &mut NetworkConnection, gateway: IpAddr) {
+    if gateway.is_ipv4() {
+        conn.gateway4 = Some(gateway);
+    } else {
+        conn.gateway6 = Some(gateway);
+    }
+}
+
+/// Whether the key contains addresses ("addresses", "address1", "address2", etc.).
+///
+/// * `key`: key to check.
+fn is_address_key(key: &str) -> bool {
+    key == "addresses"
+        || key
+            .strip_prefix("address")
+            .is_some_and(|n| n.parse::<u32>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Ipv4Method, Ipv6Method};
+
+    #[test]
+    fn test_import_ethernet() {
+        let content = r#"
+[connection]
+id=Wired connection 1
+uuid=1f8cf1b0-3a1d-4dc6-9d53-4fb1d3d1d0a4
+type=ethernet
+interface-name=eth0
+autoconnect=false
+timestamp=1700000000
+zone=public
+
+[ethernet]
+mtu=9000
+
+[ipv4]
+method=manual
+address1=192.168.1.10/24,192.168.1.1
+dns=8.8.8.8;1.1.1.1;
+dns-search=example.com;
+route1=10.0.0.0/8,192.168.1.254
+
+[ipv6]
+method=auto
+addr-gen-mode=default
+
+[proxy]
+"#;
+        let keyfiles = vec![("eth0.nmconnection".to_string(), content.to_string())];
+        let imported = import(&keyfiles).unwrap();
+
+        let conn = imported.connections.first().unwrap();
+        assert_eq!(conn.id, "Wired connection 1");
+        assert_eq!(conn.interface, Some("eth0".to_string()));
+        assert_eq!(conn.autoconnect, Some(false));
+        assert_eq!(conn.mtu, 9000);
+        assert_eq!(conn.method4, Some(Ipv4Method::Manual));
+        assert_eq!(conn.method6, Some(Ipv6Method::Auto));
+        assert_eq!(conn.addresses, vec!["192.168.1.10/24".parse().unwrap()]);
+        assert_eq!(conn.gateway4, Some("192.168.1.1".parse().unwrap()));
+        assert_eq!(
+            conn.nameservers,
+            vec![
+                "8.8.8.8".parse::<IpAddr>().unwrap(),
+                "1.1.1.1".parse::<IpAddr>().unwrap()
+            ]
+        );
+        assert_eq!(conn.dns_searchlist, vec!["example.com".to_string()]);
+        assert_eq!(
+            imported.unsupported,
+            vec![
+                "eth0.nmconnection: connection.zone".to_string(),
+                "eth0.nmconnection: ipv4.route1".to_string(),
+                "eth0.nmconnection: ipv6.addr-gen-mode".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_bond() {
+        let bond = r#"
+[connection]
+id=bond0
+uuid=8a4ae3a0-3f42-4d7a-a8d5-0e8bd2ee4a8e
+type=bond
+interface-name=bond0
+
+[bond]
+mode=active-backup
+miimon=100
+"#;
+        let port = r#"
+[connection]
+id=bond0-port-eth1
+type=ethernet
+interface-name=eth1
+controller=8a4ae3a0-3f42-4d7a-a8d5-0e8bd2ee4a8e
+port-type=bond
+"#;
+        let keyfiles = vec![
+            ("bond0.nmconnection".to_string(), bond.to_string()),
+            ("eth1.nmconnection".to_string(), port.to_string()),
+        ];
+        let imported = import(&keyfiles).unwrap();
+        assert!(imported.unsupported.is_empty());
+
+        let bond = imported.connections.first().unwrap().bond.as_ref().unwrap();
+        assert_eq!(bond.mode, "active-backup");
+        assert_eq!(bond.options, Some("miimon=100".to_string()));
+        assert_eq!(bond.ports, vec!["eth1".to_string()]);
+    }
+
+    #[test]
+    fn test_import_unsupported_type() {
+        let content = r#"
+[connection]
+id=tun0
+type=tun
+"#;
+        let keyfiles = vec![("tun0.nmconnection".to_string(), content.to_string())];
+        let imported = import(&keyfiles).unwrap();
+        assert!(imported.connections.is_empty());
+        assert_eq!(
+            imported.unsupported,
+            vec!["tun0.nmconnection: connection type 'tun'".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_import_invalid_keyfile() {
+        let keyfiles = vec![("eth0.nmconnection".to_string(), "id=eth0".to_string())];
+        let error = import(&keyfiles).unwrap_err();
+        assert!(matches!(error, ImportError::Keyfile(_, _)));
+    }
+}
@@ -0,0 +1,572 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Conversion of nmstate YAML documents into connection settings.
+//!
+//! Only a subset of the nmstate schema is supported. Any other field is reported as unsupported.
+
+use super::{ImportError, ImportedConfig};
+use crate::types::{
+    BondSettings, BridgeSettings, Ipv4Method, Ipv6Method, NetworkConnection, Status, VlanSettings,
+};
+use cidr::IpInet;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::{collections::BTreeMap, net::IpAddr};
+
+/// Fields which are not explicitly handled.
+type Other = BTreeMap<String, Value>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct State {
+    #[serde(default)]
+    interfaces: Vec<Interface>,
+    routes: Option<Routes>,
+    dns_resolver: Option<DnsResolver>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Interface {
+    name: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    state: Option<String>,
+    mtu: Option<u32>,
+    mac_address: Option<String>,
+    ipv4: Option<IpSettings>,
+    ipv6: Option<IpSettings>,
+    link_aggregation: Option<LinkAggregation>,
+    bridge: Option<Bridge>,
+    vlan: Option<Vlan>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct IpSettings {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    dhcp: bool,
+    #[serde(default)]
+    autoconf: bool,
+    #[serde(default)]
+    address: Vec<Address>,
+    auto_dns: Option<bool>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Address {
+    ip: IpAddr,
+    prefix_length: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct LinkAggregation {
+    mode: Option<String>,
+    #[serde(default, alias = "slaves")]
+    port: Vec<String>,
+    #[serde(default)]
+    options: BTreeMap<String, Value>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Bridge {
+    #[serde(default)]
+    port: Vec<BridgePort>,
+    options: Option<BridgeOptions>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct BridgePort {
+    name: String,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct BridgeOptions {
+    stp: Option<Stp>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Stp {
+    enabled: Option<bool>,
+    priority: Option<u32>,
+    forward_delay: Option<u32>,
+    hello_time: Option<u32>,
+    max_age: Option<u32>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Vlan {
+    base_iface: String,
+    id: u32,
+    protocol: Option<String>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Routes {
+    #[serde(default)]
+    config: Vec<Route>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Route {
+    destination: String,
+    next_hop_address: Option<IpAddr>,
+    next_hop_interface: Option<String>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DnsResolver {
+    config: Option<DnsConfig>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DnsConfig {
+    #[serde(default)]
+    server: Vec<IpAddr>,
+    #[serde(default)]
+    search: Vec<String>,
+    #[serde(flatten)]
+    other: Other,
+}
+
+/// Imports the given nmstate document.
+///
+/// * `content`: YAML document.
+pub fn import(content: &str) -> Result<ImportedConfig, ImportError> {
+    let state: State = serde_yaml::from_str(content)?;
+    let mut imported = ImportedConfig::default();
+    let unsupported = &mut imported.unsupported;
+    report(unsupported, "", &state.other);
+
+    for iface in state.interfaces {
+        if let Some(conn) = import_interface(iface, unsupported) {
+            imported.connections.push(conn);
+        }
+    }
+
+    if let Some(mut routes) = state.routes {
+        // the running routes are included when the document is generated by "nmstatectl show"
+        routes.other.remove("running");
+        report(unsupported, "routes", &routes.other);
+        for route in routes.config {
+            import_route(route, &mut imported.connections, unsupported);
+        }
+    }
+
+    if let Some(mut dns) = state.dns_resolver {
+        dns.other.remove("running");
+        report(unsupported, "dns-resolver", &dns.other);
+        if let Some(config) = dns.config {
+            import_dns(config, &mut imported.connections, unsupported);
+        }
+    }
+
+    Ok(imported)
+}
+
+/// Converts an interface into a connection.
+///
+/// It returns `None` if the interface type is not supported.
+///
+/// * `iface`: nmstate interface.
+/// * `unsupported`: where to report the unsupported fields.
+fn import_interface(iface: Interface, unsupported: &mut Vec<String>) -> Option<NetworkConnection> {
+    let path = format!("interfaces[{}]", iface.name);
+    let mut conn = NetworkConnection {
+        id: iface.name.clone(),
+        interface: Some(iface.name.clone()),
+        mac_address: iface.mac_address,
+        mtu: iface.mtu.unwrap_or_default(),
+        ..Default::default()
+    };
+
+    match iface.kind.as_deref() {
+        None | Some("ethernet") => {}
+        Some("bond") => conn.bond = Some(BondSettings::default()),
+        Some("linux-bridge") => conn.bridge = Some(BridgeSettings::default()),
+        Some("vlan") => {}
+        // the loopback interface is always kept as it is
+        Some("loopback") => return None,
+        Some(other) => {
+            unsupported.push(format!("{path}: type '{other}'"));
+            return None;
+        }
+    }
+
+    match iface.state.as_deref() {
+        None => {}
+        Some("up") => conn.status = Some(Status::Up),
+        Some("down") => conn.status = Some(Status::Down),
+        Some("absent") => conn.status = Some(Status::Removed),
+        Some(other) => unsupported.push(format!("{path}.state: '{other}'")),
+    }
+
+    if let Some(ipv4) = iface.ipv4 {
+        report(unsupported, &format!("{path}.ipv4"), &ipv4.other);
+        conn.method4 = Some(if !ipv4.enabled {
+            Ipv4Method::Disabled
+        } else if ipv4.dhcp {
+            Ipv4Method::Auto
+        } else if !ipv4.address.is_empty() {
+            Ipv4Method::Manual
+        } else {
+            Ipv4Method::Disabled
+        });
+        import_ip_settings(&mut conn, &ipv4, &path, unsupported);
+    }
+
+    if let Some(ipv6) = iface.ipv6 {
+        report(unsupported, &format!("{path}.ipv6"), &ipv6.other);
+        conn.method6 = Some(if !ipv6.enabled {
+            Ipv6Method::Disabled
+        } else if ipv6.dhcp || ipv6.autoconf {
+            Ipv6Method::Auto
+        } else if !ipv6.address.is_empty() {
+            Ipv6Method::Manual
+        } else {
+            Ipv6Method::LinkLocal
+        });
+        import_ip_settings(&mut conn, &ipv6, &path, unsupported);
+    }
+
+    if let Some(aggregation) = iface.link_aggregation {
+        let path = format!("{path}.link-aggregation");
+        report(unsupported, &path, &aggregation.other);
+        match conn.bond.as_mut() {
+            Some(bond) => {
+                if let Some(mode) = aggregation.mode {
+                    bond.mode = mode;
+                }
+                bond.ports = aggregation.port;
+                let mut options = vec![];
+                for (key, value) in &aggregation.options {
+                    match scalar_to_string(value) {
+                        Some(value) => options.push(format!("{key}={value}")),
+                        None => unsupported.push(format!("{path}.options.{key}")),
+                    }
+                }
+                if !options.is_empty() {
+                    bond.options = Some(options.join(" "));
+                }
+            }
+            None => unsupported.push(path),
+        }
+    }
+
+    if let Some(bridge_settings) = iface.bridge {
+        let path = format!("{path}.bridge");
+        report(unsupported, &path, &bridge_settings.other);
+        match conn.bridge.as_mut() {
+            Some(bridge) => {
+                for port in bridge_settings.port {
+                    report(
+                        unsupported,
+                        &format!("{path}.port[{}]", port.name),
+                        &port.other,
+                    );
+                    bridge.ports.push(port.name);
+                }
+
+                if let Some(options) = bridge_settings.options {
+                    report(unsupported, &format!("{path}.options"), &options.other);
+                    if let Some(stp) = options.stp {
+                        report(unsupported, &format!("{path}.options.stp"), &stp.other);
+                        bridge.stp = stp.enabled;
+                        bridge.priority = stp.priority;
+                        bridge.forward_delay = stp.forward_delay;
+                        bridge.hello_time = stp.hello_time;
+                        bridge.max_age = stp.max_age;
+                    }
+                }
+            }
+            None => unsupported.push(path),
+        }
+    }
+
+    if let Some(vlan) = iface.vlan {
+        report(unsupported, &format!("{path}.vlan"), &vlan.other);
+        conn.vlan = Some(VlanSettings {
+            parent: vlan.base_iface,
+            id: vlan.id,
+            protocol: vlan.protocol,
+            flags: None,
+        });
+    }
+
+    report(unsupported, &path, &iface.other);
+    Some(conn)
+}
+
+/// Imports the addresses and the DNS settings.
+///
+/// * `conn`: connection to update.
+/// * `settings`: nmstate IP settings.
+/// * `path`: path of the interface (used to report errors).
+/// * `unsupported`: where to report the unsupported fields.
+fn import_ip_settings(
+    conn: &mut NetworkConnection,
+    settings: &IpSettings,
+    path: &str,
+    unsupported: &mut Vec<String>,
+) {
+    for address in &settings.address {
+        match IpInet::new(address.ip, address.prefix_length) {
+            Ok(inet) => conn.addresses.push(inet),
+            Err(_) => unsupported.push(format!(
+                "{path}: address '{}/{}'",
+                address.ip, address.prefix_length
+            )),
+        }
+    }
+
+    if settings.auto_dns == Some(false) {
+        conn.ignore_auto_dns = Some(true);
+    }
+}
+
+/// Imports a route.
+///
+/// Only the default routes are supported and they are converted into gateways.
+///
+/// * `route`: nmstate route.
+/// * `conns`: imported connections.
+/// * `unsupported`: where to report the unsupported routes.
+fn import_route(route: Route, conns: &mut [NetworkConnection], unsupported: &mut Vec<String>) {
+    let path = format!("routes.config[{}]", route.destination);
+    let is_default = route.destination == "0.0.0.0/0" || route.destination == "::/0";
+
+    let conn = route
+        .next_hop_interface
+        .as_ref()
+        .and_then(|name| conns.iter_mut().find(|c| &c.id == name));
+
+    match (is_default, route.next_hop_address, conn) {
+        (true, Some(gateway), Some(conn)) => {
+            report(unsupported, &path, &route.other);
+            if gateway.is_ipv4() {
+                conn.gateway4 = Some(gateway);
+            } else {
+                conn.gateway6 = Some(gateway);
+            }
+        }
+        _ => unsupported.push(path),
+    }
+}
+
+/// Imports the DNS settings.
+///
+/// nmstate defines them globally, so they are added to all the connections that have the IP
+/// configuration enabled.
+///
+/// * `config`: nmstate DNS settings.
+/// * `conns`: imported connections.
+/// * `unsupported`: where to report the unsupported fields.
+fn import_dns(config: DnsConfig, conns: &mut [NetworkConnection], unsupported: &mut Vec<String>) {
+    report(unsupported, "dns-resolver.config", &config.other);
+    if config.server.is_empty() && config.search.is_empty() {
+        return;
+    }
+
+    let targets: Vec<_> = conns
+        .iter_mut()
+        .filter(|c| {
+            c.method4.is_some_and(|m| m != Ipv4Method::Disabled)
+                || c.method6.is_some_and(|m| m != Ipv6Method::Disabled)
+        })
+        .collect();
+
+    if targets.is_empty() {
+        unsupported.push("dns-resolver.config".to_string());
+        return;
+    }
+
+    for conn in targets {
+        conn.nameservers.extend(config.server.iter().copied());
+        conn.dns_searchlist.extend(config.search.iter().cloned());
+    }
+}
+
+/// Reports the given fields as unsupported.
+///
+/// * `unsupported`: where to report the fields.
+/// * `path`: path of the object containing the fields.
+/// * `other`: fields to report.
+fn report(unsupported: &mut Vec<String>, path: &str, other: &Other) {
+    for key in other.keys() {
+        if path.is_empty() {
+            unsupported.push(key.clone());
+        } else {
+            unsupported.push(format!("{path}.{key}"));
+        }
+    }
+}
+
+/// Converts a scalar value into a string.
+///
+/// * `value`: YAML value.
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_nmstate() {
+        let content = r#"
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: false
+  ipv6:
+    enabled: false
+- name: bond0
+  type: bond
+  state: up
+  mtu: 9000
+  ethtool:
+    feature:
+      tx-checksum-ip-generic: true
+  ipv4:
+    enabled: true
+    dhcp: false
+    dhcp-client-id: mac
+    address:
+    - ip: 192.0.2.251
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: true
+    autoconf: true
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 140
+    port:
+    - eth1
+- name: lo
+  type: loopback
+- name: br-ex
+  type: ovs-bridge
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-address: 192.0.2.1
+    next-hop-interface: bond0
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: bond0
+dns-resolver:
+  config:
+    search:
+    - example.com
+    server:
+    - 192.0.2.1
+"#;
+        let imported = import(content).unwrap();
+        assert_eq!(imported.connections.len(), 2);
+
+        let eth1 = &imported.connections[0];
+        assert_eq!(eth1.id, "eth1");
+        assert_eq!(eth1.method4, Some(Ipv4Method::Disabled));
+        assert!(eth1.nameservers.is_empty());
+
+        let bond0 = &imported.connections[1];
+        assert_eq!(bond0.interface, Some("bond0".to_string()));
+        assert_eq!(bond0.status, Some(Status::Up));
+        assert_eq!(bond0.mtu, 9000);
+        assert_eq!(bond0.method4, Some(Ipv4Method::Manual));
+        assert_eq!(bond0.method6, Some(Ipv6Method::Auto));
+        assert_eq!(bond0.addresses, vec!["192.0.2.251/24".parse().unwrap()]);
+        assert_eq!(bond0.gateway4, Some("192.0.2.1".parse().unwrap()));
+        assert_eq!(
+            bond0.nameservers,
+            vec!["192.0.2.1".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(bond0.dns_searchlist, vec!["example.com".to_string()]);
+
+        let bond = bond0.bond.as_ref().unwrap();
+        assert_eq!(bond.mode, "active-backup");
+        assert_eq!(bond.options, Some("miimon=140".to_string()));
+        assert_eq!(bond.ports, vec!["eth1".to_string()]);
+
+        assert_eq!(
+            imported.unsupported,
+            vec![
+                "interfaces[bond0].ipv4.dhcp-client-id".to_string(),
+                "interfaces[bond0].ethtool".to_string(),
+                "interfaces[br-ex]: type 'ovs-bridge'".to_string(),
+                "routes.config[198.51.100.0/24]".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_invalid_nmstate() {
+        let error = import("interfaces: foo").unwrap_err();
+        assert!(matches!(error, ImportError::Nmstate(_)));
+    }
+}
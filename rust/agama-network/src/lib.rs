@@ -24,14 +24,19 @@
 
 pub mod action;
 pub mod adapter;
+mod diagnostics;
 pub mod error;
+mod import;
 pub mod model;
 mod nm;
+mod probe;
 mod service;
 pub mod types;
+mod udev;
 
 pub use action::Action;
 pub use adapter::{Adapter, NetworkAdapterError};
+pub use diagnostics::{diagnose, diagnose_all, DiagnosticsTarget};
 pub use model::NetworkState;
 pub use nm::NetworkManagerAdapter;
 pub use service::{NetworkSystemClient, NetworkSystemError, Service, Starter};
@@ -33,6 +33,7 @@ use std::{
     collections::HashMap,
     default::Default,
     fmt,
+    net::IpAddr,
     path::{Path, PathBuf},
     str::{self, FromStr},
 };
@@ -165,6 +166,16 @@ impl NetworkState {
     /// * `state`: The new network state to sync from.
     pub fn sync_from(&mut self, mut state: NetworkState) -> bool {
         state.user_config = self.user_config.clone();
+        // The network service cannot tell the target-only connections from the ones used in
+        // both systems, so the persistence is kept from the current state.
+        for conn in state.connections.iter_mut() {
+            let target_only = self
+                .get_connection_by_uuid(conn.uuid)
+                .is_some_and(|c| c.is_target_only());
+            if target_only && conn.is_persistent() {
+                conn.persistence = Persistence::Target;
+            }
+        }
         if *self != state {
             *self = state;
             true
@@ -193,6 +204,7 @@ impl NetworkState {
         if self.get_connection(&conn.id).is_some() {
             return Err(NetworkStateError::ConnectionExists(conn.id));
         }
+        conn.validate()?;
         self.connections.push(conn);
 
         Ok(())
@@ -208,26 +220,32 @@ impl NetworkState {
             return Ok(());
         }
 
-        let to_persist: Vec<&Connection> =
-            self.connections.iter().filter(|c| c.persistent).collect();
+        let to_persist: Vec<&Connection> = self
+            .connections
+            .iter()
+            .filter(|c| c.is_persistent())
+            .collect();
         if !to_persist.is_empty() {
             return Ok(());
         }
 
         for conn in self.connections.iter_mut() {
-            conn.persistent = true;
+            conn.persistence = Persistence::Both;
             conn.keep_status();
         }
 
         Ok(())
     }
 
+    /// Copies the network configuration to the installed system.
+    ///
+    /// The connections used only in the installer are not copied.
     pub async fn install(&self) -> Result<(), NetworkStateError> {
         const CONNECTIONS_PATH: &str = "/etc/NetworkManager/system-connections";
         let from = PathBuf::from(CONNECTIONS_PATH);
         let to = PathBuf::from(self.target_dir()).join(CONNECTIONS_PATH.trim_start_matches('/'));
 
-        self.copy_files(&from, &to)?;
+        self.copy_files(&from, &to, |path| self.is_installable_keyfile(path))?;
 
         const NETWORKD_PATH: &str = "/run/agama/systemd/network";
         const TARGET_NETWORKD_PATH: &str = "/etc/systemd/network";
@@ -235,7 +253,7 @@ impl NetworkState {
         let to =
             PathBuf::from(self.target_dir()).join(TARGET_NETWORKD_PATH.trim_start_matches('/'));
 
-        self.copy_files(&from, &to)?;
+        self.copy_files(&from, &to, |_| true)?;
 
         self.enable_service(self.target_dir()).await
     }
@@ -261,11 +279,35 @@ impl NetworkState {
         Ok(())
     }
 
+    /// Determines whether a NetworkManager keyfile should be copied to the installed system.
+    ///
+    /// The files of the connections used only in the installer (or removed) are skipped. The
+    /// rest of files, including the ones not belonging to a known connection, are copied.
+    ///
+    /// * `path`: keyfile path.
+    fn is_installable_keyfile(&self, path: &Path) -> bool {
+        let Some(uuid) = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|c| keyfile_uuid(&c))
+        else {
+            return true;
+        };
+
+        self.get_connection_by_uuid(uuid)
+            .is_none_or(|c| c.is_persistent() && !c.is_removed())
+    }
+
     /// Copies the network configuration files from one directory to another.
     ///
     /// * `from`: The source directory.
     /// * `to`: The destination directory.
-    fn copy_files(&self, from: &Path, to: &Path) -> Result<(), NetworkStateError> {
+    /// * `filter`: Determines whether a file should be copied.
+    fn copy_files(
+        &self,
+        from: &Path,
+        to: &Path,
+        filter: impl Fn(&Path) -> bool,
+    ) -> Result<(), NetworkStateError> {
         if !from.exists() {
             return Ok(());
         }
@@ -280,6 +322,10 @@ impl NetworkState {
             let entry = entry.map_err(|e| NetworkStateError::IoError(e.to_string()))?;
             let path = entry.path();
             if path.is_file() {
+                if !filter(&path) {
+                    tracing::info!("Skipping {:?}, it is not used in the target system", &path);
+                    continue;
+                }
                 if let Some(file_name) = path.file_name() {
                     let dest = to.join(file_name);
                     if let Err(e) = std::fs::copy(&path, &dest) {
@@ -313,7 +359,9 @@ impl NetworkState {
                 conn.uuid = current_conn.uuid;
                 if matches!(
                     conn.config,
-                    ConnectionConfig::Bridge(_) | ConnectionConfig::Bond(_)
+                    ConnectionConfig::Bridge(_)
+                        | ConnectionConfig::Bond(_)
+                        | ConnectionConfig::Vrf(_)
                 ) {
                     controller_uuids.insert(old_uuid, conn.uuid);
                 } else {
@@ -372,7 +420,8 @@ impl NetworkState {
                         .bridge
                         .as_ref()
                         .map(|b| &b.ports)
-                        .or_else(|| conn.bond.as_ref().map(|b| &b.ports));
+                        .or_else(|| conn.bond.as_ref().map(|b| &b.ports))
+                        .or_else(|| conn.vrf.as_ref().map(|v| &v.ports));
 
                     if let Some(ports) = ports {
                         if let Some(controller) = self.get_connection(&conn.id).cloned() {
@@ -444,6 +493,7 @@ impl NetworkState {
     ///
     /// * `conn`: The connection to update.
     pub fn update_connection(&mut self, conn: Connection) -> Result<(), NetworkStateError> {
+        conn.validate()?;
         let Some(old_conn) = self.get_connection_by_uuid_mut(conn.uuid) else {
             return Err(NetworkStateError::UnknownConnection(conn.uuid.to_string()));
         };
@@ -545,7 +595,7 @@ impl NetworkState {
         ports: Vec<String>,
     ) -> Result<(), NetworkStateError> {
         match &controller.config {
-            ConnectionConfig::Bond(_) | ConnectionConfig::Bridge(_) => {
+            ConnectionConfig::Bond(_) | ConnectionConfig::Bridge(_) | ConnectionConfig::Vrf(_) => {
                 let mut controlled = vec![];
                 for port in ports {
                     let connection = self
@@ -577,6 +627,204 @@ impl NetworkState {
             )),
         }
     }
+
+    /// Returns the changes needed to go from this state to the given one.
+    ///
+    /// Connections are identified by their UUID. A connection which is marked as removed (or
+    /// whose controller is marked as removed) is only reported if it exists in this state.
+    ///
+    /// * `other`: target network state.
+    pub fn diff(&self, other: &NetworkState) -> NetworkStateDiff {
+        let mut diff = NetworkStateDiff {
+            general_state: self.general_state != other.general_state,
+            ..Default::default()
+        };
+
+        for conn in &other.connections {
+            let is_removed = conn.is_removed()
+                || conn
+                    .controller
+                    .and_then(|uuid| other.get_connection_by_uuid(uuid))
+                    .is_some_and(|c| c.is_removed());
+
+            match self.get_connection_by_uuid(conn.uuid) {
+                Some(_) if is_removed => diff.removed.push(conn.id.clone()),
+                Some(old_conn) if old_conn != conn => diff.updated.push(conn.id.clone()),
+                None if !is_removed => diff.added.push(conn.id.clone()),
+                _ => {}
+            }
+        }
+
+        diff
+    }
+
+    /// Returns the gateways of the connections that should be up.
+    pub fn gateways(&self) -> Vec<IpAddr> {
+        let mut gateways = vec![];
+        for conn in &self.connections {
+            if conn.is_removed() || conn.is_down() || conn.is_target_only() {
+                continue;
+            }
+
+            let ip_config = &conn.ip_config;
+            for gateway in [ip_config.gateway4, ip_config.gateway6]
+                .into_iter()
+                .flatten()
+            {
+                if !gateways.contains(&gateway) {
+                    gateways.push(gateway);
+                }
+            }
+        }
+        gateways
+    }
+
+    /// Binds the connections to their devices when the interface names differ on the target.
+    ///
+    /// The interface names in the installer might not match the ones predicted for the installed
+    /// system (e.g., because of `net.ifnames` or biosdevname). In that case, the connections which
+    /// are bound to an interface name are rewritten to match the device by its MAC address or,
+    /// if it is not known, by its persistent path. The connections using one of those interfaces as
+    /// their parent (e.g., VLANs) are rewritten to refer to the bound connection by its UUID, which
+    /// works in both systems. It returns the applied renames.
+    pub fn bind_to_target_names(&mut self) -> Vec<InterfaceRename> {
+        let mut renames = vec![];
+        let mut parents: HashMap<String, Uuid> = HashMap::new();
+
+        for conn in self.connections.iter_mut() {
+            if conn.is_removed()
+                || !conn.is_persistent()
+                || !matches!(
+                    conn.config,
+                    ConnectionConfig::Ethernet | ConnectionConfig::Wireless(_)
+                )
+            {
+                continue;
+            }
+
+            let Some(name) = conn.interface.as_ref() else {
+                continue;
+            };
+
+            let Some(device) = self.devices.iter().find(|d| &d.name == name) else {
+                continue;
+            };
+
+            let Some(target_name) = device.target_name.as_ref().filter(|t| *t != name) else {
+                continue;
+            };
+
+            let binding = if let MacAddress::MacAddress(mac) = device.mac_address {
+                conn.mac_address = Some(mac);
+                InterfaceBinding::MacAddress(mac.to_string())
+            } else if let Some(path) = &device.path {
+                conn.match_config.path = vec![path.clone()];
+                InterfaceBinding::Path(path.clone())
+            } else {
+                tracing::warn!(
+                    "Cannot bind the connection {} to the device {}: unknown MAC address and path",
+                    conn.id,
+                    name
+                );
+                continue;
+            };
+
+            renames.push(InterfaceRename {
+                connection: conn.id.clone(),
+                name: name.clone(),
+                target_name: target_name.clone(),
+                binding,
+            });
+            parents.insert(name.clone(), conn.uuid);
+            conn.interface = None;
+        }
+
+        for conn in self.connections.iter_mut().filter(|c| !c.is_removed()) {
+            let parent = match &mut conn.config {
+                ConnectionConfig::Vlan(config) => &mut config.parent,
+                ConnectionConfig::Macvlan(config) => &mut config.parent,
+                ConnectionConfig::Ipvlan(config) => &mut config.parent,
+                _ => continue,
+            };
+
+            if let Some(uuid) = parents.get(parent.as_str()) {
+                tracing::info!(
+                    "Referring to the parent {} of the connection {} by its UUID {}",
+                    parent,
+                    conn.id,
+                    uuid
+                );
+                *parent = uuid.to_string();
+            }
+        }
+
+        renames
+    }
+}
+
+/// How a connection is bound to its device.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterfaceBinding {
+    /// By the MAC address of the device.
+    MacAddress(String),
+    /// By the persistent path of the device.
+    Path(String),
+}
+
+impl fmt::Display for InterfaceBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MacAddress(mac) => write!(f, "MAC address {mac}"),
+            Self::Path(path) => write!(f, "path {path}"),
+        }
+    }
+}
+
+/// Connection rewritten because its interface has a different name on the target system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceRename {
+    /// Connection ID.
+    pub connection: String,
+    /// Interface name in the installer.
+    pub name: String,
+    /// Interface name predicted for the installed system.
+    pub target_name: String,
+    /// How the connection is bound to the device now.
+    pub binding: InterfaceBinding,
+}
+
+impl fmt::Display for InterfaceRename {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} (matched by {})",
+            self.connection, self.name, self.target_name, self.binding
+        )
+    }
+}
+
+/// Changes between two network states.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStateDiff {
+    /// Whether the general state changed.
+    pub general_state: bool,
+    /// IDs of the added connections.
+    pub added: Vec<String>,
+    /// IDs of the updated connections.
+    pub updated: Vec<String>,
+    /// IDs of the removed connections.
+    pub removed: Vec<String>,
+}
+
+impl NetworkStateDiff {
+    /// Whether there are no changes.
+    pub fn is_empty(&self) -> bool {
+        !self.general_state
+            && self.added.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -611,6 +859,37 @@ mod tests {
         assert_eq!(net_conn.match_settings, net_conn2.match_settings);
     }
 
+    #[test]
+    fn test_connection_wireguard_conversion() {
+        let wireguard = WireguardSettings {
+            private_key: Some("yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=".to_string()),
+            listen_port: Some(51820),
+            peers: vec![WireguardPeerSettings {
+                public_key: "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=".to_string(),
+                endpoint: Some("mirror.example.net:51820".to_string()),
+                allowed_ips: vec!["10.10.0.0/16".to_string()],
+                ..Default::default()
+            }],
+        };
+        let net_conn = NetworkConnection {
+            id: "wg0".to_string(),
+            wireguard: Some(wireguard),
+            ..Default::default()
+        };
+
+        // NetworkConnection -> Connection
+        let conn = Connection::try_from(net_conn.clone()).unwrap();
+        let ConnectionConfig::Wireguard(config) = &conn.config else {
+            panic!("Unexpected connection config: {:?}", conn.config);
+        };
+        assert_eq!(config.listen_port, Some(51820));
+        assert_eq!(config.peers[0].allowed_ips, vec!["10.10.0.0/16"]);
+
+        // Connection -> NetworkConnection
+        let net_conn2 = NetworkConnection::try_from(conn).unwrap();
+        assert_eq!(net_conn.wireguard, net_conn2.wireguard);
+    }
+
     #[test]
     fn test_add_connection() {
         let mut state = NetworkState::default();
@@ -625,6 +904,330 @@ mod tests {
         assert_eq!(found.uuid, uuid);
     }
 
+    #[test]
+    fn test_diff() {
+        let eth0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        let eth1 = Connection::new("eth1".to_string(), DeviceType::Ethernet);
+        let eth2 = Connection::new("eth2".to_string(), DeviceType::Ethernet);
+        let old_state = NetworkState::new(
+            None,
+            GeneralState::default(),
+            vec![],
+            vec![],
+            vec![eth0.clone(), eth1.clone()],
+        );
+
+        let mut new_state = old_state.clone();
+        assert!(old_state.diff(&new_state).is_empty());
+
+        let conn = new_state.get_connection_mut("eth0").unwrap();
+        conn.mtu = 9000;
+        let conn = new_state.get_connection_mut("eth1").unwrap();
+        conn.remove();
+        let mut eth3 = Connection::new("eth3".to_string(), DeviceType::Ethernet);
+        eth3.remove();
+        new_state.add_connection(eth2).unwrap();
+        new_state.connections.push(eth3);
+
+        let diff = old_state.diff(&new_state);
+        assert!(!diff.general_state);
+        assert_eq!(diff.added, vec!["eth2".to_string()]);
+        assert_eq!(diff.updated, vec!["eth0".to_string()]);
+        assert_eq!(diff.removed, vec!["eth1".to_string()]);
+    }
+
+    #[test]
+    fn test_gateways() {
+        let mut eth0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        eth0.ip_config.gateway4 = Some("192.168.1.1".parse().unwrap());
+        eth0.ip_config.gateway6 = Some("2001:db8::1".parse().unwrap());
+        let mut eth1 = Connection::new("eth1".to_string(), DeviceType::Ethernet);
+        eth1.ip_config.gateway4 = Some("10.0.0.1".parse().unwrap());
+        eth1.set_down();
+        let mut eth2 = Connection::new("eth2".to_string(), DeviceType::Ethernet);
+        eth2.ip_config.gateway4 = Some("10.0.1.1".parse().unwrap());
+        eth2.persistence = Persistence::Target;
+        let state = NetworkState::new(
+            None,
+            GeneralState::default(),
+            vec![],
+            vec![],
+            vec![eth0, eth1, eth2],
+        );
+
+        let gateways: Vec<IpAddr> = vec![
+            "192.168.1.1".parse().unwrap(),
+            "2001:db8::1".parse().unwrap(),
+        ];
+        assert_eq!(state.gateways(), gateways);
+    }
+
+    #[test]
+    fn test_bind_to_target_names() {
+        let mac = MacAddr6::from_str("52:54:00:12:34:56").unwrap();
+        let eth0_dev = Device {
+            name: "eth0".to_string(),
+            type_: DeviceType::Ethernet,
+            mac_address: MacAddress::MacAddress(mac),
+            target_name: Some("enp1s0".to_string()),
+            ..Default::default()
+        };
+        let eth1_dev = Device {
+            name: "eth1".to_string(),
+            type_: DeviceType::Ethernet,
+            path: Some("pci-0000:00:19.0".to_string()),
+            target_name: Some("enp0s25".to_string()),
+            ..Default::default()
+        };
+        let eth2_dev = Device {
+            name: "eth2".to_string(),
+            type_: DeviceType::Ethernet,
+            target_name: Some("eth2".to_string()),
+            ..Default::default()
+        };
+
+        let mut eth0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        eth0.interface = Some("eth0".to_string());
+        let mut eth1 = Connection::new("eth1".to_string(), DeviceType::Ethernet);
+        eth1.interface = Some("eth1".to_string());
+        let mut eth2 = Connection::new("eth2".to_string(), DeviceType::Ethernet);
+        eth2.interface = Some("eth2".to_string());
+        let eth0_uuid = eth0.uuid;
+        let mut vlan = Connection::new("vlan10".to_string(), DeviceType::Vlan);
+        vlan.interface = Some("eth0.10".to_string());
+        vlan.config = ConnectionConfig::Vlan(VlanConfig {
+            parent: "eth0".to_string(),
+            id: 10,
+            ..Default::default()
+        });
+        let mut vlan_eth2 = Connection::new("vlan20".to_string(), DeviceType::Vlan);
+        vlan_eth2.interface = Some("eth2.20".to_string());
+        vlan_eth2.config = ConnectionConfig::Vlan(VlanConfig {
+            parent: "eth2".to_string(),
+            id: 20,
+            ..Default::default()
+        });
+
+        let mut state = NetworkState::new(
+            None,
+            GeneralState::default(),
+            vec![],
+            vec![eth0_dev, eth1_dev, eth2_dev],
+            vec![eth0, eth1, eth2, vlan, vlan_eth2],
+        );
+
+        let renames = state.bind_to_target_names();
+        assert_eq!(
+            renames,
+            vec![
+                InterfaceRename {
+                    connection: "eth0".to_string(),
+                    name: "eth0".to_string(),
+                    target_name: "enp1s0".to_string(),
+                    binding: InterfaceBinding::MacAddress(mac.to_string()),
+                },
+                InterfaceRename {
+                    connection: "eth1".to_string(),
+                    name: "eth1".to_string(),
+                    target_name: "enp0s25".to_string(),
+                    binding: InterfaceBinding::Path("pci-0000:00:19.0".to_string()),
+                },
+            ]
+        );
+
+        let eth0 = state.get_connection("eth0").unwrap();
+        assert_eq!(eth0.interface, None);
+        assert_eq!(eth0.mac_address, Some(mac));
+
+        let eth1 = state.get_connection("eth1").unwrap();
+        assert_eq!(eth1.interface, None);
+        assert_eq!(eth1.match_config.path, vec!["pci-0000:00:19.0".to_string()]);
+
+        let eth2 = state.get_connection("eth2").unwrap();
+        assert_eq!(eth2.interface, Some("eth2".to_string()));
+
+        let vlan = state.get_connection("vlan10").unwrap();
+        assert_eq!(vlan.interface, Some("eth0.10".to_string()));
+        let ConnectionConfig::Vlan(config) = &vlan.config else {
+            panic!("Unexpected connection config: {:?}", vlan.config);
+        };
+        assert_eq!(config.parent, eth0_uuid.to_string());
+
+        let vlan = state.get_connection("vlan20").unwrap();
+        let ConnectionConfig::Vlan(config) = &vlan.config else {
+            panic!("Unexpected connection config: {:?}", vlan.config);
+        };
+        assert_eq!(config.parent, "eth2");
+
+        assert!(state.bind_to_target_names().is_empty());
+    }
+
+    #[test]
+    fn test_bind_to_target_names_installer_only() {
+        let device = Device {
+            name: "eth0".to_string(),
+            type_: DeviceType::Ethernet,
+            path: Some("pci-0000:00:19.0".to_string()),
+            target_name: Some("enp0s25".to_string()),
+            ..Default::default()
+        };
+        let mut eth0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        eth0.interface = Some("eth0".to_string());
+        eth0.persistence = Persistence::Installer;
+
+        let mut state = NetworkState::new(
+            None,
+            GeneralState::default(),
+            vec![],
+            vec![device],
+            vec![eth0],
+        );
+        assert!(state.bind_to_target_names().is_empty());
+    }
+
+    #[test]
+    fn test_persistence_conversion() {
+        let net_conn = NetworkConnection {
+            id: "eth0".to_string(),
+            persistent: Some(false),
+            ..Default::default()
+        };
+        let conn = Connection::try_from(net_conn).unwrap();
+        assert_eq!(conn.persistence, Persistence::Installer);
+
+        let net_conn = NetworkConnection {
+            id: "eth0".to_string(),
+            persistent: Some(false),
+            persistence: Some(Persistence::Target),
+            ..Default::default()
+        };
+        let conn = Connection::try_from(net_conn).unwrap();
+        assert_eq!(conn.persistence, Persistence::Target);
+        assert!(conn.is_target_only());
+
+        let net_conn = NetworkConnection::try_from(conn).unwrap();
+        assert_eq!(net_conn.persistent, Some(true));
+        assert_eq!(net_conn.persistence, Some(Persistence::Target));
+    }
+
+    #[test]
+    fn test_sync_from_keeps_target_only() {
+        let mut vlan = Connection::new("vlan10".to_string(), DeviceType::Vlan);
+        vlan.persistence = Persistence::Target;
+        let mut state = NetworkState::default();
+        state.add_connection(vlan.clone()).unwrap();
+
+        // The network service reports the connection as persistent.
+        let mut read = vlan.clone();
+        read.persistence = Persistence::Both;
+        read.set_down();
+        let new_state =
+            NetworkState::new(None, GeneralState::default(), vec![], vec![], vec![read]);
+        state.sync_from(new_state);
+        let conn = state.get_connection("vlan10").unwrap();
+        assert_eq!(conn.persistence, Persistence::Target);
+
+        // It was turned into an in-memory connection.
+        let mut read = vlan.clone();
+        read.persistence = Persistence::Installer;
+        let new_state =
+            NetworkState::new(None, GeneralState::default(), vec![], vec![], vec![read]);
+        state.sync_from(new_state);
+        let conn = state.get_connection("vlan10").unwrap();
+        assert_eq!(conn.persistence, Persistence::Installer);
+    }
+
+    #[test]
+    fn test_proposal_target_connections() {
+        let eth0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        let mut vlan10 = Connection::new("vlan10".to_string(), DeviceType::Vlan);
+        vlan10.persistence = Persistence::Installer;
+        let mut vlan20 = Connection::new("vlan20".to_string(), DeviceType::Vlan);
+        vlan20.persistence = Persistence::Target;
+        let state = NetworkState::new(
+            None,
+            GeneralState::default(),
+            vec![],
+            vec![],
+            vec![eth0, vlan10, vlan20],
+        );
+
+        let proposal = Proposal::try_from(state).unwrap();
+        assert_eq!(
+            proposal.target_connections,
+            vec!["eth0".to_string(), "vlan20".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_installable_keyfiles() {
+        let eth0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        let mut vlan10 = Connection::new("vlan10".to_string(), DeviceType::Vlan);
+        vlan10.persistence = Persistence::Installer;
+        let keyfile = |conn: &Connection| {
+            format!(
+                "[connection]\nid={}\nuuid={}\ntype=ethernet\n\n[ipv4]\nmethod=auto\n",
+                conn.id, conn.uuid
+            )
+        };
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let eth0_path = tmpdir.path().join("eth0.nmconnection");
+        std::fs::write(&eth0_path, keyfile(&eth0)).unwrap();
+        let vlan10_path = tmpdir.path().join("vlan10.nmconnection");
+        std::fs::write(&vlan10_path, keyfile(&vlan10)).unwrap();
+        let unknown_path = tmpdir.path().join("unknown.nmconnection");
+        std::fs::write(&unknown_path, "[connection]\nid=unknown\n").unwrap();
+
+        let state = NetworkState::new(
+            None,
+            GeneralState::default(),
+            vec![],
+            vec![],
+            vec![eth0, vlan10],
+        );
+        assert!(state.is_installable_keyfile(&eth0_path));
+        assert!(!state.is_installable_keyfile(&vlan10_path));
+        assert!(state.is_installable_keyfile(&unknown_path));
+    }
+
+    #[test]
+    fn test_add_connection_with_routing_rules() {
+        let mut state = NetworkState::default();
+        let mut conn0 = Connection::new("eth0".to_string(), DeviceType::Ethernet);
+        conn0.ip_config.route_table4 = Some(100);
+        conn0.ip_config.routing_rules4 = vec![RoutingRule {
+            priority: 1000,
+            from: Some("192.168.10.0/24".parse().unwrap()),
+            table: 100,
+            ..Default::default()
+        }];
+        state.add_connection(conn0.clone()).unwrap();
+
+        conn0.ip_config.routing_rules6 = vec![RoutingRule {
+            priority: 1000,
+            from: Some("192.168.10.0/24".parse().unwrap()),
+            table: 100,
+            ..Default::default()
+        }];
+        let error = state.update_connection(conn0.clone()).unwrap_err();
+        assert!(matches!(
+            error,
+            NetworkStateError::InvalidRoutingRule(1000, _)
+        ));
+
+        conn0.ip_config.routing_rules6 = vec![RoutingRule {
+            priority: 1000,
+            iif: Some("eth1".to_string()),
+            ..Default::default()
+        }];
+        let error = state.update_connection(conn0).unwrap_err();
+        assert!(matches!(
+            error,
+            NetworkStateError::InvalidRoutingRule(1000, _)
+        ));
+    }
+
     #[test]
     fn test_add_duplicated_connection() {
         let mut state = NetworkState::default();
@@ -697,6 +1300,7 @@ mod tests {
                 copy_network: Some(true),
                 ..Default::default()
             }),
+            import: None,
         };
         // This should NOT fail even if we modify the runtime state manually and it differs
         state.get_connection_mut("eth0").unwrap().ip_config.method4 = Some(Ipv4Method::Auto);
@@ -786,6 +1390,7 @@ mod tests {
                 copy_network: Some(true),
                 ..Default::default()
             }),
+            import: None,
         };
 
         state.update_state(config).unwrap();
@@ -916,6 +1521,63 @@ mod tests {
         assert_eq!(eth0_found.controller, None);
     }
 
+    #[test]
+    fn test_set_vrf_ports() {
+        let mut state = NetworkState::default();
+        let eth0 = Connection {
+            id: "eth0".to_string(),
+            interface: Some("eth0".to_string()),
+            ..Default::default()
+        };
+        let vrf0 = Connection {
+            id: "mgmt".to_string(),
+            interface: Some("mgmt".to_string()),
+            config: ConnectionConfig::Vrf(VrfConfig { table: 100 }),
+            ..Default::default()
+        };
+
+        state.add_connection(eth0).unwrap();
+        state.add_connection(vrf0.clone()).unwrap();
+
+        state.set_ports(&vrf0, vec!["eth0".to_string()]).unwrap();
+
+        let eth0_found = state.get_connection("eth0").unwrap();
+        assert_eq!(eth0_found.controller, Some(vrf0.uuid));
+    }
+
+    #[test]
+    fn test_connection_macvlan_conversion() {
+        let net_conn = NetworkConnection {
+            id: "macvlan0".to_string(),
+            macvlan: Some(MacvlanSettings {
+                parent: "eth0".to_string(),
+                mode: Some("private".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        let conn = Connection::try_from(net_conn.clone()).unwrap();
+        let ConnectionConfig::Macvlan(config) = &conn.config else {
+            panic!("Unexpected connection config: {:?}", conn.config);
+        };
+        assert_eq!(config.parent, "eth0");
+        assert_eq!(config.mode, MacvlanMode::Private);
+
+        let net_conn2 = NetworkConnection::try_from(conn).unwrap();
+        assert_eq!(net_conn.macvlan, net_conn2.macvlan);
+
+        let wrong_mode = NetworkConnection {
+            id: "macvlan0".to_string(),
+            macvlan: Some(MacvlanSettings {
+                parent: "eth0".to_string(),
+                mode: Some("l2".to_string()),
+            }),
+            ..Default::default()
+        };
+        let error = Connection::try_from(wrong_mode).unwrap_err();
+        assert!(matches!(error, NetworkStateError::InvalidMacvlanMode(_)));
+    }
+
     #[test]
     fn test_set_bonding_missing_port() {
         let mut state = NetworkState::default();
@@ -990,6 +1652,49 @@ mod tests {
     }
 }
 
+/// Validates a policy routing rule.
+///
+/// * `rule`: rule to validate.
+/// * `ipv4`: whether it is an IPv4 (true) or an IPv6 (false) rule.
+fn validate_routing_rule(rule: &RoutingRule, ipv4: bool) -> Result<(), NetworkStateError> {
+    let invalid =
+        |message: &str| NetworkStateError::InvalidRoutingRule(rule.priority, message.to_string());
+
+    if rule.table == 0 {
+        return Err(invalid("the routing table cannot be 0"));
+    }
+
+    let family = if ipv4 { "IPv4" } else { "IPv6" };
+    for network in [&rule.from, &rule.to].into_iter().flatten() {
+        if network.is_ipv4() != ipv4 {
+            return Err(invalid(&format!("{network} is not an {family} network")));
+        }
+    }
+
+    if rule.iif.as_ref().is_some_and(|iif| iif.is_empty()) {
+        return Err(invalid("the incoming interface cannot be empty"));
+    }
+
+    Ok(())
+}
+
+/// Returns the UUID of the connection defined in a NetworkManager keyfile.
+///
+/// * `content`: keyfile content.
+fn keyfile_uuid(content: &str) -> Option<Uuid> {
+    let mut in_connection = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_connection = line == "[connection]";
+        } else if in_connection {
+            if let Some(("uuid", value)) = line.split_once('=').map(|(k, v)| (k.trim(), v)) {
+                return Uuid::parse_str(value.trim()).ok();
+            }
+        }
+    }
+    None
+}
+
 pub const NOT_COPY_NETWORK_PATH: &str = "/run/agama/not_copy_network";
 
 /// General network state and settings.
@@ -1027,7 +1732,7 @@ pub struct Connection {
     pub ieee_8021x_config: Option<IEEE8021XConfig>,
     pub autoconnect: bool,
     pub state: ConnectionState,
-    pub persistent: bool,
+    pub persistence: Persistence,
     pub flags: u32,
 }
 
@@ -1045,6 +1750,11 @@ impl Connection {
             DeviceType::Bond => ConnectionConfig::Bond(Default::default()),
             DeviceType::Vlan => ConnectionConfig::Vlan(Default::default()),
             DeviceType::Bridge => ConnectionConfig::Bridge(Default::default()),
+            DeviceType::Wireguard => ConnectionConfig::Wireguard(Default::default()),
+            DeviceType::Vrf => ConnectionConfig::Vrf(Default::default()),
+            DeviceType::Macvlan => ConnectionConfig::Macvlan(Default::default()),
+            DeviceType::Ipvlan => ConnectionConfig::Ipvlan(Default::default()),
+            DeviceType::Veth => ConnectionConfig::Veth(Default::default()),
         };
         Self {
             id,
@@ -1081,6 +1791,17 @@ impl Connection {
         self.status = Status::Down
     }
 
+    /// Whether the connection is written to the installed system.
+    pub fn is_persistent(&self) -> bool {
+        self.persistence.is_persistent()
+    }
+
+    /// Whether the connection is only written to the installed system, without activating it
+    /// during the installation.
+    pub fn is_target_only(&self) -> bool {
+        self.persistence == Persistence::Target
+    }
+
     /// Determines whether it is a loopback interface.
     pub fn is_loopback(&self) -> bool {
         matches!(self.config, ConnectionConfig::Loopback)
@@ -1093,6 +1814,22 @@ impl Connection {
             || matches!(self.config, ConnectionConfig::Bond(_))
             || matches!(self.config, ConnectionConfig::Vlan(_))
             || matches!(self.config, ConnectionConfig::Bridge(_))
+            || matches!(self.config, ConnectionConfig::Macvlan(_))
+            || matches!(self.config, ConnectionConfig::Ipvlan(_))
+            || matches!(self.config, ConnectionConfig::Veth(_))
+    }
+
+    /// Checks whether the connection settings are consistent.
+    ///
+    /// So far, it only validates the policy routing rules.
+    pub fn validate(&self) -> Result<(), NetworkStateError> {
+        for rule in &self.ip_config.routing_rules4 {
+            validate_routing_rule(rule, true)?;
+        }
+        for rule in &self.ip_config.routing_rules6 {
+            validate_routing_rule(rule, false)?;
+        }
+        Ok(())
     }
 
     /// Determines whether the connection is bound to a specific interface or MAC address.
@@ -1156,7 +1893,7 @@ impl Default for Connection {
             ieee_8021x_config: Default::default(),
             autoconnect: true,
             state: Default::default(),
-            persistent: true,
+            persistence: Default::default(),
             flags: Default::default(),
         }
     }
@@ -1181,7 +1918,15 @@ impl TryFrom<NetworkConnection> for Connection {
         }
 
         if let Some(persistent) = conn.persistent {
-            connection.persistent = persistent;
+            connection.persistence = if persistent {
+                Persistence::Both
+            } else {
+                Persistence::Installer
+            };
+        }
+
+        if let Some(persistence) = conn.persistence {
+            connection.persistence = persistence;
         }
 
         if let Some(ignore_auto_dns) = conn.ignore_auto_dns {
@@ -1205,6 +1950,26 @@ impl TryFrom<NetworkConnection> for Connection {
             let config = BridgeConfig::try_from(bridge_config)?;
             connection.config = config.into();
         }
+        if let Some(wireguard_config) = conn.wireguard {
+            let config = WireguardConfig::try_from(wireguard_config)?;
+            connection.config = config.into();
+        }
+        if let Some(vrf_config) = conn.vrf {
+            let config = VrfConfig::try_from(vrf_config)?;
+            connection.config = config.into();
+        }
+        if let Some(macvlan_config) = conn.macvlan {
+            let config = MacvlanConfig::try_from(macvlan_config)?;
+            connection.config = config.into();
+        }
+        if let Some(ipvlan_config) = conn.ipvlan {
+            let config = IpvlanConfig::try_from(ipvlan_config)?;
+            connection.config = config.into();
+        }
+        if let Some(veth_config) = conn.veth {
+            let config = VethConfig::try_from(veth_config)?;
+            connection.config = config.into();
+        }
 
         if let Some(ieee_8021x_config) = conn.ieee_8021x {
             connection.ieee_8021x_config = Some(IEEE8021XConfig::try_from(ieee_8021x_config)?);
@@ -1258,7 +2023,8 @@ impl TryFrom<Connection> for NetworkConnection {
             .ieee_8021x_config
             .and_then(|x| IEEE8021XSettings::try_from(x).ok());
         let autoconnect = Some(conn.autoconnect);
-        let persistent = Some(conn.persistent);
+        let persistent = Some(conn.is_persistent());
+        let persistence = Some(conn.persistence);
 
         let match_settings = (!conn.match_config.is_empty()).then(|| MatchSettings {
             driver: conn.match_config.driver.clone(),
@@ -1285,6 +2051,7 @@ impl TryFrom<Connection> for NetworkConnection {
             ieee_8021x,
             autoconnect,
             persistent,
+            persistence,
             match_settings,
             ..Default::default()
         };
@@ -1302,6 +2069,21 @@ impl TryFrom<Connection> for NetworkConnection {
             ConnectionConfig::Vlan(config) => {
                 connection.vlan = Some(VlanSettings::try_from(config)?);
             }
+            ConnectionConfig::Wireguard(config) => {
+                connection.wireguard = Some(WireguardSettings::try_from(config)?);
+            }
+            ConnectionConfig::Vrf(config) => {
+                connection.vrf = Some(VrfSettings::try_from(config)?);
+            }
+            ConnectionConfig::Macvlan(config) => {
+                connection.macvlan = Some(MacvlanSettings::try_from(config)?);
+            }
+            ConnectionConfig::Ipvlan(config) => {
+                connection.ipvlan = Some(IpvlanSettings::try_from(config)?);
+            }
+            ConnectionConfig::Veth(config) => {
+                connection.veth = Some(VethSettings::try_from(config)?);
+            }
             _ => {}
         }
 
@@ -1324,6 +2106,11 @@ pub enum ConnectionConfig {
     OvsBridge(OvsBridgeConfig),
     OvsPort(OvsPortConfig),
     OvsInterface(OvsInterfaceConfig),
+    Wireguard(WireguardConfig),
+    Vrf(VrfConfig),
+    Macvlan(MacvlanConfig),
+    Ipvlan(IpvlanConfig),
+    Veth(VethConfig),
 }
 
 #[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -1358,6 +2145,36 @@ impl From<WirelessConfig> for ConnectionConfig {
     }
 }
 
+impl From<WireguardConfig> for ConnectionConfig {
+    fn from(value: WireguardConfig) -> Self {
+        Self::Wireguard(value)
+    }
+}
+
+impl From<VrfConfig> for ConnectionConfig {
+    fn from(value: VrfConfig) -> Self {
+        Self::Vrf(value)
+    }
+}
+
+impl From<MacvlanConfig> for ConnectionConfig {
+    fn from(value: MacvlanConfig) -> Self {
+        Self::Macvlan(value)
+    }
+}
+
+impl From<IpvlanConfig> for ConnectionConfig {
+    fn from(value: IpvlanConfig) -> Self {
+        Self::Ipvlan(value)
+    }
+}
+
+impl From<VethConfig> for ConnectionConfig {
+    fn from(value: VethConfig) -> Self {
+        Self::Veth(value)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct MatchConfig {
@@ -1956,6 +2773,9 @@ impl TryFrom<ConnectionCollection> for NetworkConnectionsCollection {
                 if let Some(ref mut bridge) = conn.bridge {
                     bridge.ports = collection.ports_for(c.uuid);
                 };
+                if let Some(ref mut vrf) = conn.vrf {
+                    vrf.ports = collection.ports_for(c.uuid);
+                };
                 conn
             })
             .collect();
@@ -1979,6 +2799,9 @@ impl TryFrom<ConnectionCollection> for NetworkConnectionsWithStateCollection {
                 if let Some(ref mut bridge) = conn.bridge {
                     bridge.ports = collection.ports_for(c.uuid);
                 };
+                if let Some(ref mut vrf) = conn.vrf {
+                    vrf.ports = collection.ports_for(c.uuid);
+                };
                 NetworkConnectionWithState {
                     connection: conn,
                     state: c.state,
@@ -2007,6 +2830,9 @@ impl TryFrom<NetworkConnectionsCollection> for ConnectionCollection {
             if let Some(bond) = &net_conn.bond {
                 ports = bond.ports.clone();
             }
+            if let Some(vrf) = &net_conn.vrf {
+                ports = vrf.ports.clone();
+            }
             for port in &ports {
                 controller_ports.insert(port.to_string(), conn.uuid);
             }
@@ -2051,6 +2877,7 @@ impl TryFrom<NetworkState> for Config {
         Ok(Config {
             connections: Some(connections),
             state: Some(state.general_state.try_into()?),
+            import: None,
         })
     }
 }
@@ -2075,12 +2902,19 @@ impl TryFrom<NetworkState> for Proposal {
     type Error = NetworkStateError;
 
     fn try_from(state: NetworkState) -> Result<Self, Self::Error> {
+        let target_connections = state
+            .connections
+            .iter()
+            .filter(|c| c.is_persistent() && !c.is_removed())
+            .map(|c| c.id.clone())
+            .collect();
         let connections: NetworkConnectionsCollection =
             ConnectionCollection(state.connections).try_into()?;
 
         Ok(Proposal {
             connections,
             state: state.general_state.try_into()?,
+            target_connections,
         })
     }
 }
@@ -2239,6 +3073,280 @@ pub struct TunConfig {
     pub owner: Option<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct WireguardConfig {
+    pub private_key: Option<String>,
+    pub listen_port: Option<u32>,
+    pub peers: Vec<WireguardPeer>,
+}
+
+#[skip_serializing_none]
+#[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct WireguardPeer {
+    pub public_key: String,
+    pub preshared_key: Option<String>,
+    pub endpoint: Option<String>,
+    pub allowed_ips: Vec<String>,
+    pub persistent_keepalive: Option<u32>,
+}
+
+impl TryFrom<ConnectionConfig> for WireguardConfig {
+    type Error = NetworkStateError;
+
+    fn try_from(value: ConnectionConfig) -> Result<Self, Self::Error> {
+        match value {
+            ConnectionConfig::Wireguard(config) => Ok(config),
+            _ => Err(NetworkStateError::UnexpectedConfiguration),
+        }
+    }
+}
+
+impl TryFrom<WireguardSettings> for WireguardConfig {
+    type Error = NetworkStateError;
+
+    fn try_from(settings: WireguardSettings) -> Result<Self, Self::Error> {
+        let peers = settings
+            .peers
+            .into_iter()
+            .map(|peer| WireguardPeer {
+                public_key: peer.public_key,
+                preshared_key: peer.preshared_key,
+                endpoint: peer.endpoint,
+                allowed_ips: peer.allowed_ips,
+                persistent_keepalive: peer.persistent_keepalive,
+            })
+            .collect();
+
+        Ok(WireguardConfig {
+            private_key: settings.private_key,
+            listen_port: settings.listen_port,
+            peers,
+        })
+    }
+}
+
+impl TryFrom<WireguardConfig> for WireguardSettings {
+    type Error = NetworkStateError;
+
+    fn try_from(wireguard: WireguardConfig) -> Result<Self, Self::Error> {
+        let peers = wireguard
+            .peers
+            .into_iter()
+            .map(|peer| WireguardPeerSettings {
+                public_key: peer.public_key,
+                preshared_key: peer.preshared_key,
+                endpoint: peer.endpoint,
+                allowed_ips: peer.allowed_ips,
+                persistent_keepalive: peer.persistent_keepalive,
+            })
+            .collect();
+
+        Ok(WireguardSettings {
+            private_key: wireguard.private_key,
+            listen_port: wireguard.listen_port,
+            peers,
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct VrfConfig {
+    pub table: u32,
+}
+
+impl TryFrom<VrfSettings> for VrfConfig {
+    type Error = NetworkStateError;
+
+    fn try_from(settings: VrfSettings) -> Result<Self, Self::Error> {
+        Ok(VrfConfig {
+            table: settings.table,
+        })
+    }
+}
+
+impl TryFrom<VrfConfig> for VrfSettings {
+    type Error = NetworkStateError;
+
+    fn try_from(vrf: VrfConfig) -> Result<Self, Self::Error> {
+        Ok(VrfSettings {
+            table: vrf.table,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+pub enum MacvlanMode {
+    Vepa = 1,
+    #[default]
+    Bridge = 2,
+    Private = 3,
+    Passthru = 4,
+    Source = 5,
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid MACVLAN mode: {0}")]
+pub struct InvalidMacvlanMode(String);
+
+impl FromStr for MacvlanMode {
+    type Err = InvalidMacvlanMode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vepa" => Ok(Self::Vepa),
+            "bridge" => Ok(Self::Bridge),
+            "private" => Ok(Self::Private),
+            "passthru" => Ok(Self::Passthru),
+            "source" => Ok(Self::Source),
+            _ => Err(InvalidMacvlanMode(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for MacvlanMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match &self {
+            MacvlanMode::Vepa => "vepa",
+            MacvlanMode::Bridge => "bridge",
+            MacvlanMode::Private => "private",
+            MacvlanMode::Passthru => "passthru",
+            MacvlanMode::Source => "source",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct MacvlanConfig {
+    pub parent: String,
+    pub mode: MacvlanMode,
+}
+
+impl TryFrom<MacvlanSettings> for MacvlanConfig {
+    type Error = NetworkStateError;
+
+    fn try_from(settings: MacvlanSettings) -> Result<Self, Self::Error> {
+        let mut config = MacvlanConfig {
+            parent: settings.parent,
+            ..Default::default()
+        };
+
+        if let Some(mode) = settings.mode {
+            config.mode = MacvlanMode::from_str(mode.as_str())
+                .map_err(|_| NetworkStateError::InvalidMacvlanMode(mode))?;
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<MacvlanConfig> for MacvlanSettings {
+    type Error = NetworkStateError;
+
+    fn try_from(macvlan: MacvlanConfig) -> Result<Self, Self::Error> {
+        Ok(MacvlanSettings {
+            parent: macvlan.parent,
+            mode: Some(macvlan.mode.to_string()),
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+pub enum IpvlanMode {
+    #[default]
+    L2 = 1,
+    L3 = 2,
+    L3S = 3,
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid IPVLAN mode: {0}")]
+pub struct InvalidIpvlanMode(String);
+
+impl FromStr for IpvlanMode {
+    type Err = InvalidIpvlanMode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "l2" => Ok(Self::L2),
+            "l3" => Ok(Self::L3),
+            "l3s" => Ok(Self::L3S),
+            _ => Err(InvalidIpvlanMode(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for IpvlanMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match &self {
+            IpvlanMode::L2 => "l2",
+            IpvlanMode::L3 => "l3",
+            IpvlanMode::L3S => "l3s",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct IpvlanConfig {
+    pub parent: String,
+    pub mode: IpvlanMode,
+}
+
+impl TryFrom<IpvlanSettings> for IpvlanConfig {
+    type Error = NetworkStateError;
+
+    fn try_from(settings: IpvlanSettings) -> Result<Self, Self::Error> {
+        let mut config = IpvlanConfig {
+            parent: settings.parent,
+            ..Default::default()
+        };
+
+        if let Some(mode) = settings.mode {
+            config.mode = IpvlanMode::from_str(mode.as_str())
+                .map_err(|_| NetworkStateError::InvalidIpvlanMode(mode))?;
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<IpvlanConfig> for IpvlanSettings {
+    type Error = NetworkStateError;
+
+    fn try_from(ipvlan: IpvlanConfig) -> Result<Self, Self::Error> {
+        Ok(IpvlanSettings {
+            parent: ipvlan.parent,
+            mode: Some(ipvlan.mode.to_string()),
+        })
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct VethConfig {
+    pub peer: String,
+}
+
+impl TryFrom<VethSettings> for VethConfig {
+    type Error = NetworkStateError;
+
+    fn try_from(settings: VethSettings) -> Result<Self, Self::Error> {
+        Ok(VethConfig {
+            peer: settings.peer,
+        })
+    }
+}
+
+impl TryFrom<VethConfig> for VethSettings {
+    type Error = NetworkStateError;
+
+    fn try_from(veth: VethConfig) -> Result<Self, Self::Error> {
+        Ok(VethSettings { peer: veth.peer })
+    }
+}
+
 /// Represents a network change.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
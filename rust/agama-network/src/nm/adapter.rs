@@ -20,8 +20,9 @@
 
 use crate::{
     adapter::Watcher,
-    model::{Connection, NetworkState, StateConfig},
+    model::{Connection, NetworkState, NetworkStateDiff, StateConfig},
     nm::{NetworkManagerClient, NetworkManagerWatcher},
+    probe::{self, ConnectivityProbe},
     types::ConnectionState,
     Adapter, NetworkAdapterError,
 };
@@ -30,10 +31,16 @@ use async_trait::async_trait;
 use core::time;
 use std::thread;
 use tokio::time::{sleep, Duration, Instant};
-use zbus::zvariant::OwnedObjectPath;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
 
 use super::error::NmError;
 
+/// Seconds after which NetworkManager rolls back the changes on its own if the checkpoint is not
+/// destroyed (e.g., because the service got stuck).
+const CHECKPOINT_ROLLBACK_TIMEOUT: u32 = 180;
+/// Number of attempts to reach the gateways or the remote clients after applying the changes.
+const CONNECTIVITY_PROBE_ATTEMPTS: u32 = 5;
+
 /// An adapter for NetworkManager
 pub struct NetworkManagerAdapter<'a> {
     client: NetworkManagerClient<'a>,
@@ -72,7 +79,9 @@ impl<'a> NetworkManagerAdapter<'a> {
         let devices = self.client.devices().await?;
         for device in devices {
             let is_using_this = device.connection.as_ref() == Some(&conn.id);
-            let is_target = conn.is_up() && conn.matches_device(&device.name, &device.mac_address);
+            let is_target = conn.is_up()
+                && !conn.is_target_only()
+                && conn.matches_device(&device.name, &device.mac_address);
             let is_compatible = conn.is_compatible(&device.name, &device.mac_address);
 
             if (is_using_this && !is_compatible)
@@ -146,12 +155,24 @@ impl Adapter for NetworkManagerAdapter<'_> {
     /// that using async recursive functions is giving us some troubles, so we decided to go with a
     /// simpler approach.
     ///
+    /// The changes are wrapped in a checkpoint. If the gateways or the remote clients were
+    /// reachable before writing the changes but none of them is reachable afterwards, the
+    /// checkpoint is rolled back.
+    ///
     /// * `network`: network model.
-    async fn write(&self, network: &NetworkState) -> Result<(), NetworkAdapterError> {
+    async fn write(&self, network: &NetworkState) -> Result<NetworkStateDiff, NetworkAdapterError> {
         let old_state = self.read(StateConfig::default()).await?;
+        let diff = old_state.diff(network);
+        let clients = probe::remote_clients();
+
+        let mut targets = old_state.gateways();
+        targets.extend(clients.iter().copied());
+        let check_connectivity =
+            !diff.is_empty() && ConnectivityProbe::new(targets).is_reachable().await;
+
         let checkpoint = self
             .client
-            .create_checkpoint()
+            .create_checkpoint(CHECKPOINT_ROLLBACK_TIMEOUT)
             .await
             .map_err(|e| NetworkAdapterError::Checkpoint(anyhow!(e)))?;
 
@@ -228,7 +249,15 @@ impl Adapter for NetworkManagerAdapter<'_> {
                     tracing::error!("Failed to cleanup devices for {}: {}", &conn.id, e);
                 }
 
-                if conn.is_up() {
+                if conn.is_target_only() {
+                    tracing::info!(
+                        "Not activating connection {}, it is only for the target system",
+                        &conn.id
+                    );
+                    if let Err(e) = self.client.deactivate_connection(path).await {
+                        tracing::error!("Failed to deactivate connection {}: {}", &conn.id, e);
+                    }
+                } else if conn.is_up() {
                     match self.activate_connection(conn, path).await {
                         Ok(active_path) => {
                             tracing::info!(
@@ -251,11 +280,6 @@ impl Adapter for NetworkManagerAdapter<'_> {
             }
         }
 
-        self.client
-            .destroy_checkpoint(&checkpoint.as_ref())
-            .await
-            .map_err(|e| NetworkAdapterError::Checkpoint(anyhow!(e)))?;
-
         if !active_paths.is_empty() {
             let start = Instant::now();
             let timeout = Duration::from_secs(30);
@@ -292,7 +316,15 @@ impl Adapter for NetworkManagerAdapter<'_> {
             }
         }
 
-        Ok(())
+        let probe = check_connectivity.then(|| {
+            let mut targets = network.gateways();
+            targets.extend(clients);
+            ConnectivityProbe::new(targets).with_attempts(CONNECTIVITY_PROBE_ATTEMPTS)
+        });
+        keep_or_rollback(&self.client, &checkpoint.as_ref(), probe).await?;
+
+        tracing::info!("Network changes applied: {:?}", &diff);
+        Ok(diff)
     }
 
     fn watcher(&self) -> Option<Box<dyn Watcher + Send>> {
@@ -300,6 +332,52 @@ impl Adapter for NetworkManagerAdapter<'_> {
     }
 }
 
+/// Handles the NetworkManager checkpoints.
+#[async_trait]
+trait Checkpoints {
+    async fn rollback_checkpoint(&self, checkpoint: &ObjectPath<'_>) -> Result<(), NmError>;
+    async fn destroy_checkpoint(&self, checkpoint: &ObjectPath<'_>) -> Result<(), NmError>;
+}
+
+#[async_trait]
+impl Checkpoints for NetworkManagerClient<'_> {
+    async fn rollback_checkpoint(&self, checkpoint: &ObjectPath<'_>) -> Result<(), NmError> {
+        NetworkManagerClient::rollback_checkpoint(self, checkpoint).await
+    }
+
+    async fn destroy_checkpoint(&self, checkpoint: &ObjectPath<'_>) -> Result<(), NmError> {
+        NetworkManagerClient::destroy_checkpoint(self, checkpoint).await
+    }
+}
+
+/// Keeps the changes if the system is still reachable, otherwise it rolls them back.
+///
+/// * `client`: client to handle the checkpoint.
+/// * `checkpoint`: checkpoint's D-Bus path.
+/// * `probe`: probe to check the connectivity, if needed.
+async fn keep_or_rollback(
+    client: &(impl Checkpoints + Sync),
+    checkpoint: &ObjectPath<'_>,
+    probe: Option<ConnectivityProbe>,
+) -> Result<(), NetworkAdapterError> {
+    if let Some(probe) = probe {
+        if !probe.is_empty() && !probe.is_reachable().await {
+            tracing::error!("The system is not reachable anymore, rolling back the changes");
+            client
+                .rollback_checkpoint(checkpoint)
+                .await
+                .map_err(|e| NetworkAdapterError::Checkpoint(anyhow!(e)))?;
+
+            return Err(NetworkAdapterError::ConnectivityLost);
+        }
+    }
+
+    client
+        .destroy_checkpoint(checkpoint)
+        .await
+        .map_err(|e| NetworkAdapterError::Checkpoint(anyhow!(e)))
+}
+
 /// Returns the connections in the order they should be processed.
 ///
 /// * `network`: network model.
@@ -328,3 +406,71 @@ fn add_ordered_connections<'b>(
         conns.push(conn);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::Pinger;
+    use std::{net::IpAddr, sync::Mutex};
+
+    #[derive(Default)]
+    struct FakeCheckpoints {
+        calls: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl Checkpoints for FakeCheckpoints {
+        async fn rollback_checkpoint(&self, _checkpoint: &ObjectPath<'_>) -> Result<(), NmError> {
+            self.calls.lock().unwrap().push("rollback");
+            Ok(())
+        }
+
+        async fn destroy_checkpoint(&self, _checkpoint: &ObjectPath<'_>) -> Result<(), NmError> {
+            self.calls.lock().unwrap().push("destroy");
+            Ok(())
+        }
+    }
+
+    struct FakePinger {
+        reachable: bool,
+    }
+
+    #[async_trait]
+    impl Pinger for FakePinger {
+        async fn ping(&self, _address: &IpAddr) -> bool {
+            self.reachable
+        }
+    }
+
+    fn probe(reachable: bool) -> ConnectivityProbe {
+        ConnectivityProbe::new(vec!["192.168.122.1".parse().unwrap()])
+            .with_pinger(FakePinger { reachable })
+    }
+
+    #[tokio::test]
+    async fn test_rollback_when_unreachable() {
+        let client = FakeCheckpoints::default();
+        let checkpoint =
+            ObjectPath::try_from("/org/freedesktop/NetworkManager/Checkpoint/1").unwrap();
+
+        let result = keep_or_rollback(&client, &checkpoint, Some(probe(false))).await;
+        assert!(matches!(result, Err(NetworkAdapterError::ConnectivityLost)));
+        assert_eq!(*client.calls.lock().unwrap(), vec!["rollback"]);
+    }
+
+    #[tokio::test]
+    async fn test_keep_when_reachable() {
+        let client = FakeCheckpoints::default();
+        let checkpoint =
+            ObjectPath::try_from("/org/freedesktop/NetworkManager/Checkpoint/1").unwrap();
+
+        keep_or_rollback(&client, &checkpoint, Some(probe(true)))
+            .await
+            .unwrap();
+        assert_eq!(*client.calls.lock().unwrap(), vec!["destroy"]);
+
+        let client = FakeCheckpoints::default();
+        keep_or_rollback(&client, &checkpoint, None).await.unwrap();
+        assert_eq!(*client.calls.lock().unwrap(), vec!["destroy"]);
+    }
+}
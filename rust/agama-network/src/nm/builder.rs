@@ -29,8 +29,9 @@ use crate::{
     },
     types::{
         AccessPoint, ConnectionFlags, Device, DeviceState, DeviceType, IpConfig, IpRoute,
-        MacAddress, SSID,
+        MacAddress, Persistence, SSID,
     },
+    udev::{self, UdevProperties},
 };
 use cidr::IpInet;
 use std::{collections::HashMap, net::IpAddr, str::FromStr};
@@ -95,7 +96,11 @@ impl<'a> ConnectionFromProxyBuilder<'a> {
         match connection_from_dbus(settings) {
             Ok(mut connection) => {
                 connection.flags = flags;
-                connection.persistent = flags == 0;
+                connection.persistence = if flags == 0 {
+                    Persistence::Both
+                } else {
+                    Persistence::Installer
+                };
                 Ok(connection)
             }
             Err(e) => {
@@ -130,11 +135,20 @@ impl<'a> DeviceFromProxyBuilder<'a> {
             device.ip_config = self.build_ip_config().await?;
         }
 
-        device.mac_address = self.mac_address_from_dbus(self.proxy.hw_address().await?.as_str());
+        let hw_address = self.proxy.hw_address().await?;
+        device.mac_address = self.mac_address_from_dbus(hw_address.as_str());
         if let Ok((connection, _)) = self.proxy.get_applied_connection(0).await {
             device.connection = self.connection_id(connection);
         }
 
+        if matches!(device.type_, DeviceType::Ethernet | DeviceType::Wireless) {
+            let properties = UdevProperties::cached(&device.name, &hw_address).await;
+            device.path = properties.path().map(ToString::to_string);
+            if udev::target_uses_predictable_names() {
+                device.target_name = properties.predicted_name().map(ToString::to_string);
+            }
+        }
+
         Ok(device)
     }
 
@@ -279,6 +293,7 @@ impl<'a> DeviceFromProxyBuilder<'a> {
             destination,
             next_hop: None,
             metric: None,
+            table: None,
         };
 
         if let Some(next_hop) = route_data.get("next-hop") {
@@ -289,6 +304,10 @@ impl<'a> DeviceFromProxyBuilder<'a> {
             let metric: u32 = metric.downcast_ref().ok()?;
             new_route.metric = Some(metric);
         }
+        if let Some(table) = route_data.get("table") {
+            let table: u32 = table.downcast_ref().ok()?;
+            new_route.table = Some(table);
+        }
 
         Some(new_route)
     }
@@ -38,7 +38,8 @@ use crate::model::{
     Connection, ConnectionConfig, GeneralState, SecurityProtocol, NOT_COPY_NETWORK_PATH,
 };
 use crate::types::{
-    AccessPoint, AddFlags, ConnectionFlags, ConnectionState, Device, DeviceType, UpdateFlags, SSID,
+    AccessPoint, AddFlags, ConnectionFlags, ConnectionState, Device, DeviceType, Persistence,
+    UpdateFlags, SSID,
 };
 use agama_utils::dbus::get_optional_property;
 use semver::Version;
@@ -235,7 +236,11 @@ impl<'a> NetworkManagerClient<'a> {
 
                     Self::add_secrets(&mut connection.config, &proxy).await?;
                     connection.flags = flags;
-                    connection.persistent = flags == 0;
+                    connection.persistence = if flags == 0 {
+                        Persistence::Both
+                    } else {
+                        Persistence::Installer
+                    };
 
                     if let Some(controller) = controller {
                         controlled_by.insert(connection.uuid, controller);
@@ -298,28 +303,32 @@ impl<'a> NetworkManagerClient<'a> {
         let path = if let Ok(proxy) = self.get_connection_proxy(conn.uuid).await {
             let original = proxy.get_settings().await?;
             let merged = merge_dbus_connections(&original, &new_conn)?;
-            let persist = if conn.persistent {
-                UpdateFlags::ToDisk
+            let mut persist = if conn.is_persistent() {
+                UpdateFlags::ToDisk as u32
             } else {
-                UpdateFlags::InMemoryOnly
+                UpdateFlags::InMemoryOnly as u32
             };
+            if conn.is_target_only() {
+                persist |= UpdateFlags::BlockAutoconnect as u32;
+            }
 
-            proxy
-                .update2(merged, persist as u32, Default::default())
-                .await?;
+            proxy.update2(merged, persist, Default::default()).await?;
             OwnedObjectPath::from(proxy.inner().path().to_owned())
         } else {
             let proxy = SettingsProxy::new(&self.connection).await?;
             // https://networkmanager.dev/docs/api/latest/nm-dbus-types.html#NMSettingsConnectionFlags
-            // 0x1 persist to disk, 0x2 memory only
-            let persist = if conn.persistent {
-                AddFlags::ToDisk
+            // 0x1 persist to disk, 0x2 memory only, 0x20 block autoconnect
+            let mut persist = if conn.is_persistent() {
+                AddFlags::ToDisk as u32
             } else {
-                AddFlags::InMemory
+                AddFlags::InMemory as u32
             };
+            if conn.is_target_only() {
+                persist |= AddFlags::BlockAutoconnect as u32;
+            }
             cleanup_dbus_connection(&mut new_conn);
             let (path, _) = proxy
-                .add_connection2(new_conn, persist as u32, Default::default())
+                .add_connection2(new_conn, persist, Default::default())
                 .await?;
             path
         };
@@ -344,9 +353,18 @@ impl<'a> NetworkManagerClient<'a> {
         Ok(())
     }
 
-    /// Creates a checkpoint.
-    pub async fn create_checkpoint(&self) -> Result<OwnedObjectPath, NmError> {
-        let path = self.nm_proxy.checkpoint_create(&[], 0, 0).await?;
+    /// Creates a checkpoint for all the devices.
+    ///
+    /// * `rollback_timeout`: seconds after which NetworkManager rolls back the checkpoint on its
+    ///   own (0 means no timeout).
+    pub async fn create_checkpoint(
+        &self,
+        rollback_timeout: u32,
+    ) -> Result<OwnedObjectPath, NmError> {
+        let path = self
+            .nm_proxy
+            .checkpoint_create(&[], rollback_timeout, 0)
+            .await?;
         Ok(path)
     }
 
@@ -506,21 +524,30 @@ impl<'a> NetworkManagerClient<'a> {
         config: &mut ConnectionConfig,
         proxy: &ConnectionProxy<'_>,
     ) -> Result<(), NmError> {
-        let ConnectionConfig::Wireless(ref mut wireless) = config else {
-            return Ok(());
-        };
-
-        if wireless.security == SecurityProtocol::WPA2 {
-            match proxy.get_secrets("802-11-wireless-security").await {
+        match config {
+            ConnectionConfig::Wireless(wireless) if wireless.security == SecurityProtocol::WPA2 => {
+                match proxy.get_secrets("802-11-wireless-security").await {
+                    Ok(secrets) => {
+                        if let Some(secret) = secrets.get("802-11-wireless-security") {
+                            wireless.password = get_optional_property(secret, "psk")?;
+                        }
+                    }
+                    Err(_) => {
+                        tracing::error!("Could not read connection secrets");
+                    }
+                }
+            }
+            ConnectionConfig::Wireguard(wireguard) => match proxy.get_secrets("wireguard").await {
                 Ok(secrets) => {
-                    if let Some(secret) = secrets.get("802-11-wireless-security") {
-                        wireless.password = get_optional_property(secret, "psk")?;
+                    if let Some(secret) = secrets.get("wireguard") {
+                        wireguard.private_key = get_optional_property(secret, "private-key")?;
                     }
                 }
                 Err(_) => {
                     tracing::error!("Could not read connection secrets");
                 }
-            }
+            },
+            _ => {}
         }
         Ok(())
     }
@@ -46,10 +46,18 @@ const BRIDGE_KEY: &str = "bridge";
 const BRIDGE_PORT_KEY: &str = "bridge-port";
 const INFINIBAND_KEY: &str = "infiniband";
 const TUN_KEY: &str = "tun";
+const WIREGUARD_KEY: &str = "wireguard";
+const VRF_KEY: &str = "vrf";
+const MACVLAN_KEY: &str = "macvlan";
+const IPVLAN_KEY: &str = "ipvlan";
+const VETH_KEY: &str = "veth";
 const IEEE_8021X_KEY: &str = "802-1x";
 const OVS_PORT: &str = "ovs-port";
 const OVS_INTERFACE: &str = "ovs-interface";
 const OVS_BRIDGE: &str = "ovs-bridge";
+// Address families used in the routing rules.
+const AF_INET: i32 = 2;
+const AF_INET6: i32 = 10;
 
 /// Converts a connection struct into a HashMap that can be sent over D-Bus.
 ///
@@ -76,6 +84,7 @@ pub fn connection_to_dbus<'a>(
             ConnectionConfig::Bridge(_) => BRIDGE_KEY,
             ConnectionConfig::OvsPort(_) => OVS_PORT,
             ConnectionConfig::OvsBridge(_) => OVS_BRIDGE,
+            ConnectionConfig::Vrf(_) => VRF_KEY,
             _ => {
                 tracing::error!("Controller {} has unhandled config type", controller.id);
                 ""
@@ -189,6 +198,57 @@ pub fn connection_to_dbus<'a>(
             connection_dbus.insert("autoconnect-slaves", 1.into());
             result.insert(OVS_PORT, ovs_port_config_to_dbus(port));
         }
+        ConnectionConfig::Wireguard(wireguard) => {
+            connection_dbus.insert("type", WIREGUARD_KEY.into());
+            if !connection_dbus.contains_key("interface-name") {
+                connection_dbus.insert("interface-name", conn.id.as_str().into());
+            }
+            result.insert(WIREGUARD_KEY, wireguard_config_to_dbus(wireguard));
+        }
+        ConnectionConfig::Vrf(vrf) => {
+            connection_dbus.insert("type", VRF_KEY.into());
+            connection_dbus.insert("autoconnect-slaves", 1.into());
+            if !connection_dbus.contains_key("interface-name") {
+                connection_dbus.insert("interface-name", conn.id.as_str().into());
+            }
+            result.insert(VRF_KEY, HashMap::from([("table", vrf.table.into())]));
+        }
+        ConnectionConfig::Macvlan(macvlan) => {
+            connection_dbus.insert("type", MACVLAN_KEY.into());
+            if !connection_dbus.contains_key("interface-name") {
+                connection_dbus.insert("interface-name", conn.id.as_str().into());
+            }
+            result.insert(
+                MACVLAN_KEY,
+                HashMap::from([
+                    ("parent", macvlan.parent.as_str().into()),
+                    ("mode", (macvlan.mode as u32).into()),
+                ]),
+            );
+        }
+        ConnectionConfig::Ipvlan(ipvlan) => {
+            connection_dbus.insert("type", IPVLAN_KEY.into());
+            if !connection_dbus.contains_key("interface-name") {
+                connection_dbus.insert("interface-name", conn.id.as_str().into());
+            }
+            result.insert(
+                IPVLAN_KEY,
+                HashMap::from([
+                    ("parent", ipvlan.parent.as_str().into()),
+                    ("mode", (ipvlan.mode as u32).into()),
+                ]),
+            );
+        }
+        ConnectionConfig::Veth(veth) => {
+            connection_dbus.insert("type", VETH_KEY.into());
+            if !connection_dbus.contains_key("interface-name") {
+                connection_dbus.insert("interface-name", conn.id.as_str().into());
+            }
+            result.insert(
+                VETH_KEY,
+                HashMap::from([("peer", veth.peer.as_str().into())]),
+            );
+        }
         _ => {}
     }
 
@@ -252,6 +312,31 @@ pub fn connection_from_dbus(conn: OwnedNestedHash) -> Result<Connection, NmError
         return Ok(connection);
     }
 
+    if let Some(wireguard_config) = wireguard_config_from_dbus(&conn)? {
+        connection.config = ConnectionConfig::Wireguard(wireguard_config);
+        return Ok(connection);
+    }
+
+    if let Some(vrf_config) = vrf_config_from_dbus(&conn)? {
+        connection.config = ConnectionConfig::Vrf(vrf_config);
+        return Ok(connection);
+    }
+
+    if let Some(macvlan_config) = macvlan_config_from_dbus(&conn)? {
+        connection.config = ConnectionConfig::Macvlan(macvlan_config);
+        return Ok(connection);
+    }
+
+    if let Some(ipvlan_config) = ipvlan_config_from_dbus(&conn)? {
+        connection.config = ConnectionConfig::Ipvlan(ipvlan_config);
+        return Ok(connection);
+    }
+
+    if let Some(veth_config) = veth_config_from_dbus(&conn)? {
+        connection.config = ConnectionConfig::Veth(veth_config);
+        return Ok(connection);
+    }
+
     if let Some(ovs_bridge) = ovs_bridge_from_dbus(&conn)? {
         connection.config = ConnectionConfig::OvsBridge(ovs_bridge);
         return Ok(connection);
@@ -501,6 +586,22 @@ fn ip_config_to_ipv4_dbus<'a>(
         );
     }
 
+    if let Some(route_table) = ip_config.route_table4 {
+        ipv4_dbus.insert("route-table", route_table.into());
+    }
+
+    if !ip_config.routing_rules4.is_empty() {
+        ipv4_dbus.insert(
+            "routing-rules",
+            ip_config
+                .routing_rules4
+                .iter()
+                .map(|rule| routing_rule_to_dbus(rule, AF_INET))
+                .collect::<Vec<HashMap<&str, Value>>>()
+                .into(),
+        );
+    }
+
     if let Some(gateway) = &ip_config.gateway4 {
         ipv4_dbus.insert("gateway", gateway.to_string().into());
     }
@@ -612,6 +713,22 @@ fn ip_config_to_ipv6_dbus<'a>(
         );
     }
 
+    if let Some(route_table) = ip_config.route_table6 {
+        ipv6_dbus.insert("route-table", route_table.into());
+    }
+
+    if !ip_config.routing_rules6.is_empty() {
+        ipv6_dbus.insert(
+            "routing-rules",
+            ip_config
+                .routing_rules6
+                .iter()
+                .map(|rule| routing_rule_to_dbus(rule, AF_INET6))
+                .collect::<Vec<HashMap<&str, Value>>>()
+                .into(),
+        );
+    }
+
     if let Some(gateway) = &ip_config.gateway6 {
         ipv6_dbus.insert("gateway", gateway.to_string().into());
     }
@@ -676,6 +793,39 @@ fn ip_config_to_ipv6_dbus<'a>(
     ipv6_dbus
 }
 
+/// Converts a routing rule into its D-Bus representation.
+///
+/// * `rule`: routing rule to convert.
+/// * `family`: address family (AF_INET or AF_INET6).
+fn routing_rule_to_dbus(rule: &RoutingRule, family: i32) -> HashMap<&str, Value<'_>> {
+    let mut rule_dbus: HashMap<&str, Value> = HashMap::from([
+        ("family", Value::new(family)),
+        ("priority", Value::new(rule.priority)),
+        ("table", Value::new(rule.table)),
+    ]);
+
+    if let Some(from) = &rule.from {
+        rule_dbus.insert("from", Value::new(from.address().to_string()));
+        rule_dbus.insert("from-len", Value::new(from.network_length()));
+    }
+
+    if let Some(to) = &rule.to {
+        rule_dbus.insert("to", Value::new(to.address().to_string()));
+        rule_dbus.insert("to-len", Value::new(to.network_length()));
+    }
+
+    if let Some(iif) = &rule.iif {
+        rule_dbus.insert("iifname", Value::new(iif.as_str()));
+    }
+
+    if let Some(fwmark) = rule.fwmark {
+        rule_dbus.insert("fwmark", Value::new(fwmark));
+        rule_dbus.insert("fwmask", Value::new(u32::MAX));
+    }
+
+    rule_dbus
+}
+
 fn wireless_config_to_dbus(config: &'_ WirelessConfig) -> NestedHash<'_> {
     let mut wireless: HashMap<&str, zvariant::Value> = HashMap::from([
         ("mode", Value::new(config.mode.to_string())),
@@ -901,6 +1051,155 @@ fn tun_config_from_dbus(conn: &OwnedNestedHash) -> Result<Option<TunConfig>, NmE
     }))
 }
 
+fn wireguard_config_to_dbus(config: &WireguardConfig) -> HashMap<&str, zvariant::Value<'_>> {
+    let peers: Vec<HashMap<&str, Value>> = config
+        .peers
+        .iter()
+        .map(|peer| {
+            let mut peer_dbus = HashMap::from([
+                ("public-key", Value::new(peer.public_key.as_str())),
+                ("allowed-ips", Value::new(peer.allowed_ips.clone())),
+            ]);
+
+            if let Some(endpoint) = &peer.endpoint {
+                peer_dbus.insert("endpoint", endpoint.as_str().into());
+            }
+
+            if let Some(preshared_key) = &peer.preshared_key {
+                peer_dbus.insert("preshared-key", preshared_key.as_str().into());
+                // store the key in the connection file
+                peer_dbus.insert("preshared-key-flags", 0_u32.into());
+            }
+
+            if let Some(keepalive) = peer.persistent_keepalive {
+                peer_dbus.insert("persistent-keepalive", keepalive.into());
+            }
+
+            peer_dbus
+        })
+        .collect();
+
+    let mut wireguard_config: HashMap<&str, zvariant::Value> =
+        HashMap::from([("peers", peers.into())]);
+
+    if let Some(private_key) = &config.private_key {
+        wireguard_config.insert("private-key", private_key.as_str().into());
+        // store the key in the connection file
+        wireguard_config.insert("private-key-flags", 0_u32.into());
+    }
+
+    if let Some(listen_port) = config.listen_port {
+        wireguard_config.insert("listen-port", listen_port.into());
+    }
+
+    wireguard_config
+}
+
+fn wireguard_config_from_dbus(conn: &OwnedNestedHash) -> Result<Option<WireguardConfig>, NmError> {
+    let Some(wireguard) = conn.get(WIREGUARD_KEY) else {
+        return Ok(None);
+    };
+
+    let mut config = WireguardConfig {
+        private_key: get_optional_property(wireguard, "private-key")?,
+        listen_port: get_optional_property(wireguard, "listen-port")?,
+        ..Default::default()
+    };
+
+    if let Some(peers) = wireguard.get("peers") {
+        config.peers = wireguard_peers_from_dbus(peers)?;
+    }
+
+    Ok(Some(config))
+}
+
+fn wireguard_peers_from_dbus(peers_data: &OwnedValue) -> Result<Vec<WireguardPeer>, NmError> {
+    let peers_data = peers_data.downcast_ref::<zbus::zvariant::Array>()?;
+    let mut peers: Vec<WireguardPeer> = vec![];
+    for peer in peers_data.iter() {
+        let dict = peer.downcast_ref::<zvariant::Dict>()?;
+        let map = <HashMap<String, zvariant::Value<'_>>>::try_from(dict)?;
+        let map = to_owned_hash(&map)?;
+
+        let mut allowed_ips: Vec<String> = vec![];
+        if let Ok(ips) = get_property::<zbus::zvariant::Array>(&map, "allowed-ips") {
+            allowed_ips = ips
+                .iter()
+                .flat_map(|x| x.downcast_ref::<String>())
+                .collect();
+        }
+
+        peers.push(WireguardPeer {
+            public_key: get_property(&map, "public-key")?,
+            preshared_key: get_optional_property(&map, "preshared-key")?,
+            endpoint: get_optional_property(&map, "endpoint")?,
+            allowed_ips,
+            persistent_keepalive: get_optional_property(&map, "persistent-keepalive")?,
+        })
+    }
+    Ok(peers)
+}
+
+fn vrf_config_from_dbus(conn: &OwnedNestedHash) -> Result<Option<VrfConfig>, NmError> {
+    let Some(vrf) = conn.get(VRF_KEY) else {
+        return Ok(None);
+    };
+
+    Ok(Some(VrfConfig {
+        table: get_optional_property(vrf, "table")?.unwrap_or_default(),
+    }))
+}
+
+fn macvlan_config_from_dbus(conn: &OwnedNestedHash) -> Result<Option<MacvlanConfig>, NmError> {
+    let Some(macvlan) = conn.get(MACVLAN_KEY) else {
+        return Ok(None);
+    };
+
+    // NetworkManager omits the mode when it has the default value
+    let mode = match get_optional_property::<u32>(macvlan, "mode")? {
+        None | Some(2) => MacvlanMode::Bridge,
+        Some(1) => MacvlanMode::Vepa,
+        Some(3) => MacvlanMode::Private,
+        Some(4) => MacvlanMode::Passthru,
+        Some(5) => MacvlanMode::Source,
+        Some(mode) => return Err(NmError::UnsupportedMacvlanMode(mode)),
+    };
+
+    Ok(Some(MacvlanConfig {
+        parent: get_optional_property(macvlan, "parent")?.unwrap_or_default(),
+        mode,
+    }))
+}
+
+fn ipvlan_config_from_dbus(conn: &OwnedNestedHash) -> Result<Option<IpvlanConfig>, NmError> {
+    let Some(ipvlan) = conn.get(IPVLAN_KEY) else {
+        return Ok(None);
+    };
+
+    // NetworkManager omits the mode when it has the default value
+    let mode = match get_optional_property::<u32>(ipvlan, "mode")? {
+        None | Some(1) => IpvlanMode::L2,
+        Some(2) => IpvlanMode::L3,
+        Some(3) => IpvlanMode::L3S,
+        Some(mode) => return Err(NmError::UnsupportedIpvlanMode(mode)),
+    };
+
+    Ok(Some(IpvlanConfig {
+        parent: get_optional_property(ipvlan, "parent")?.unwrap_or_default(),
+        mode,
+    }))
+}
+
+fn veth_config_from_dbus(conn: &OwnedNestedHash) -> Result<Option<VethConfig>, NmError> {
+    let Some(veth) = conn.get(VETH_KEY) else {
+        return Ok(None);
+    };
+
+    Ok(Some(VethConfig {
+        peer: get_optional_property(veth, "peer")?.unwrap_or_default(),
+    }))
+}
+
 fn ovs_bridge_config_to_dbus(br: &OvsBridgeConfig) -> HashMap<&str, zvariant::Value<'_>> {
     let mut br_config: HashMap<&str, zvariant::Value> = HashMap::new();
 
@@ -1151,6 +1450,13 @@ fn ip_config_from_dbus(conn: &OwnedNestedHash) -> Result<IpConfig, NmError> {
             ip_config.routes4 = routes_from_dbus(route_data)?;
         }
 
+        ip_config.route_table4 =
+            get_optional_property::<u32>(ipv4, "route-table")?.filter(|table| *table != 0);
+
+        if let Some(rules_data) = ipv4.get("routing-rules") {
+            ip_config.routing_rules4 = routing_rules_from_dbus(rules_data)?;
+        }
+
         if let Ok(gateway) = get_property::<String>(ipv4, "gateway") {
             ip_config.gateway4 = gateway.parse().ok();
         }
@@ -1235,6 +1541,13 @@ fn ip_config_from_dbus(conn: &OwnedNestedHash) -> Result<IpConfig, NmError> {
             ip_config.routes6 = routes_from_dbus(route_data)?;
         }
 
+        ip_config.route_table6 =
+            get_optional_property::<u32>(ipv6, "route-table")?.filter(|table| *table != 0);
+
+        if let Some(rules_data) = ipv6.get("routing-rules") {
+            ip_config.routing_rules6 = routing_rules_from_dbus(rules_data)?;
+        }
+
         if let Ok(gateway) = get_property::<String>(ipv6, "gateway") {
             ip_config.gateway6 = gateway.parse().ok();
         }
@@ -1308,16 +1621,82 @@ fn routes_from_dbus(route_data: &OwnedValue) -> Result<Vec<IpRoute>, NmError> {
             destination,
             next_hop: None,
             metric: None,
+            table: None,
         };
         if let Some(next_hop) = get_optional_property::<String>(&map, "next-hop")? {
             new_route.next_hop = Some(IpAddr::from_str(next_hop.as_str()).unwrap());
         }
         new_route.metric = get_optional_property(&map, "metric")?;
+        new_route.table = get_optional_property(&map, "table")?;
         routes.push(new_route)
     }
     Ok(routes)
 }
 
+/// Routing rule attributes supported by Agama.
+const ROUTING_RULE_KEYS: [&str; 10] = [
+    "family", "priority", "table", "from", "from-len", "to", "to-len", "iifname", "fwmark",
+    "fwmask",
+];
+
+/// NetworkManager "action" for rules looking up a routing table (FR_ACT_TO_TBL).
+const ROUTING_RULE_ACTION_TO_TABLE: u8 = 1;
+
+/// Reads the routing rules from D-Bus.
+///
+/// When any rule uses an attribute not supported by Agama (e.g., a "prohibit" action or an
+/// "oifname"), no rule is imported. In that case, the rules are not written back either, so
+/// NetworkManager keeps the original ones untouched (see [merge_dbus_connections]).
+fn routing_rules_from_dbus(rules_data: &OwnedValue) -> Result<Vec<RoutingRule>, NmError> {
+    let rules_data = rules_data.downcast_ref::<zbus::zvariant::Array>()?;
+    let mut rules: Vec<RoutingRule> = vec![];
+    for rule in rules_data.iter() {
+        let dict = rule.downcast_ref::<zvariant::Dict>()?;
+        let map = <HashMap<String, zvariant::Value<'_>>>::try_from(dict)?;
+        let map = to_owned_hash(&map)?;
+
+        if !is_supported_routing_rule(&map)? {
+            tracing::info!("Keeping the routing rules as they use unsupported attributes");
+            return Ok(vec![]);
+        }
+
+        let mut new_rule = RoutingRule {
+            priority: get_optional_property(&map, "priority")?.unwrap_or_default(),
+            table: get_optional_property(&map, "table")?.unwrap_or_default(),
+            iif: get_optional_property(&map, "iifname")?,
+            fwmark: get_optional_property(&map, "fwmark")?,
+            ..Default::default()
+        };
+        if let Some(from) = get_optional_property::<String>(&map, "from")? {
+            let len: u8 = get_optional_property(&map, "from-len")?.unwrap_or_default();
+            new_rule.from = Some(IpInet::new(from.parse()?, len)?);
+        }
+        if let Some(to) = get_optional_property::<String>(&map, "to")? {
+            let len: u8 = get_optional_property(&map, "to-len")?.unwrap_or_default();
+            new_rule.to = Some(IpInet::new(to.parse()?, len)?);
+        }
+        rules.push(new_rule)
+    }
+    Ok(rules)
+}
+
+/// Whether Agama can represent the given routing rule without losing information.
+fn is_supported_routing_rule(map: &HashMap<String, OwnedValue>) -> Result<bool, NmError> {
+    let action = get_optional_property::<u8>(map, "action")?;
+    let fwmask = get_optional_property::<u32>(map, "fwmask")?;
+    let invert = get_optional_property::<bool>(map, "invert")?;
+
+    let keys_supported = map
+        .keys()
+        .filter(|k| !["action", "invert"].contains(&k.as_str()))
+        .all(|k| ROUTING_RULE_KEYS.contains(&k.as_str()));
+
+    Ok(keys_supported
+        && action.is_none_or(|a| a == ROUTING_RULE_ACTION_TO_TABLE)
+        && fwmask.is_none_or(|m| m == u32::MAX)
+        && !invert.unwrap_or_default())
+}
+
 fn nameservers_from_dbus(dns_data: &OwnedValue) -> Result<Vec<IpAddr>, NmError> {
     let dns_data = dns_data.downcast_ref::<zbus::zvariant::Array>()?;
     let mut servers: Vec<IpAddr> = vec![];
@@ -1678,8 +2057,8 @@ mod test {
         model::*,
         nm::{
             dbus::{
-                BOND_KEY, BRIDGE_KEY, ETHERNET_KEY, INFINIBAND_KEY, VLAN_KEY, WIRELESS_KEY,
-                WIRELESS_SECURITY_KEY,
+                AF_INET, AF_INET6, BOND_KEY, BRIDGE_KEY, ETHERNET_KEY, INFINIBAND_KEY, IPVLAN_KEY,
+                MACVLAN_KEY, VLAN_KEY, VRF_KEY, WIREGUARD_KEY, WIRELESS_KEY, WIRELESS_SECURITY_KEY,
             },
             error::NmError,
         },
@@ -1809,7 +2188,8 @@ mod test {
             vec![IpRoute {
                 destination: IpInet::new("192.168.0.0".parse().unwrap(), 24_u8).unwrap(),
                 next_hop: Some(IpAddr::from_str("192.168.0.1").unwrap()),
-                metric: Some(100)
+                metric: Some(100),
+                table: None
             }]
         );
         assert_eq!(
@@ -1817,7 +2197,8 @@ mod test {
             vec![IpRoute {
                 destination: IpInet::new("2001:db8::".parse().unwrap(), 64_u8).unwrap(),
                 next_hop: Some(IpAddr::from_str("2001:db8::1").unwrap()),
-                metric: Some(100)
+                metric: Some(100),
+                table: None
             }]
         );
         assert!(!connection.autoconnect);
@@ -2093,6 +2474,255 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_connection_from_dbus_wireguard() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "wg0")?, hi("uuid", uuid)?]);
+
+        let peers = vec![HashMap::from([
+            hi("public-key", "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=")?,
+            hi("endpoint", "mirror.example.net:51820")?,
+            hi("allowed-ips", vec!["10.10.0.0/16", "fd00::/64"])?,
+            hi("persistent-keepalive", 25_u32)?,
+        ])];
+
+        let wireguard_section = HashMap::from([hi("listen-port", 51820_u32)?, hi("peers", peers)?]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            (WIREGUARD_KEY.to_string(), wireguard_section),
+        ]);
+
+        let connection = connection_from_dbus(dbus_conn).unwrap();
+        let ConnectionConfig::Wireguard(wireguard) = &connection.config else {
+            panic!("Wrong connection type")
+        };
+        assert_eq!(wireguard.private_key, None);
+        assert_eq!(wireguard.listen_port, Some(51820));
+        let peer = wireguard.peers.first().unwrap();
+        assert_eq!(
+            peer.public_key,
+            "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg="
+        );
+        assert_eq!(peer.endpoint, Some("mirror.example.net:51820".to_string()));
+        assert_eq!(peer.allowed_ips, vec!["10.10.0.0/16", "fd00::/64"]);
+        assert_eq!(peer.persistent_keepalive, Some(25));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_from_dbus_macvlan() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "macvlan0")?, hi("uuid", uuid)?]);
+        let macvlan_section = HashMap::from([hi("parent", "eth0")?, hi("mode", 3_u32)?]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            (MACVLAN_KEY.to_string(), macvlan_section),
+        ]);
+
+        let connection = connection_from_dbus(dbus_conn).unwrap();
+        let ConnectionConfig::Macvlan(macvlan) = &connection.config else {
+            panic!("Wrong connection type")
+        };
+        assert_eq!(macvlan.parent, "eth0");
+        assert_eq!(macvlan.mode, MacvlanMode::Private);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_from_dbus_unknown_macvlan_mode() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "macvlan0")?, hi("uuid", uuid)?]);
+        let macvlan_section = HashMap::from([hi("parent", "eth0")?, hi("mode", 16_u32)?]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            (MACVLAN_KEY.to_string(), macvlan_section),
+        ]);
+
+        let error = connection_from_dbus(dbus_conn).unwrap_err();
+        assert!(matches!(error, NmError::UnsupportedMacvlanMode(16)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_from_dbus_unknown_ipvlan_mode() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "ipvlan0")?, hi("uuid", uuid)?]);
+        let ipvlan_section = HashMap::from([hi("parent", "eth0")?, hi("mode", 7_u32)?]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            (IPVLAN_KEY.to_string(), ipvlan_section),
+        ]);
+
+        let error = connection_from_dbus(dbus_conn).unwrap_err();
+        assert!(matches!(error, NmError::UnsupportedIpvlanMode(7)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_from_dbus_vrf() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "mgmt")?, hi("uuid", uuid)?]);
+        let vrf_section = HashMap::from([hi("table", 100_u32)?]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            (VRF_KEY.to_string(), vrf_section),
+        ]);
+
+        let connection = connection_from_dbus(dbus_conn).unwrap();
+        assert_eq!(
+            connection.config,
+            ConnectionConfig::Vrf(VrfConfig { table: 100 })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_from_dbus_routing_rules() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "eth1")?, hi("uuid", uuid)?]);
+
+        let rules_data = vec![HashMap::from([
+            hi("family", AF_INET)?,
+            hi("priority", 1000_u32)?,
+            hi("from", "192.168.10.0")?,
+            hi("from-len", 24_u8)?,
+            hi("iifname", "eth1")?,
+            hi("table", 100_u32)?,
+        ])];
+        let route_data = vec![HashMap::from([
+            hi("dest", "0.0.0.0")?,
+            hi("prefix", 0_u32)?,
+            hi("next-hop", "192.168.10.1")?,
+            hi("table", 100_u32)?,
+        ])];
+        let ipv4_section = HashMap::from([
+            hi("method", "auto")?,
+            hi("route-table", 100_u32)?,
+            hi("route-data", route_data)?,
+            hi("routing-rules", rules_data)?,
+        ]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            ("ipv4".to_string(), ipv4_section),
+            (ETHERNET_KEY.to_string(), HashMap::new()),
+        ]);
+
+        let connection = connection_from_dbus(dbus_conn).unwrap();
+        let ip_config = connection.ip_config;
+        assert_eq!(ip_config.route_table4, Some(100));
+        assert_eq!(ip_config.routes4[0].table, Some(100));
+        assert_eq!(
+            ip_config.routing_rules4,
+            vec![RoutingRule {
+                priority: 1000,
+                from: Some("192.168.10.0/24".parse().unwrap()),
+                iif: Some("eth1".to_string()),
+                table: 100,
+                ..Default::default()
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_from_dbus_unsupported_routing_rules() -> anyhow::Result<()> {
+        let uuid = Uuid::new_v4().to_string();
+        let connection_section = HashMap::from([hi("id", "eth1")?, hi("uuid", uuid)?]);
+
+        let rules_data = vec![
+            HashMap::from([
+                hi("family", AF_INET)?,
+                hi("priority", 1000_u32)?,
+                hi("iifname", "eth1")?,
+                hi("table", 100_u32)?,
+            ]),
+            // prohibit rule
+            HashMap::from([
+                hi("family", AF_INET)?,
+                hi("priority", 2000_u32)?,
+                hi("to", "10.0.0.0")?,
+                hi("to-len", 8_u8)?,
+                hi("action", 3_u8)?,
+            ]),
+        ];
+        let ipv4_section = HashMap::from([hi("method", "auto")?, hi("routing-rules", rules_data)?]);
+
+        let dbus_conn = HashMap::from([
+            ("connection".to_string(), connection_section),
+            ("ipv4".to_string(), ipv4_section),
+            (ETHERNET_KEY.to_string(), HashMap::new()),
+        ]);
+
+        let connection = connection_from_dbus(dbus_conn).unwrap();
+        assert!(connection.ip_config.routing_rules4.is_empty());
+        assert!(connection.validate().is_ok());
+
+        // the original rules are not overwritten
+        let conn_dbus = connection_to_dbus(&connection, None, semver::Version::new(1, 50, 0));
+        let ipv4_dbus = conn_dbus.get("ipv4").unwrap();
+        assert!(!ipv4_dbus.contains_key("routing-rules"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dbus_from_routing_rules() -> anyhow::Result<()> {
+        let mut conn = build_base_connection();
+        conn.ip_config.route_table6 = Some(200);
+        conn.ip_config.routing_rules6 = vec![RoutingRule {
+            priority: 2000,
+            to: Some("2001:db8::/64".parse().unwrap()),
+            table: 200,
+            fwmark: Some(0x10),
+            ..Default::default()
+        }];
+        let conn_dbus = connection_to_dbus(&conn, None, semver::Version::new(1, 50, 0));
+
+        let ipv6_dbus = conn_dbus.get("ipv6").unwrap();
+        let route_table = ipv6_dbus
+            .get("route-table")
+            .unwrap()
+            .downcast_ref::<u32>()?;
+        assert_eq!(route_table, 200);
+
+        let rules: Array = ipv6_dbus
+            .get("routing-rules")
+            .unwrap()
+            .downcast_ref::<Value>()?
+            .try_into()?;
+        let rule: Dict = rules
+            .iter()
+            .next()
+            .unwrap()
+            .downcast_ref::<Value>()?
+            .try_into()?;
+        let rule: HashMap<String, Value> = rule.try_into()?;
+        assert_eq!(rule["family"], Value::from(AF_INET6));
+        assert_eq!(rule["priority"], Value::from(2000_u32));
+        assert_eq!(rule["to"], Value::from("2001:db8::"));
+        assert_eq!(rule["to-len"], Value::from(64_u8));
+        assert_eq!(rule["table"], Value::from(200_u32));
+        assert_eq!(rule["fwmark"], Value::from(0x10_u32));
+        assert!(!rule.contains_key("from"));
+
+        let ipv4_dbus = conn_dbus.get("ipv4").unwrap();
+        assert!(!ipv4_dbus.contains_key("routing-rules"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_connection_from_dbus_ieee_8021x() -> anyhow::Result<()> {
         let connection_section = HashMap::from([
@@ -2187,6 +2817,105 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_dbus_from_wireguard_connection() -> anyhow::Result<()> {
+        let config = WireguardConfig {
+            private_key: Some("yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=".to_string()),
+            listen_port: Some(51820),
+            peers: vec![WireguardPeer {
+                public_key: "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=".to_string(),
+                endpoint: Some("mirror.example.net:51820".to_string()),
+                allowed_ips: vec!["10.10.0.0/16".to_string()],
+                ..Default::default()
+            }],
+        };
+        let mut wireguard = build_base_connection();
+        wireguard.config = ConnectionConfig::Wireguard(config);
+        let wireguard_dbus = connection_to_dbus(&wireguard, None, semver::Version::new(1, 50, 0));
+
+        let connection = wireguard_dbus.get("connection").unwrap();
+        let conn_type: &str = connection.get("type").unwrap().downcast_ref()?;
+        assert_eq!(conn_type, WIREGUARD_KEY);
+
+        let wireguard = wireguard_dbus.get(WIREGUARD_KEY).unwrap();
+        let private_key: &str = wireguard.get("private-key").unwrap().downcast_ref()?;
+        assert_eq!(private_key, "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=");
+        let listen_port = wireguard
+            .get("listen-port")
+            .unwrap()
+            .downcast_ref::<u32>()?;
+        assert_eq!(listen_port, 51820);
+
+        let peers = wireguard
+            .get("peers")
+            .unwrap()
+            .downcast_ref::<zbus::zvariant::Array>()?;
+        assert_eq!(peers.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dbus_from_ipvlan_connection() -> anyhow::Result<()> {
+        let mut ipvlan = build_base_connection();
+        ipvlan.config = ConnectionConfig::Ipvlan(IpvlanConfig {
+            parent: "eth0".to_string(),
+            mode: IpvlanMode::L3,
+        });
+        let ipvlan_dbus = connection_to_dbus(&ipvlan, None, semver::Version::new(1, 52, 0));
+
+        let connection = ipvlan_dbus.get("connection").unwrap();
+        let conn_type: &str = connection.get("type").unwrap().downcast_ref()?;
+        assert_eq!(conn_type, IPVLAN_KEY);
+
+        let ipvlan = ipvlan_dbus.get(IPVLAN_KEY).unwrap();
+        let parent: &str = ipvlan.get("parent").unwrap().downcast_ref()?;
+        assert_eq!(parent, "eth0");
+        let mode = ipvlan.get("mode").unwrap().downcast_ref::<u32>()?;
+        assert_eq!(mode, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dbus_from_veth_connection_with_mtu() -> anyhow::Result<()> {
+        let mut veth = build_base_connection();
+        veth.mtu = 9000;
+        veth.custom_mac_address = MacAddress::from_str("12:34:56:78:9A:BC")?;
+        veth.config = ConnectionConfig::Veth(VethConfig {
+            peer: "veth1".to_string(),
+        });
+        let veth_dbus = connection_to_dbus(&veth, None, semver::Version::new(1, 52, 0));
+
+        let ethernet = veth_dbus.get(ETHERNET_KEY).unwrap();
+        let mtu = ethernet.get("mtu").unwrap().downcast_ref::<u32>()?;
+        assert_eq!(mtu, 9000);
+        let mac: &str = ethernet
+            .get("assigned-mac-address")
+            .unwrap()
+            .downcast_ref()?;
+        assert_eq!(mac, "12:34:56:78:9A:BC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dbus_from_vrf_port() -> anyhow::Result<()> {
+        let mut vrf = build_base_connection();
+        vrf.interface = Some("mgmt".to_string());
+        vrf.config = ConnectionConfig::Vrf(VrfConfig { table: 100 });
+        let port = build_base_connection();
+        let port_dbus = connection_to_dbus(&port, Some(&vrf), semver::Version::new(1, 50, 0));
+
+        let connection = port_dbus.get("connection").unwrap();
+        let port_type: &str = connection.get("port-type").unwrap().downcast_ref()?;
+        assert_eq!(port_type, VRF_KEY);
+        let controller: &str = connection.get("master").unwrap().downcast_ref()?;
+        assert_eq!(controller, "mgmt");
+
+        Ok(())
+    }
+
     #[test]
     fn test_dbus_from_wireless_connection() -> anyhow::Result<()> {
         let config = WirelessConfig {
@@ -2549,11 +3278,13 @@ mod test {
                 destination: IpInet::new("192.168.0.0".parse().unwrap(), 24_u8).unwrap(),
                 next_hop: Some(IpAddr::from_str("192.168.0.1").unwrap()),
                 metric: Some(100),
+                table: None,
             }],
             routes6: vec![IpRoute {
                 destination: IpInet::new("2001:db8::".parse().unwrap(), 64_u8).unwrap(),
                 next_hop: Some(IpAddr::from_str("2001:db8::1").unwrap()),
                 metric: Some(100),
+                table: None,
             }],
             dns_searchlist: vec!["suse.com".to_string(), "suse.de".to_string()],
             dhcp4_settings: Some(Dhcp4Settings {
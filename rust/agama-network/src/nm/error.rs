@@ -48,6 +48,10 @@ pub enum NmError {
     UnsupportedSecurityProtocol(String),
     #[error("Unsupported wireless mode: '{0}'")]
     UnsupportedWirelessMode(String),
+    #[error("Unsupported MACVLAN mode: '{0}'")]
+    UnsupportedMacvlanMode(u32),
+    #[error("Unsupported IPVLAN mode: '{0}'")]
+    UnsupportedIpvlanMode(u32),
     #[error("Missing connection information")]
     MissingConnectionSection,
     #[error("Invalid device name: '{0}'")]
@@ -105,7 +105,12 @@ impl TryFrom<NmDeviceType> for DeviceType {
             NmDeviceType(10) => Ok(DeviceType::Bond),
             NmDeviceType(11) => Ok(DeviceType::Vlan),
             NmDeviceType(13) => Ok(DeviceType::Bridge),
+            NmDeviceType(18) => Ok(DeviceType::Macvlan),
+            NmDeviceType(20) => Ok(DeviceType::Veth),
             NmDeviceType(22) => Ok(DeviceType::Dummy),
+            NmDeviceType(29) => Ok(DeviceType::Wireguard),
+            NmDeviceType(31) => Ok(DeviceType::Vrf),
+            NmDeviceType(34) => Ok(DeviceType::Ipvlan),
             NmDeviceType(32) => Ok(DeviceType::Loopback),
             NmDeviceType(_) => Err(NmError::UnsupportedDeviceType(value.into())),
         }
@@ -0,0 +1,267 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Connectivity checks to run after applying a network configuration.
+//!
+//! The idea is to find out whether the system is still reachable (e.g., by the gateways or by the
+//! remote clients, like the web UI or an SSH session), so the changes can be rolled back if they
+//! are not.
+
+use async_trait::async_trait;
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str,
+};
+use tokio::{
+    process::Command,
+    time::{sleep, Duration},
+};
+
+const TCP_ESTABLISHED: &str = "01";
+const TCP_LISTEN: &str = "0A";
+const RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Checks whether an address is reachable.
+#[async_trait]
+pub trait Pinger: Send + Sync {
+    /// Whether the given address is reachable.
+    ///
+    /// * `address`: address to check.
+    async fn ping(&self, address: &IpAddr) -> bool;
+}
+
+/// Pinger based on the `ping` command.
+pub struct CommandPinger;
+
+#[async_trait]
+impl Pinger for CommandPinger {
+    async fn ping(&self, address: &IpAddr) -> bool {
+        ping(address).await
+    }
+}
+
+/// Checks whether any of the given addresses is reachable.
+pub struct ConnectivityProbe {
+    targets: Vec<IpAddr>,
+    attempts: u32,
+    pinger: Box<dyn Pinger>,
+}
+
+impl ConnectivityProbe {
+    /// Creates a probe for the given addresses.
+    ///
+    /// * `targets`: addresses to check.
+    pub fn new(targets: Vec<IpAddr>) -> Self {
+        let mut unique = vec![];
+        for target in targets {
+            if !unique.contains(&target) {
+                unique.push(target);
+            }
+        }
+
+        Self {
+            targets: unique,
+            attempts: 1,
+            pinger: Box::new(CommandPinger),
+        }
+    }
+
+    /// Sets the pinger to check the targets.
+    ///
+    /// * `pinger`: pinger to use.
+    pub fn with_pinger(mut self, pinger: impl Pinger + 'static) -> Self {
+        self.pinger = Box::new(pinger);
+        self
+    }
+
+    /// Sets the number of attempts before giving up.
+    ///
+    /// * `attempts`: number of attempts.
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// Whether there is nothing to check.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Whether any of the targets is reachable.
+    ///
+    /// It returns false if there are no targets.
+    pub async fn is_reachable(&self) -> bool {
+        for attempt in 1..=self.attempts {
+            for target in &self.targets {
+                if self.pinger.ping(target).await {
+                    tracing::info!("{} is reachable (attempt {})", target, attempt);
+                    return true;
+                }
+            }
+
+            if attempt < self.attempts {
+                sleep(RETRY_INTERVAL).await;
+            }
+        }
+
+        false
+    }
+}
+
+/// Sends an ICMP echo request to the given address.
+///
+/// If the request cannot be sent (e.g., the `ping` command is missing), the address is considered
+/// unreachable. As the same check runs before applying the changes, an unusable `ping` command
+/// just disables the automatic rollback.
+///
+/// * `address`: address to check.
+async fn ping(address: &IpAddr) -> bool {
+    let output = Command::new("ping")
+        .args(["-c", "1", "-W", "2", &address.to_string()])
+        .output()
+        .await;
+
+    match output {
+        Ok(output) => output.status.success(),
+        Err(error) => {
+            tracing::warn!(
+                "Could not check whether {} is reachable: {}",
+                address,
+                error
+            );
+            false
+        }
+    }
+}
+
+/// Returns the addresses of the remote clients connected to the system.
+///
+/// It considers the established TCP connections to any of the listening ports, which includes the
+/// web server and the SSH daemon.
+pub fn remote_clients() -> Vec<IpAddr> {
+    let mut clients = vec![];
+    for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                for client in remote_clients_from(&content) {
+                    if !clients.contains(&client) {
+                        clients.push(client);
+                    }
+                }
+            }
+            Err(error) => tracing::warn!("Could not read {}: {}", path, error),
+        }
+    }
+    clients
+}
+
+/// Returns the remote clients from the content of a /proc/net/tcp-like file.
+///
+/// * `content`: file content.
+fn remote_clients_from(content: &str) -> Vec<IpAddr> {
+    let sockets: Vec<_> = content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().skip(1);
+            let local = parse_socket_address(fields.next()?)?;
+            let remote = parse_socket_address(fields.next()?)?;
+            let state = fields.next()?;
+            Some((local, remote, state))
+        })
+        .collect();
+
+    let listening: Vec<u16> = sockets
+        .iter()
+        .filter(|(_, _, state)| *state == TCP_LISTEN)
+        .map(|((_, port), _, _)| *port)
+        .collect();
+
+    let mut clients = vec![];
+    for ((_, local_port), (remote_ip, _), state) in sockets {
+        if state != TCP_ESTABLISHED
+            || !listening.contains(&local_port)
+            || remote_ip.is_loopback()
+            || clients.contains(&remote_ip)
+        {
+            continue;
+        }
+        clients.push(remote_ip);
+    }
+    clients
+}
+
+/// Parses a socket address as written in /proc/net/tcp (e.g., "0100007F:0016").
+///
+/// The address is written as a sequence of 32-bit words in the host byte order.
+///
+/// * `text`: socket address.
+fn parse_socket_address(text: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = vec![];
+    for word in address.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(str::from_utf8(word).ok()?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(bytes).ok()?)),
+        16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => return None,
+    };
+    Some((ip.to_canonical(), port))
+}
+
+#[cfg(all(test, target_endian = "little"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_clients_from_tcp() {
+        let content = r#"  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1001 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:0016 0202000A:D431 01 00000000:00000000 02:00096E3B 00000000     0        0 1002 2 0000000000000000 20 4 31 10 -1
+   2: 0100007F:0016 0100007F:A0B2 01 00000000:00000000 02:00096E3B 00000000     0        0 1003 2 0000000000000000 20 4 31 10 -1
+   3: 0F02000A:B5C2 5DB8D8C0:01BB 01 00000000:00000000 02:00096E3B 00000000     0        0 1004 2 0000000000000000 20 4 31 10 -1
+"#;
+        let clients = remote_clients_from(content);
+        assert_eq!(clients, vec!["10.0.2.2".parse::<IpAddr>().unwrap()]);
+    }
+
+    #[test]
+    fn test_remote_clients_from_tcp6() {
+        let content = r#"  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:01BB 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 2001 1 0000000000000000 100 0 0 10 0
+   1: B80D0120000000000000000002000000:01BB B80D0120000000000000000001000000:C350 01 00000000:00000000 02:00096E3B 00000000     0        0 2002 2 0000000000000000 20 4 31 10 -1
+   2: 0000000000000000FFFF00000F02000A:01BB 0000000000000000FFFF00000302000A:C351 01 00000000:00000000 02:00096E3B 00000000     0        0 2003 2 0000000000000000 20 4 31 10 -1
+"#;
+        let clients = remote_clients_from(content);
+        assert_eq!(
+            clients,
+            vec![
+                "2001:db8::1".parse::<IpAddr>().unwrap(),
+                "10.0.2.3".parse::<IpAddr>().unwrap(),
+            ]
+        );
+    }
+}
@@ -21,14 +21,15 @@
 use crate::{
     action::Action,
     error::NetworkStateError,
-    model::{Connection, GeneralState, NetworkChange, NetworkState, StateConfig},
+    import,
+    model::{Connection, GeneralState, NetworkChange, NetworkState, NetworkStateDiff, StateConfig},
     types::{AccessPoint, Config, Device, Proposal, SystemInfo},
     Adapter, NetworkAdapterError, NetworkManagerAdapter,
 };
 use agama_utils::{
     actor::Handler,
-    api::{event, Event, Scope},
-    progress,
+    api::{event, Event, Issue, Scope},
+    issue, progress,
 };
 use gettextrs::gettext;
 use std::error::Error;
@@ -55,6 +56,7 @@ pub struct Starter {
     adapter: Option<Box<dyn Adapter + Send + 'static>>,
     events: event::Sender,
     progress: Handler<progress::Service>,
+    issues: Handler<issue::Service>,
 }
 
 impl Starter {
@@ -63,12 +65,19 @@ impl Starter {
     /// This function does not start the system. To get it running, you must call
     /// the [start](Self::start) method.
     ///
-    /// * `adapter`: networking configuration adapter.
-    pub fn new(events: event::Sender, progress: Handler<progress::Service>) -> Self {
+    /// * `events`: channel to emit the events.
+    /// * `progress`: handler to the progress service.
+    /// * `issues`: handler to the issues service.
+    pub fn new(
+        events: event::Sender,
+        progress: Handler<progress::Service>,
+        issues: Handler<issue::Service>,
+    ) -> Self {
         Self {
             adapter: None,
             events,
             progress,
+            issues,
         }
     }
 
@@ -112,6 +121,7 @@ impl Starter {
             let mut server = Service {
                 events: self.events,
                 progress: self.progress,
+                issues: self.issues,
                 state,
                 input: actions_rx,
                 output: updates_tx_clone,
@@ -211,7 +221,10 @@ impl NetworkSystemClient {
         Ok(result)
     }
 
-    pub async fn apply(&self) -> Result<(), NetworkSystemError> {
+    /// Applies the current configuration and returns the changes that were made.
+    ///
+    /// If the system is not reachable after applying the changes, they are rolled back.
+    pub async fn apply(&self) -> Result<NetworkStateDiff, NetworkSystemError> {
         let (tx, rx) = oneshot::channel();
         self.actions.send(Action::Apply(tx))?;
         let result = rx.await?;
@@ -259,6 +272,7 @@ impl NetworkSystemClient {
 pub struct Service {
     events: event::Sender,
     progress: Handler<progress::Service>,
+    issues: Handler<issue::Service>,
     state: NetworkState,
     input: UnboundedReceiver<Action>,
     output: broadcast::Sender<NetworkChange>,
@@ -266,8 +280,12 @@ pub struct Service {
 }
 
 impl Service {
-    pub fn starter(events: event::Sender, progress: Handler<progress::Service>) -> Starter {
-        Starter::new(events, progress)
+    pub fn starter(
+        events: event::Sender,
+        progress: Handler<progress::Service>,
+        issues: Handler<issue::Service>,
+    ) -> Starter {
+        Starter::new(events, progress, issues)
     }
 
     /// Process incoming actions.
@@ -349,7 +367,14 @@ impl Service {
                 tx.send(config).unwrap();
             }
             Action::UpdateConfig(config, tx) => {
-                let result = self.state.update_state(*config);
+                let mut config = *config;
+                // The imported configuration is the only source of network issues, so they are
+                // replaced as a whole when the configuration changes.
+                let issues = self.import_connections(&mut config).await;
+                let result = self.state.update_state(config);
+                self.bind_to_target_names();
+                self.issues
+                    .cast(issue::message::Set::new(Scope::Network, issues))?;
                 tx.send(result).unwrap();
             }
             Action::GetConnections(tx) => {
@@ -467,6 +492,7 @@ impl Service {
             }
             Action::ProposeDefault(tx) => {
                 let result = self.state.propose_default();
+                self.bind_to_target_names();
                 tx.send(result).unwrap();
             }
             Action::Install(tx) => {
@@ -479,6 +505,58 @@ impl Service {
         Ok(None)
     }
 
+    /// Adds the connections from the configuration to import, if any, to the given one.
+    ///
+    /// The connections which are explicitly defined in the configuration take precedence over the
+    /// imported ones. It returns the issues found when importing the configuration.
+    ///
+    /// * `config`: network configuration.
+    async fn import_connections(&self, config: &mut Config) -> Vec<Issue> {
+        let Some(settings) = config.import.clone() else {
+            return vec![];
+        };
+
+        let imported = match import::import_async(settings).await {
+            Ok(imported) => imported,
+            Err(error) => {
+                tracing::error!("Could not import the network configuration: {error}");
+                return vec![Issue::new(
+                    "network.import_failed",
+                    &gettext("Could not import the network configuration"),
+                )
+                .with_details(&error.to_string())];
+            }
+        };
+
+        let connections = config.connections.get_or_insert_with(Default::default);
+        for conn in imported.connections {
+            if connections.0.iter().any(|c| c.id == conn.id) {
+                tracing::info!("Ignoring imported connection {}", &conn.id);
+            } else {
+                connections.0.push(conn);
+            }
+        }
+
+        if imported.unsupported.is_empty() {
+            return vec![];
+        }
+
+        vec![Issue::new(
+            "network.unsupported_settings",
+            &gettext("Some network settings could not be imported"),
+        )
+        .with_details(&imported.unsupported.join("\n"))]
+    }
+
+    /// Binds the connections to their devices when the interface names differ on the target.
+    ///
+    /// The renamed interfaces are not a problem for the installation, so they are only logged.
+    fn bind_to_target_names(&mut self) {
+        for rename in self.state.bind_to_target_names() {
+            tracing::info!("Interface renamed in the installed system: {rename}");
+        }
+    }
+
     /// Reads the system network configuration.
     pub async fn read(&mut self) -> Result<NetworkState, NetworkAdapterError> {
         self.adapter.read(StateConfig::default()).await
@@ -506,7 +584,9 @@ impl Service {
 
     /// Writes the network configuration based on current state and then replace the state from the
     /// one read from the system.
-    pub async fn apply(&mut self) -> Result<(), NetworkAdapterError> {
+    ///
+    /// Returns the changes that were applied.
+    pub async fn apply(&mut self) -> Result<NetworkStateDiff, NetworkAdapterError> {
         let steps = vec![
             gettext("Writing network configuration"),
             gettext("Syncing the network service state"),
@@ -517,13 +597,16 @@ impl Service {
             steps,
         ));
 
-        if let Err(e) = self.adapter.write(&self.state).await {
-            self.progress
-                .call(progress::message::Finish::new(Scope::Network))
-                .await?;
+        let diff = match self.adapter.write(&self.state).await {
+            Ok(diff) => diff,
+            Err(e) => {
+                self.progress
+                    .call(progress::message::Finish::new(Scope::Network))
+                    .await?;
 
-            return Err(e);
-        }
+                return Err(e);
+            }
+        };
         self.progress
             .cast(progress::message::Next::new(Scope::Network))?;
 
@@ -531,7 +614,7 @@ impl Service {
             Err(e) => Err(e),
             Ok(state) => {
                 self.state.sync_from(state);
-                Ok(())
+                Ok(diff)
             }
         };
 
@@ -20,12 +20,13 @@
 
 //! This module implements a set of utilities for tests.
 
-use agama_utils::{actor::Handler, api::event, progress};
+use agama_utils::{actor::Handler, api::event, issue, progress};
 use async_trait::async_trait;
 
 use crate::{
-    adapter::Watcher, model::StateConfig, Adapter, NetworkAdapterError, NetworkState,
-    NetworkSystemClient, Starter,
+    adapter::Watcher,
+    model::{NetworkStateDiff, StateConfig},
+    Adapter, NetworkAdapterError, NetworkState, NetworkSystemClient, Starter,
 };
 
 /// Network adapter for tests.
@@ -40,8 +41,11 @@ impl Adapter for TestAdapter {
         Ok(NetworkState::default())
     }
 
-    async fn write(&self, _network: &NetworkState) -> Result<(), NetworkAdapterError> {
-        Ok(())
+    async fn write(
+        &self,
+        _network: &NetworkState,
+    ) -> Result<NetworkStateDiff, NetworkAdapterError> {
+        Ok(NetworkStateDiff::default())
     }
 
     fn watcher(&self) -> Option<Box<dyn Watcher + Send>> {
@@ -53,10 +57,11 @@ impl Adapter for TestAdapter {
 pub async fn start_service(
     events: event::Sender,
     progress: Handler<progress::Service>,
+    issues: Handler<issue::Service>,
 ) -> NetworkSystemClient {
     let adapter = TestAdapter;
 
-    Starter::new(events, progress)
+    Starter::new(events, progress, issues)
         .with_adapter(adapter)
         .start()
         .await
@@ -0,0 +1,192 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Prediction of the interface names in the installed system.
+//!
+//! The names in the installer might differ from the ones in the installed system (e.g., the
+//! installer renamed an interface using the `ifname=` boot option). This module predicts the
+//! target names from the udev properties, following the default systemd naming policy.
+
+use agama_utils::kernel_cmdline::KernelCmdline;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+use tokio::process::Command;
+
+/// Properties containing the predictable names, in order of preference.
+///
+/// It matches systemd's default `NamePolicy` ("kernel database onboard slot path").
+const NAME_PROPERTIES: [&str; 4] = [
+    "ID_NET_NAME_FROM_DATABASE",
+    "ID_NET_NAME_ONBOARD",
+    "ID_NET_NAME_SLOT",
+    "ID_NET_NAME_PATH",
+];
+
+/// udev properties of a network interface.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UdevProperties(HashMap<String, String>);
+
+impl UdevProperties {
+    /// Returns the properties of the given interface, reading them only once per device.
+    ///
+    /// The properties are cached by interface name and hardware address, so they are read again
+    /// if a different device takes the name. It returns an empty set of properties if they cannot
+    /// be read, trying again on the next call.
+    ///
+    /// * `interface`: interface name.
+    /// * `hw_address`: hardware address of the interface.
+    pub async fn cached(interface: &str, hw_address: &str) -> Self {
+        static CACHE: OnceLock<Mutex<HashMap<(String, String), UdevProperties>>> = OnceLock::new();
+        let cache = CACHE.get_or_init(Default::default);
+        let key = (interface.to_string(), hw_address.to_string());
+
+        if let Some(properties) = cache.lock().unwrap().get(&key) {
+            return properties.clone();
+        }
+
+        let Some(properties) = Self::read(interface).await else {
+            return Self::default();
+        };
+        cache.lock().unwrap().insert(key, properties.clone());
+        properties
+    }
+
+    /// Reads the properties of the given interface.
+    ///
+    /// It returns `None` if they cannot be read.
+    ///
+    /// * `interface`: interface name.
+    pub async fn read(interface: &str) -> Option<Self> {
+        let path = format!("/sys/class/net/{interface}");
+        let output = Command::new("udevadm")
+            .args(["info", "--query=property", "--path", &path])
+            .output()
+            .await;
+
+        match output {
+            Ok(output) if output.status.success() => {
+                Some(Self::parse(&String::from_utf8_lossy(&output.stdout)))
+            }
+            Ok(output) => {
+                tracing::warn!("Could not read the udev properties of {interface}: {output:?}");
+                None
+            }
+            Err(error) => {
+                tracing::warn!("Could not run udevadm for {interface}: {error}");
+                None
+            }
+        }
+    }
+
+    /// Parses the properties from the output of `udevadm info --query=property`.
+    ///
+    /// * `content`: list of properties ("KEY=value" lines).
+    pub fn parse(content: &str) -> Self {
+        let properties = content
+            .lines()
+            .filter_map(|l| l.split_once('='))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+        Self(properties)
+    }
+
+    /// Returns the persistent path of the device (e.g., "pci-0000:00:19.0").
+    pub fn path(&self) -> Option<&str> {
+        self.0.get("ID_PATH").map(String::as_str)
+    }
+
+    /// Predicts the interface name in the installed system.
+    ///
+    /// It returns `None` if the name cannot be predicted (e.g., the device does not offer any
+    /// predictable name, like virtual devices).
+    pub fn predicted_name(&self) -> Option<&str> {
+        NAME_PROPERTIES
+            .iter()
+            .find_map(|p| self.0.get(*p).filter(|v| !v.is_empty()))
+            .map(String::as_str)
+    }
+}
+
+/// Determines whether the installed system uses predictable interface names.
+///
+/// They are disabled with `net.ifnames=0` or replaced by `biosdevname=1`. In both cases, the
+/// installed system keeps those options, so the names do not change.
+///
+/// * `cmdline`: kernel command-line.
+pub fn uses_predictable_names(cmdline: &KernelCmdline) -> bool {
+    cmdline.get_last("net.ifnames").as_deref() != Some("0")
+        && cmdline.get_last("biosdevname").as_deref() != Some("1")
+}
+
+/// Determines whether the installed system uses predictable interface names, according to the
+/// kernel command-line of the installer.
+///
+/// The result is cached, as the command-line does not change.
+pub fn target_uses_predictable_names() -> bool {
+    static PREDICTABLE: OnceLock<bool> = OnceLock::new();
+    *PREDICTABLE.get_or_init(|| {
+        let cmdline = KernelCmdline::parse().unwrap_or_default();
+        uses_predictable_names(&cmdline)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ETH0: &str = "\
+DEVPATH=/devices/pci0000:00/0000:00:19.0/net/eth0
+INTERFACE=eth0
+ID_NET_NAMING_SCHEME=v255
+ID_NET_NAME_MAC=enx525400123456
+ID_NET_NAME_PATH=enp0s25
+ID_PATH=pci-0000:00:19.0
+ID_NET_DRIVER=e1000e
+";
+
+    #[test]
+    fn test_parse_properties() {
+        let properties = UdevProperties::parse(ETH0);
+        assert_eq!(properties.path(), Some("pci-0000:00:19.0"));
+        assert_eq!(properties.predicted_name(), Some("enp0s25"));
+    }
+
+    #[test]
+    fn test_predicted_name_preference() {
+        let content = format!("{ETH0}ID_NET_NAME_SLOT=ens1\nID_NET_NAME_ONBOARD=eno1\n");
+        let properties = UdevProperties::parse(&content);
+        assert_eq!(properties.predicted_name(), Some("eno1"));
+
+        let properties = UdevProperties::parse("INTERFACE=veth0\nID_NET_NAME_MAC=enx1234\n");
+        assert_eq!(properties.predicted_name(), None);
+    }
+
+    #[test]
+    fn test_uses_predictable_names() {
+        let cmdline = KernelCmdline::parse_str("root=/dev/sda1 quiet");
+        assert!(uses_predictable_names(&cmdline));
+        let cmdline = KernelCmdline::parse_str("net.ifnames=0 quiet");
+        assert!(!uses_predictable_names(&cmdline));
+        let cmdline = KernelCmdline::parse_str("biosdevname=1");
+        assert!(!uses_predictable_names(&cmdline));
+    }
+}
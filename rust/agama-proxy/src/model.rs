@@ -22,6 +22,7 @@ use std::{
     os::unix::fs::OpenOptionsExt,
     path::{Path, PathBuf},
 };
+use url::Url;
 
 use agama_utils::kernel_cmdline::KernelCmdline;
 use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
@@ -164,6 +165,40 @@ impl ProxyConfig {
         })
     }
 
+    /// Returns the proxy to use for reaching the given URL, if any.
+    ///
+    /// It takes the `enabled` flag and the `no_proxy` exceptions into account. A `no_proxy`
+    /// entry matches the host itself and any of its subdomains, and "*" matches any host.
+    ///
+    /// * `url`: URL to reach.
+    pub fn proxy_for(&self, url: &str) -> Option<&Proxy> {
+        if self.enabled == Some(false) {
+            return None;
+        }
+
+        let url = Url::parse(url).ok()?;
+        let host = url.host_str()?.trim_matches(['[', ']']).to_lowercase();
+        let protocol = match url.scheme() {
+            "http" => Protocol::HTTP,
+            "https" => Protocol::HTTPS,
+            "ftp" => Protocol::FTP,
+            _ => return None,
+        };
+
+        let excluded = self
+            .no_proxy
+            .iter()
+            .flat_map(|n| n.split(','))
+            .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .any(|e| e == "*" || host == e || host.ends_with(&format!(".{e}")));
+        if excluded {
+            return None;
+        }
+
+        self.proxies.iter().find(|p| p.protocol == protocol)
+    }
+
     pub fn write(&self) -> Result<(), Error> {
         self.write_to(&Self::config_path())
     }
@@ -289,6 +324,37 @@ mod tests {
         assert_eq!(ProxyConfig::from_kernel_cmdline(&cmdline), None);
     }
 
+    #[test]
+    fn test_proxy_for() {
+        let mut config = ProxyConfig {
+            proxies: vec![
+                Proxy::new("http://proxy.example.com:3128".to_string(), Protocol::HTTP),
+                Proxy::new(
+                    "http://secure.example.com:3128".to_string(),
+                    Protocol::HTTPS,
+                ),
+            ],
+            enabled: Some(true),
+            no_proxy: Some("localhost, .internal.net,example.org".to_string()),
+        };
+
+        let proxy = config.proxy_for("https://download.opensuse.org/tumbleweed");
+        assert_eq!(proxy.unwrap().url, "http://secure.example.com:3128");
+        let proxy = config.proxy_for("http://download.opensuse.org/tumbleweed");
+        assert_eq!(proxy.unwrap().url, "http://proxy.example.com:3128");
+
+        assert_eq!(config.proxy_for("ftp://ftp.opensuse.org/"), None);
+        assert_eq!(config.proxy_for("http://localhost:8080/repo"), None);
+        assert_eq!(config.proxy_for("http://smt.internal.net/repo"), None);
+        assert_eq!(config.proxy_for("http://internal.net/repo"), None);
+        assert_eq!(config.proxy_for("https://example.org/repo"), None);
+        assert_eq!(config.proxy_for("dvd:/?devices=/dev/sr0"), None);
+        assert!(config.proxy_for("https://notexample.org/repo").is_some());
+
+        config.enabled = Some(false);
+        assert_eq!(config.proxy_for("https://download.opensuse.org"), None);
+    }
+
     #[test]
     fn test_write() {
         let config = ProxyConfig {
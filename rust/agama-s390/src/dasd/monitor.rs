@@ -64,6 +64,10 @@ impl From<ProgressData> for Progress {
             steps: data.steps,
             step: data.step,
             index: data.index,
+            total_bytes: None,
+            done_bytes: None,
+            bytes_per_second: None,
+            eta: None,
         }
     }
 }
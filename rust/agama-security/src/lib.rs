@@ -22,13 +22,13 @@
 //! including certificates management.
 
 pub mod service;
-pub use service::{Service, Starter};
+pub use service::{Service, Starter, DEFAULT_WORKDIR as CERTIFICATES_DIR};
 
 pub mod message;
 
 pub mod test_utils;
 
-mod certificate;
+pub mod certificate;
 
 #[cfg(test)]
 mod tests {
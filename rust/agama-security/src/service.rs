@@ -33,7 +33,8 @@ use gettextrs::gettext;
 
 use crate::{certificate::Certificate, message};
 
-const DEFAULT_WORKDIR: &str = "/etc/pki/trust/anchors";
+/// Directory where the imported certificates are written.
+pub const DEFAULT_WORKDIR: &str = "/etc/pki/trust/anchors";
 const DEFAULT_INSTALL_DIR: &str = "/mnt";
 
 #[derive(thiserror::Error, Debug)]
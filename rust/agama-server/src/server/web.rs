@@ -27,15 +27,18 @@ use crate::web::error::{ProblemDetailsExt, ProblemDetailsResponse};
 use agama_lib::logs;
 use agama_manager::service::Error as ManagerError;
 use agama_manager::users::PasswordCheckResult;
-use agama_manager::{self as manager, message, users};
+use agama_manager::{self as manager, message, network::model::NetworkStateDiff, software, users};
 use agama_utils::api::ProblemDetails;
 use agama_utils::{
     actor::Handler,
     api::{
         event,
+        files::FileSource,
         manager::LicenseContent,
+        network::UrlDiagnostics,
         query,
         question::{Question, QuestionSpec, UpdateQuestion},
+        software::{GpgKey, Manifest},
         Action, Config, IssueWithScope, Patch, Proposal, Status, SystemInfo,
     },
     progress, question,
@@ -48,7 +51,7 @@ use axum::{
     extract::{Path, Query, State},
     http::HeaderValue,
     response::{IntoResponse, Response},
-    routing::{get, post},
+    routing::{delete, get, post},
     Json,
 };
 use gettextrs::gettext;
@@ -176,6 +179,12 @@ pub fn server_with_state(
         .route("/private/download_logs", get(download_logs))
         .route("/private/list_logs", get(list_logs))
         .route("/private/password_check", post(check_password))
+        .route("/private/software_manifest", get(get_software_manifest))
+        .route("/private/solver_testcase", get(download_solver_testcase))
+        .route("/private/gpg_keys", get(list_gpg_keys).post(import_gpg_key))
+        .route("/private/gpg_keys/{id}", delete(remove_gpg_key))
+        .route("/private/network_diagnostics", post(diagnose_network))
+        .route("/private/network_apply", post(apply_network))
         .nest_service("/private/profile", profile_routes)
         .with_state(state))
 }
@@ -657,6 +666,79 @@ async fn solve_storage_model(
     Ok(Json(solved_model))
 }
 
+/// Returns the manifest of the packages selected for installation.
+async fn get_software_manifest(
+    State(state): State<ServerState>,
+) -> Result<Json<Manifest>, Response> {
+    let manifest = state
+        .manager
+        .call(software::message::GetManifest)
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+    Ok(Json(manifest))
+}
+
+/// Returns the GPG keys known by the package manager.
+async fn list_gpg_keys(State(state): State<ServerState>) -> Result<Json<Vec<GpgKey>>, Response> {
+    let keys = state
+        .manager
+        .call(software::message::ListGpgKeys)
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+    Ok(Json(keys))
+}
+
+/// Imports a GPG key, given as an armored block or as a URL, into the trusted keyring.
+async fn import_gpg_key(
+    State(state): State<ServerState>,
+    Json(source): Json<FileSource>,
+) -> Result<(), Response> {
+    state
+        .manager
+        .call(software::message::ImportGpgKey::new(source))
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+    Ok(())
+}
+
+/// Removes a GPG key from the keyring.
+async fn remove_gpg_key(
+    State(state): State<ServerState>,
+    Path(id): Path<String>,
+) -> Result<(), Response> {
+    state
+        .manager
+        .call(software::message::RemoveGpgKey::new(&id))
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+    Ok(())
+}
+
+/// Creates a solver testcase for the current software selection and returns the archive.
+async fn download_solver_testcase(State(state): State<ServerState>) -> Result<Response, Response> {
+    let path = state
+        .manager
+        .call(software::message::CreateSolverTestcase)
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+
+    let file = tokio::fs::File::open(&path)
+        .await
+        .map_err(|_| StatusCode::INSUFFICIENT_STORAGE.into_response())?;
+    let body = Body::from_stream(ReaderStream::new(file));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-compressed-tar"),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"solver-testcase.tar.gz\""),
+    );
+    Ok((StatusCode::OK, headers, body).into_response())
+}
+
 fn to_option_response<T: Serialize>(value: Option<T>) -> Response {
     match value {
         Some(inner) => Json(inner).into_response(),
@@ -664,7 +746,10 @@ fn to_option_response<T: Serialize>(value: Option<T>) -> Response {
     }
 }
 
-/// Solves a storage config model.
+/// Returns an archive with the logs.
+///
+/// It includes the solver testcase only if it already exists (e.g., after a failed solver run).
+/// Use the `/private/solver_testcase` endpoint to create a new one.
 async fn download_logs() -> impl IntoResponse {
     let mut headers = HeaderMap::new();
     let err_response = (headers.clone(), Body::empty());
@@ -711,8 +796,50 @@ async fn list_logs() -> impl IntoResponse {
 }
 
 #[derive(Deserialize, JsonSchema)]
+pub struct DiagnoseParams {
+    /// URLs to diagnose. If empty, it diagnoses the repositories and the registration server.
+    #[serde(default)]
+    urls: Vec<String>,
+}
+
+/// Diagnoses the access to the given URLs (DNS, TCP, TLS and HTTP).
+///
+/// The diagnostics run concurrently and outside the manager, so they do not block it.
+async fn diagnose_network(
+    State(state): State<ServerState>,
+    Json(params): Json<DiagnoseParams>,
+) -> Result<Json<Vec<UrlDiagnostics>>, Response> {
+    let targets = state
+        .manager
+        .call(message::GetDiagnosticsTargets::new(params.urls))
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+    let results = manager::network::diagnose_all(&targets).await;
+    Ok(Json(results))
+}
+
+/// Applies the network configuration and returns the changes.
+///
+/// If the system is not reachable after applying the changes, they are rolled back and it
+/// returns an error.
+async fn apply_network(
+    State(state): State<ServerState>,
+) -> Result<Json<NetworkStateDiff>, Response> {
+    let diff = state
+        .manager
+        .call(message::ApplyNetwork)
+        .await
+        .map_err(|e| Error::from(e).internal_server_error())?;
+    Ok(Json(diff))
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct PasswordParams {
     password: String,
+    /// Name of the user the password belongs to, if known.
+    #[serde(default)]
+    user_name: Option<String>,
 }
 
 async fn check_password(
@@ -721,7 +848,10 @@ async fn check_password(
 ) -> Result<Json<PasswordCheckResult>, Response> {
     let result = state
         .manager
-        .call(users::message::CheckPassword::new(password.password))
+        .call(
+            users::message::CheckPassword::new(password.password)
+                .with_user_name(password.user_name),
+        )
         .await
         .map_err(|e| Error::from(e).internal_server_error())?;
     Ok(Json(result))
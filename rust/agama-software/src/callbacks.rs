@@ -9,6 +9,8 @@ mod security;
 pub use security::Security;
 mod install;
 pub use install::Install;
+mod retry_policy;
+pub use retry_policy::{PackageKind, Retries, RetryAction, RetryPolicy};
 
 pub fn ask_software_question(
     handler: &Handler<question::Service>,
@@ -1,4 +1,9 @@
-use std::{ffi::OsStr, path::Path};
+use std::{
+    cell::Cell,
+    ffi::OsStr,
+    path::Path,
+    time::{Duration, Instant},
+};
 
 use agama_l10n::helpers::gettext_noop;
 use agama_utils::{
@@ -7,14 +12,23 @@ use agama_utils::{
     progress,
     question::{self},
 };
-use zypp_agama::callbacks::pkg_download::{Callback, DownloadError};
+use zypp_agama::callbacks::{
+    download_progress,
+    pkg_download::{Callback, DownloadError},
+};
+
+use crate::callbacks::{ask_software_question, Retries, RetryAction};
 
-use crate::callbacks::ask_software_question;
+/// Minimum time between two throughput reports.
+const THROUGHPUT_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Clone)]
 pub struct CommitDownload {
     progress: Handler<progress::Service>,
     questions: Handler<question::Service>,
+    retries: Retries,
+    // when the throughput was reported for the last time
+    throughput_reported: Cell<Option<Instant>>,
 }
 
 impl CommitDownload {
@@ -25,8 +39,15 @@ impl CommitDownload {
         Self {
             progress,
             questions,
+            retries: Retries::default(),
+            throughput_reported: Cell::new(None),
         }
     }
+
+    /// Sets the retry policy tracker to apply before asking the user.
+    pub fn set_retries(&mut self, retries: Retries) {
+        self.retries = retries;
+    }
 }
 
 impl Callback for CommitDownload {
@@ -40,6 +61,13 @@ impl Callback for CommitDownload {
         error: DownloadError,
         description: String,
     ) -> zypp_agama::callbacks::ProblemResponse {
+        if let Some(response) = self
+            .retries
+            .handle(&name, RetryAction::Download, &self.progress)
+        {
+            return response;
+        }
+
         let error_str = error.to_string();
         let question =
             QuestionSpec::new(description.as_str(), "software.package_error.provide_error")
@@ -113,6 +141,15 @@ impl Callback for CommitDownload {
             let _ = self
                 .progress
                 .cast(progress::message::NextWithStep::new(Scope::Software, &msg));
+            match file.metadata() {
+                Ok(metadata) => {
+                    let _ = self.progress.cast(progress::message::AddBytes::new(
+                        Scope::Software,
+                        metadata.len(),
+                    ));
+                }
+                Err(error) => tracing::warn!("Cannot read the size of {}: {}", file_str, error),
+            }
         } else {
             // just log that download failed as libzypp will automatically use next mirror
             // so we should not bother user. But also do not update progress otherwise it will
@@ -121,3 +158,24 @@ impl Callback for CommitDownload {
         }
     }
 }
+
+// Reports the throughput of the individual package downloads (at most once per second).
+impl download_progress::Callback for CommitDownload {
+    fn progress(&self, _value: i32, _url: String, _bps_avg: f64, bps_current: f64) -> bool {
+        if self
+            .throughput_reported
+            .get()
+            .is_some_and(|t| t.elapsed() < THROUGHPUT_INTERVAL)
+        {
+            return true;
+        }
+        self.throughput_reported.set(Some(Instant::now()));
+
+        // just ignore issues with reporting progress
+        let _ = self.progress.cast(progress::message::SetThroughput::new(
+            Scope::Software,
+            bps_current as u64,
+        ));
+        true
+    }
+}
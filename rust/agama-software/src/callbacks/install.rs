@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use agama_utils::{
     actor::Handler,
     api::{question::QuestionSpec, Scope},
@@ -7,13 +9,22 @@ use agama_utils::{
 use gettextrs::gettext;
 use zypp_agama::callbacks::{install, ProblemResponse};
 
-use crate::callbacks::ask_software_question;
+use crate::callbacks::{ask_software_question, Retries, RetryAction};
 use agama_l10n::helpers::gettext_noop;
 
+/// Package identifier: name, edition and architecture.
+///
+/// The name is not enough because several versions of the same package (e.g., kernels) can be
+/// installed.
+pub type PackageKey = (String, String, String);
+
 #[derive(Clone)]
 pub struct Install {
     progress: Handler<progress::Service>,
     questions: Handler<question::Service>,
+    // installed size of the packages (in bytes)
+    installed_sizes: HashMap<PackageKey, u64>,
+    retries: Retries,
 }
 
 impl Install {
@@ -24,8 +35,32 @@ impl Install {
         Self {
             progress,
             questions,
+            installed_sizes: HashMap::new(),
+            retries: Retries::default(),
         }
     }
+
+    /// Sets the retry policy tracker to apply before asking the user.
+    pub fn set_retries(&mut self, retries: Retries) {
+        self.retries = retries;
+    }
+
+    /// Sets the installed sizes of the packages to report the byte-level progress.
+    pub fn set_installed_sizes(&mut self, installed_sizes: HashMap<PackageKey, u64>) {
+        self.installed_sizes = installed_sizes;
+    }
+
+    // Reports the installed bytes.
+    fn report_installed(&self, key: &PackageKey) {
+        let Some(size) = self.installed_sizes.get(key).copied() else {
+            return;
+        };
+
+        // just ignore issues with reporting progress
+        let _ = self
+            .progress
+            .cast(progress::message::AddBytes::new(Scope::Software, size));
+    }
 }
 
 impl install::Callback for Install {
@@ -51,6 +86,13 @@ impl install::Callback for Install {
             description
         );
 
+        if let Some(response) =
+            self.retries
+                .handle(&package_name, RetryAction::Install, &self.progress)
+        {
+            return response;
+        }
+
         let question = QuestionSpec::new(&description, "software.package_error.install_error")
             // TODO: add abort when it is properly handled in UI/backend
             .with_action_ids(&[gettext_noop("Retry"), gettext_noop("Ignore")])
@@ -97,7 +139,8 @@ impl install::Callback for Install {
         }
     }
 
-    fn package_finish(&self, package_name: String) {
-        tracing::info!("Finished installing package {}", package_name);
+    fn package_finish(&self, package_name: String, edition: String, arch: String) {
+        tracing::info!("Finished installing package {package_name}-{edition}.{arch}");
+        self.report_installed(&(package_name, edition, arch));
     }
 }
@@ -0,0 +1,285 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Retry policy for the package download and installation problems.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use agama_utils::{actor::Handler, api::software::RetryPolicyConfig, api::Scope, progress};
+use i18n_format::i18n_format;
+use zypp_agama::callbacks::ProblemResponse;
+
+/// Highest exponent used to compute the backoff delay.
+const MAX_BACKOFF_EXPONENT: u32 = 10;
+
+/// Defines how to handle the package problems before asking the user.
+///
+/// The default policy does not retry, skip or abort, so the user is always asked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of automatic retries for each package.
+    pub max_retries: u32,
+    /// Delay before the first retry. It is doubled on each retry.
+    pub backoff: Duration,
+    /// Whether to skip the optional packages once the retries are exhausted.
+    pub skip_optional: bool,
+    /// Whether to abort when a core package fails once the retries are exhausted.
+    pub abort_on_core_failure: bool,
+}
+
+impl From<&RetryPolicyConfig> for RetryPolicy {
+    fn from(config: &RetryPolicyConfig) -> Self {
+        Self {
+            max_retries: config.max_retries.unwrap_or_default(),
+            backoff: Duration::from_secs(config.backoff.unwrap_or_default()),
+            skip_optional: config.skip_optional.unwrap_or_default(),
+            abort_on_core_failure: config.abort_on_core_failure.unwrap_or_default(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Decides how to handle a problem with a package.
+    ///
+    /// It returns `None` when the user should be asked.
+    ///
+    /// * `retries`: number of retries already done for the package.
+    /// * `kind`: kind of the failed package.
+    pub fn decide(&self, retries: u32, kind: PackageKind) -> Option<ProblemResponse> {
+        if retries < self.max_retries {
+            return Some(ProblemResponse::RETRY);
+        }
+
+        match kind {
+            PackageKind::Optional if self.skip_optional => Some(ProblemResponse::IGNORE),
+            PackageKind::Core if self.abort_on_core_failure => Some(ProblemResponse::ABORT),
+            _ => None,
+        }
+    }
+
+    /// Delay before the next retry.
+    ///
+    /// * `retries`: number of retries already done for the package.
+    pub fn delay(&self, retries: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1 << retries.min(MAX_BACKOFF_EXPONENT))
+    }
+}
+
+/// Kind of package regarding the retry policy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PackageKind {
+    /// Package explicitly required by the user, the product or Agama.
+    Core,
+    /// Package which can be skipped if it is not available.
+    Optional,
+    /// Any other package (e.g., a dependency).
+    Other,
+}
+
+/// Action which failed, used to report the retries.
+#[derive(Clone, Copy, Debug)]
+pub enum RetryAction {
+    Download,
+    Install,
+}
+
+/// Applies the [RetryPolicy] to the package problems, keeping track of the retries.
+#[derive(Clone, Default)]
+pub struct Retries {
+    policy: RetryPolicy,
+    core_packages: HashSet<String>,
+    optional_packages: HashSet<String>,
+    counters: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl Retries {
+    /// Builds a new tracker.
+    ///
+    /// * `policy`: policy to apply.
+    /// * `core_packages`: names of the core packages.
+    /// * `optional_packages`: names of the optional packages.
+    pub fn new(
+        policy: RetryPolicy,
+        core_packages: HashSet<String>,
+        optional_packages: HashSet<String>,
+    ) -> Self {
+        Self {
+            policy,
+            core_packages,
+            optional_packages,
+            counters: Default::default(),
+        }
+    }
+
+    /// Handles a problem with the given package according to the policy.
+    ///
+    /// Before retrying, it waits for the backoff delay and reports the retry in the progress.
+    /// It returns `None` when the user should be asked.
+    ///
+    /// Blocking the thread while waiting is intended: the libzypp callbacks run in the dedicated
+    /// thread of the zypp server (see `ZyppServer::start`), never in a Tokio worker, and libzypp
+    /// cannot continue until the callback returns the response anyway. It is the same approach
+    /// used when asking the user (see `ask_software_question`).
+    ///
+    /// * `package`: name of the failed package.
+    /// * `action`: failed action.
+    /// * `progress`: progress service to report the retries.
+    pub fn handle(
+        &self,
+        package: &str,
+        action: RetryAction,
+        progress: &Handler<progress::Service>,
+    ) -> Option<ProblemResponse> {
+        let retries = self.retries(package);
+        let response = self.policy.decide(retries, self.kind(package))?;
+
+        match response {
+            ProblemResponse::RETRY => {
+                let attempt = retries + 1;
+                self.counters
+                    .lock()
+                    .unwrap()
+                    .insert(package.to_string(), attempt);
+
+                let step = Self::retry_step(package, action, attempt, self.policy.max_retries);
+                tracing::info!("{}", &step);
+                // just ignore issues with reporting progress
+                let _ = progress.cast(progress::message::SetStep::new(Scope::Software, &step));
+                // blocking is fine, see the method documentation
+                std::thread::sleep(self.policy.delay(retries));
+            }
+            ProblemResponse::IGNORE => {
+                tracing::warn!("Skipping the optional package {package}");
+            }
+            ProblemResponse::ABORT => {
+                tracing::error!("Aborting because the core package {package} failed");
+            }
+        }
+        Some(response)
+    }
+
+    fn retries(&self, package: &str) -> u32 {
+        self.counters
+            .lock()
+            .unwrap()
+            .get(package)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn kind(&self, package: &str) -> PackageKind {
+        if self.core_packages.contains(package) {
+            PackageKind::Core
+        } else if self.optional_packages.contains(package) {
+            PackageKind::Optional
+        } else {
+            PackageKind::Other
+        }
+    }
+
+    fn retry_step(package: &str, action: RetryAction, attempt: u32, max: u32) -> String {
+        let (attempt, max) = (attempt.to_string(), max.to_string());
+        match action {
+            RetryAction::Download => i18n_format!(
+                // TRANSLATORS: substituting: package name, attempt number, maximum attempts
+                "Retrying the download of {0} (attempt {1} of {2})",
+                package,
+                &attempt,
+                &max
+            ),
+            RetryAction::Install => i18n_format!(
+                // TRANSLATORS: substituting: package name, attempt number, maximum attempts
+                "Retrying the installation of {0} (attempt {1} of {2})",
+                package,
+                &attempt,
+                &max
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_asks_the_user() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.decide(0, PackageKind::Core), None);
+        assert_eq!(policy.decide(0, PackageKind::Optional), None);
+        assert_eq!(policy.decide(0, PackageKind::Other), None);
+    }
+
+    #[test]
+    fn test_decide() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            skip_optional: true,
+            abort_on_core_failure: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            policy.decide(1, PackageKind::Core),
+            Some(ProblemResponse::RETRY)
+        );
+        assert_eq!(
+            policy.decide(2, PackageKind::Core),
+            Some(ProblemResponse::ABORT)
+        );
+        assert_eq!(
+            policy.decide(2, PackageKind::Optional),
+            Some(ProblemResponse::IGNORE)
+        );
+        assert_eq!(policy.decide(2, PackageKind::Other), None);
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy {
+            backoff: Duration::from_secs(2),
+            ..Default::default()
+        };
+
+        assert_eq!(policy.delay(0), Duration::from_secs(2));
+        assert_eq!(policy.delay(1), Duration::from_secs(4));
+        assert_eq!(policy.delay(3), Duration::from_secs(16));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = RetryPolicyConfig {
+            max_retries: Some(3),
+            backoff: Some(5),
+            skip_optional: None,
+            abort_on_core_failure: Some(true),
+        };
+        let policy = RetryPolicy::from(&config);
+        assert_eq!(policy.max_retries, 3);
+        assert_eq!(policy.backoff, Duration::from_secs(5));
+        assert!(!policy.skip_optional);
+        assert!(policy.abort_on_core_failure);
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Support for deploying the system from a container image.
+//!
+//! Image-based products (e.g., MicroOS or Kalpa) can be installed by unpacking an OCI image
+//! onto the target file system instead of installing RPM packages. The image is fetched with
+//! `skopeo` (if it is remote) and unpacked with `umoci`.
+
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Tag used for images copied to the local OCI layout.
+const LOCAL_TAG: &str = "agama";
+/// Default tag for local OCI layouts.
+const DEFAULT_TAG: &str = "latest";
+
+#[derive(thiserror::Error, Debug)]
+pub enum ImageError {
+    #[error("Invalid image reference: '{0}'")]
+    InvalidReference(String),
+    #[error("Command '{command}' failed: {error}")]
+    Command { command: String, error: String },
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+}
+
+/// Source of a container image.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageSource {
+    /// Local OCI layout ("oci:<path>[:<tag>]").
+    Layout { path: PathBuf, tag: String },
+    /// Remote image, including the transport (e.g., "docker://registry.example.net/image:1.0").
+    Remote(String),
+}
+
+impl ImageSource {
+    /// Parses an image reference.
+    ///
+    /// References without a transport are considered to be in a container registry.
+    ///
+    /// * `reference`: image reference.
+    pub fn parse(reference: &str) -> Result<Self, ImageError> {
+        let reference = reference.trim();
+        if reference.is_empty() || reference.chars().any(char::is_whitespace) {
+            return Err(ImageError::InvalidReference(reference.to_string()));
+        }
+
+        if let Some(layout) = reference.strip_prefix("oci:") {
+            let (path, tag) = match layout.rsplit_once(':') {
+                Some((path, tag)) if !tag.contains('/') => (path, tag),
+                _ => (layout, DEFAULT_TAG),
+            };
+            if path.is_empty() || tag.is_empty() {
+                return Err(ImageError::InvalidReference(reference.to_string()));
+            }
+            return Ok(Self::Layout {
+                path: PathBuf::from(path),
+                tag: tag.to_string(),
+            });
+        }
+
+        if reference.contains("://") {
+            return Ok(Self::Remote(reference.to_string()));
+        }
+
+        Ok(Self::Remote(format!("docker://{reference}")))
+    }
+
+    /// Whether the image must be downloaded before unpacking it.
+    pub fn is_remote(&self) -> bool {
+        matches!(self, Self::Remote(_))
+    }
+}
+
+/// Copies a remote image into a local OCI layout.
+///
+/// It returns the path and the tag of the local image.
+///
+/// * `reference`: remote image reference.
+/// * `work_dir`: directory to keep the local OCI layout.
+pub async fn download(reference: &str, work_dir: &Path) -> Result<(PathBuf, String), ImageError> {
+    let layout = work_dir.join("layout");
+    if tokio::fs::try_exists(&layout).await? {
+        tokio::fs::remove_dir_all(&layout).await?;
+    }
+    tokio::fs::create_dir_all(work_dir).await?;
+
+    let mut command = Command::new("skopeo");
+    command
+        .arg("copy")
+        .arg(reference)
+        .arg(format!("oci:{}:{LOCAL_TAG}", layout.display()));
+    run(command).await?;
+
+    Ok((layout, LOCAL_TAG.to_string()))
+}
+
+/// Unpacks the image from a local OCI layout into the target directory.
+///
+/// The image is unpacked directly into the target, so no additional disk space is needed. The
+/// target is not emptied: the files from the image overwrite the existing ones with the same path
+/// and the rest of the existing files are kept. It is expected to contain only the freshly created
+/// file systems, so the only existing files are the mount points created by the storage setup
+/// (and their content, e.g., an already formatted EFI partition).
+///
+/// * `layout`: path to the OCI layout.
+/// * `tag`: image tag.
+/// * `target`: target directory (e.g., "/mnt").
+pub async fn unpack(layout: &Path, tag: &str, target: &Path) -> Result<(), ImageError> {
+    let mut command = Command::new("umoci");
+    command
+        .args(["raw", "unpack", "--image"])
+        .arg(format!("{}:{tag}", layout.display()))
+        .arg(target);
+    run(command).await
+}
+
+/// Runs the given command and returns an error if it fails.
+async fn run(mut command: Command) -> Result<(), ImageError> {
+    let description = format!("{:?}", command.as_std());
+    tracing::info!("Running {description}");
+    let output = command.output().await?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        tracing::error!("{description} failed: {error}");
+        return Err(ImageError::Command {
+            command: description,
+            error,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layout() {
+        assert_eq!(
+            ImageSource::parse("oci:/srv/microos:1.0").unwrap(),
+            ImageSource::Layout {
+                path: PathBuf::from("/srv/microos"),
+                tag: "1.0".to_string()
+            }
+        );
+        assert_eq!(
+            ImageSource::parse("oci:/srv/microos").unwrap(),
+            ImageSource::Layout {
+                path: PathBuf::from("/srv/microos"),
+                tag: "latest".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_remote() {
+        let source = ImageSource::parse("registry.opensuse.org/opensuse/microos:latest").unwrap();
+        assert_eq!(
+            source,
+            ImageSource::Remote(
+                "docker://registry.opensuse.org/opensuse/microos:latest".to_string()
+            )
+        );
+        assert!(source.is_remote());
+
+        assert_eq!(
+            ImageSource::parse("docker://example.net/kalpa").unwrap(),
+            ImageSource::Remote("docker://example.net/kalpa".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(ImageSource::parse("").is_err());
+        assert!(ImageSource::parse("oci:").is_err());
+        assert!(ImageSource::parse("example.net/image with spaces").is_err());
+    }
+}
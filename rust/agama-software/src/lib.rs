@@ -40,10 +40,11 @@ pub use service::Service;
 
 mod model;
 pub use model::{
-    state, Model, ModelAdapter, Registration, Resolvable, ResolvableType, WriteIssues,
+    registration, state, Model, ModelAdapter, Registration, Resolvable, ResolvableType, WriteIssues,
 };
 
 mod callbacks;
+pub mod image;
 pub mod message;
 pub mod zypp_server;
 
@@ -20,10 +20,14 @@
 
 use agama_utils::{
     actor::Message,
-    api::software::{Config, Proposal, SystemInfo},
+    api::{
+        files::FileSource,
+        software::{Config, GpgKey, Manifest, Proposal, SystemInfo},
+        storage::MountPoint,
+    },
     products::ProductSpec,
 };
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::sync::RwLock;
 
 use crate::Resolvable;
@@ -83,6 +87,59 @@ impl Message for GetProposal {
     type Reply = Option<Proposal>;
 }
 
+/// Gets the manifest of the packages selected for installation.
+pub struct GetManifest;
+
+impl Message for GetManifest {
+    type Reply = Manifest;
+}
+
+/// Creates a solver testcase for the current selection.
+///
+/// It replies with the path to the archive containing the testcase.
+pub struct CreateSolverTestcase;
+
+impl Message for CreateSolverTestcase {
+    type Reply = PathBuf;
+}
+
+/// Lists the GPG keys known by the package manager.
+pub struct ListGpgKeys;
+
+impl Message for ListGpgKeys {
+    type Reply = Vec<GpgKey>;
+}
+
+/// Imports a GPG key into the trusted keyring.
+pub struct ImportGpgKey {
+    pub source: FileSource,
+}
+
+impl ImportGpgKey {
+    pub fn new(source: FileSource) -> Self {
+        Self { source }
+    }
+}
+
+impl Message for ImportGpgKey {
+    type Reply = ();
+}
+
+/// Removes a GPG key from the keyring.
+pub struct RemoveGpgKey {
+    pub id: String,
+}
+
+impl RemoveGpgKey {
+    pub fn new(id: &str) -> Self {
+        Self { id: id.to_string() }
+    }
+}
+
+impl Message for RemoveGpgKey {
+    type Reply = ();
+}
+
 pub struct Install;
 
 impl Message for Install {
@@ -117,6 +174,23 @@ impl Message for SetResolvables {
     type Reply = ();
 }
 
+/// Sets the mount points of the storage proposal.
+///
+/// They are used to check whether there is enough disk space for the packages.
+pub struct SetMountPoints {
+    pub mount_points: Vec<MountPoint>,
+}
+
+impl SetMountPoints {
+    pub fn new(mount_points: Vec<MountPoint>) -> Self {
+        Self { mount_points }
+    }
+}
+
+impl Message for SetMountPoints {
+    type Reply = ();
+}
+
 #[derive(Clone)]
 pub struct SetLocale {
     pub locale: String,
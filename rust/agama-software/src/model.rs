@@ -22,18 +22,21 @@ use agama_security as security;
 use agama_utils::{
     actor::Handler,
     api::{
-        software::{Repository, SoftwareProposal, SystemInfo},
+        files::FileSource,
+        software::{GpgKey, Manifest, Repository, SoftwareProposal, SystemInfo},
         Issue,
     },
     products::ProductSpec,
     progress, question,
 };
 use async_trait::async_trait;
+use std::path::PathBuf;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::{model::state::SoftwareState, service, zypp_server::SoftwareAction};
 
 pub mod conflict;
+pub mod mirrors;
 pub mod packages;
 pub mod registration;
 pub mod software_selection;
@@ -68,6 +71,21 @@ pub trait ModelAdapter: Send + Sync + 'static {
 
     async fn proposal(&self) -> Result<SoftwareProposal, service::Error>;
 
+    /// Returns the manifest of the packages selected for installation.
+    async fn manifest(&self) -> Result<Manifest, service::Error>;
+
+    /// Creates a solver testcase for the current selection and returns the path to the archive.
+    async fn solver_testcase(&self) -> Result<PathBuf, service::Error>;
+
+    /// Returns the GPG keys known by the package manager.
+    async fn gpg_keys(&self) -> Result<Vec<GpgKey>, service::Error>;
+
+    /// Imports a GPG key into the trusted keyring.
+    async fn import_gpg_key(&self, source: FileSource) -> Result<(), service::Error>;
+
+    /// Removes a GPG key from the keyring.
+    async fn remove_gpg_key(&self, id: &str) -> Result<(), service::Error>;
+
     /// Refresh repositories information.
     async fn refresh(&mut self) -> Result<(), service::Error>;
 
@@ -195,6 +213,39 @@ impl ModelAdapter for Model {
         Ok(rx.await??)
     }
 
+    async fn manifest(&self) -> Result<Manifest, service::Error> {
+        let (tx, rx) = oneshot::channel();
+        self.zypp_sender.send(SoftwareAction::GetManifest(tx))?;
+        Ok(rx.await??)
+    }
+
+    async fn solver_testcase(&self) -> Result<PathBuf, service::Error> {
+        let (tx, rx) = oneshot::channel();
+        self.zypp_sender
+            .send(SoftwareAction::CreateSolverTestcase(tx))?;
+        Ok(rx.await??)
+    }
+
+    async fn gpg_keys(&self) -> Result<Vec<GpgKey>, service::Error> {
+        let (tx, rx) = oneshot::channel();
+        self.zypp_sender.send(SoftwareAction::ListGpgKeys(tx))?;
+        Ok(rx.await??)
+    }
+
+    async fn import_gpg_key(&self, source: FileSource) -> Result<(), service::Error> {
+        let (tx, rx) = oneshot::channel();
+        self.zypp_sender
+            .send(SoftwareAction::ImportGpgKey(source, tx))?;
+        Ok(rx.await??)
+    }
+
+    async fn remove_gpg_key(&self, id: &str) -> Result<(), service::Error> {
+        let (tx, rx) = oneshot::channel();
+        self.zypp_sender
+            .send(SoftwareAction::RemoveGpgKey(id.to_string(), tx))?;
+        Ok(rx.await??)
+    }
+
     async fn proposal(&self) -> Result<SoftwareProposal, service::Error> {
         let Some(product_spec) = self.selected_product.clone() else {
             return Err(service::Error::MissingProduct);
@@ -0,0 +1,145 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Selection of the fastest mirror for the installation repositories.
+//!
+//! A repository can be served by several mirrors (see
+//! [RepositorySpec](agama_utils::products::RepositorySpec)). The mirrors are probed by
+//! downloading the repository metadata index and sorted by their response time, so the fastest one
+//! is used first and the rest can serve as fallbacks. The mirrors are probed concurrently and each
+//! probe gives up after [PROBE_TIMEOUT], so a dead mirror does not block the repositories setup.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use agama_transfer::Transfer;
+use url::Url;
+
+/// Repository metadata index, relative to the repository URL.
+const REPO_METADATA: &str = "repodata/repomd.xml";
+/// Maximum time to download the metadata index from a mirror.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sorts the given repository URLs by their response time.
+///
+/// Mirrors which cannot be probed (unreachable or using a non-network scheme like `dvd:`) are
+/// kept at the end of the list in their original order, so they can still be used as a last
+/// resort.
+pub fn sort_by_speed(urls: &[String]) -> Vec<String> {
+    let mut probed: Vec<_> = thread::scope(|scope| {
+        let probes: Vec<_> = urls
+            .iter()
+            .map(|url| (scope.spawn(move || probe(url)), url))
+            .collect();
+        probes
+            .into_iter()
+            .map(|(probe, url)| (probe.join().ok().flatten(), url))
+            .collect()
+    });
+    // sort_by_key is stable, so the unreachable mirrors keep their order
+    probed.sort_by_key(|(elapsed, _)| elapsed.unwrap_or(Duration::MAX));
+    probed.into_iter().map(|(_, url)| url.clone()).collect()
+}
+
+/// Returns the time needed to download the metadata index from the given mirror.
+///
+/// It returns `None` if the mirror cannot be probed.
+fn probe(url: &str) -> Option<Duration> {
+    let metadata_url = metadata_url(url)?;
+    let start = Instant::now();
+    match Transfer::get_with_timeout(&metadata_url, &mut std::io::sink(), false, PROBE_TIMEOUT) {
+        Ok(()) => {
+            let elapsed = start.elapsed();
+            tracing::info!("Mirror {} responded in {:?}", url, elapsed);
+            Some(elapsed)
+        }
+        Err(error) => {
+            tracing::info!("Mirror {} is not reachable: {}", url, error);
+            None
+        }
+    }
+}
+
+/// Builds the URL of the metadata index for the given repository URL.
+///
+/// It returns `None` for the schemes that are not probed.
+fn metadata_url(url: &str) -> Option<String> {
+    let mut url = Url::parse(url).ok()?;
+    if !matches!(url.scheme(), "http" | "https" | "ftp" | "file") {
+        return None;
+    }
+
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url.join(REPO_METADATA).ok().map(|u| u.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_url() {
+        assert_eq!(
+            metadata_url("https://download.opensuse.org/tumbleweed/repo/oss"),
+            Some(
+                "https://download.opensuse.org/tumbleweed/repo/oss/repodata/repomd.xml".to_string()
+            )
+        );
+        assert_eq!(
+            metadata_url("https://download.opensuse.org/tumbleweed/repo/oss/"),
+            Some(
+                "https://download.opensuse.org/tumbleweed/repo/oss/repodata/repomd.xml".to_string()
+            )
+        );
+        assert_eq!(metadata_url("dvd:/?devices=/dev/sr0"), None);
+        assert_eq!(metadata_url("not an URL"), None);
+    }
+
+    #[test]
+    fn test_sort_by_speed() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let repodata = tmp_dir.path().join("repodata");
+        std::fs::create_dir(&repodata).unwrap();
+        std::fs::write(repodata.join("repomd.xml"), "<repomd/>").unwrap();
+
+        let reachable = Url::from_directory_path(tmp_dir.path())
+            .unwrap()
+            .to_string();
+        let urls = vec![
+            "dvd:/?devices=/dev/sr0".to_string(),
+            "file:///non-existing/repo/".to_string(),
+            reachable.clone(),
+        ];
+
+        assert_eq!(
+            sort_by_speed(&urls),
+            vec![
+                reachable,
+                "dvd:/?devices=/dev/sr0".to_string(),
+                "file:///non-existing/repo/".to_string(),
+            ]
+        );
+    }
+}
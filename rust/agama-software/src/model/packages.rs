@@ -35,3 +35,72 @@ impl ResolvableTypeExt for ResolvableType {
         }
     }
 }
+
+/// Expression to select packages from the repositories.
+///
+/// Apart from plain package names, the user can select packages using:
+///
+/// * A capability: "provides:webserver".
+/// * A glob: "kernel-firmware-*".
+/// * A regular expression: "regex:^kernel-firmware-(amd|intel)".
+#[derive(Clone, Debug, PartialEq)]
+pub enum PackageExpression {
+    /// Exact package name.
+    Name(String),
+    /// Capability provided by the packages.
+    Provides(String),
+    /// Glob to match the package names.
+    Glob(String),
+    /// Regular expression to match the package names.
+    Regex(String),
+}
+
+impl PackageExpression {
+    const PROVIDES_PREFIX: &str = "provides:";
+    const REGEX_PREFIX: &str = "regex:";
+    const GLOB_CHARS: [char; 3] = ['*', '?', '['];
+
+    /// Parses a package entry from the configuration.
+    ///
+    /// * `entry`: package name or expression.
+    pub fn parse(entry: &str) -> Self {
+        if let Some(capability) = entry.strip_prefix(Self::PROVIDES_PREFIX) {
+            return Self::Provides(capability.trim().to_string());
+        }
+
+        if let Some(regex) = entry.strip_prefix(Self::REGEX_PREFIX) {
+            return Self::Regex(regex.to_string());
+        }
+
+        if entry.contains(Self::GLOB_CHARS) {
+            return Self::Glob(entry.to_string());
+        }
+
+        Self::Name(entry.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackageExpression;
+
+    #[test]
+    fn test_parse_package_expression() {
+        assert_eq!(
+            PackageExpression::parse("vim"),
+            PackageExpression::Name("vim".to_string())
+        );
+        assert_eq!(
+            PackageExpression::parse("provides:webserver"),
+            PackageExpression::Provides("webserver".to_string())
+        );
+        assert_eq!(
+            PackageExpression::parse("kernel-firmware-*"),
+            PackageExpression::Glob("kernel-firmware-*".to_string())
+        );
+        assert_eq!(
+            PackageExpression::parse("regex:^kernel-firmware-(amd|intel)$"),
+            PackageExpression::Regex("^kernel-firmware-(amd|intel)$".to_string())
+        );
+    }
+}
@@ -23,6 +23,9 @@
 //! It interacts with SUSEConnect-ng (using the [suseconnect_agama] crate) to register
 //! the system and its add-ons and with libzypp (through [zypp_agama]) to add the
 //! corresponding services to `libzypp`.
+//!
+//! The calls to the registration server go through a [RegistrationBackend], so they can be
+//! replaced with canned responses (see [FileBackend]).
 
 use agama_security as security;
 use agama_utils::{
@@ -30,13 +33,20 @@ use agama_utils::{
     api::software::{AddonInfo, AddonRegistration, RegistrationInfo},
     arch::Arch,
 };
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use openssl::x509::X509;
+use std::sync::Arc;
 use suseconnect_agama::{self, ConnectParams, Credentials};
 use url::Url;
 
 use crate::callbacks;
-use crate::state::Addon;
+use crate::state::{Addon, RegistrationState};
+
+pub mod backend;
+pub mod fake;
+
+pub use backend::{RegistrationBackend, SUSEConnectBackend};
+pub use fake::FileBackend;
 
 #[derive(thiserror::Error, Debug)]
 pub enum RegistrationError {
@@ -44,6 +54,8 @@ pub enum RegistrationError {
     Registration(#[from] suseconnect_agama::Error),
     #[error("Failed to add the service {0}: {1}")]
     AddService(String, #[source] zypp_agama::ZyppError),
+    #[error("Failed to remove the service {0}: {1}")]
+    RemoveService(String, #[source] zypp_agama::ZyppError),
     #[error("Failed to refresh the service {0}: {1}")]
     RefreshService(String, #[source] zypp_agama::ZyppError),
     #[error("Failed to select product from service {0}: {1}")]
@@ -78,6 +90,7 @@ pub struct Registration {
     addons: Vec<Addon>,
     // Holds all config files it created, later it will be copied to target system
     config_files: Vec<Utf8PathBuf>,
+    backend: Arc<dyn RegistrationBackend>,
 }
 
 impl Registration {
@@ -112,6 +125,121 @@ impl Registration {
         self.find_registered_addon(&addon.id).is_some()
     }
 
+    /// Deregisters the given add-on.
+    ///
+    /// It deactivates the add-on and removes the corresponding service from libzypp.
+    ///
+    /// * `zypp`: zypp instance.
+    /// * `id`: add-on identifier.
+    pub fn deregister_addon(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        id: &str,
+    ) -> RegistrationResult<()> {
+        let Some(index) = self.addons.iter().position(|a| a.id == id) else {
+            tracing::info!("Skipping the deregistration of {id} because it is not registered");
+            return Ok(());
+        };
+
+        let version = self.addons[index]
+            .version
+            .clone()
+            .unwrap_or(self.version.clone());
+        self.deactivate_product(zypp, id, &version)?;
+        self.addons.remove(index);
+        Ok(())
+    }
+
+    /// Deregisters the system, including all the registered add-ons.
+    ///
+    /// It removes the services and the credentials files created during the registration.
+    ///
+    /// * `zypp`: zypp instance.
+    pub fn deregister(&mut self, zypp: &zypp_agama::Zypp) -> RegistrationResult<()> {
+        let ids: Vec<_> = self.addons.iter().rev().map(|a| a.id.clone()).collect();
+        for id in ids {
+            self.deregister_addon(zypp, &id)?;
+        }
+
+        tracing::debug!("Deregistering the system");
+        self.backend
+            .deactivate_system(self.connect_params.clone())?;
+
+        for service in std::mem::take(&mut self.services) {
+            self.remove_service(zypp, &service)?;
+        }
+
+        for path in std::mem::take(&mut self.config_files) {
+            Self::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Determines whether the system is registered using the given settings.
+    ///
+    /// It checks the product, the registration code and the server, so a change in any of them
+    /// requires registering the system again.
+    ///
+    /// * `state`: wanted registration state.
+    pub fn matches(&self, state: &RegistrationState) -> bool {
+        self.product == state.product
+            && self.version == state.version
+            && self.connect_params.token == state.code
+            && self.connect_params.url == state.url
+    }
+
+    /// Returns the identifiers of the registered add-ons.
+    pub fn registered_addons(&self) -> Vec<String> {
+        self.addons.iter().map(|a| a.id.clone()).collect()
+    }
+
+    // Deactivates the product and removes its service.
+    fn deactivate_product(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        name: &str,
+        version: &str,
+    ) -> RegistrationResult<()> {
+        let product = Self::product_specification(name, version, self.arch);
+        tracing::debug!("Deregistering product {product:?}");
+        let service = self
+            .backend
+            .deactivate_product(product, self.connect_params.clone())?;
+
+        if let Some(index) = self.services.iter().position(|s| s.name == service.name) {
+            let service = self.services.remove(index);
+            self.remove_service(zypp, &service)?;
+        }
+        Ok(())
+    }
+
+    // Removes the libzypp service and its credentials file.
+    fn remove_service(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        service: &suseconnect_agama::Service,
+    ) -> RegistrationResult<()> {
+        zypp.remove_service(&service.name)
+            .map_err(|e| RegistrationError::RemoveService(service.name.clone(), e))?;
+
+        if let Some(file) = Self::credentials_from_url(&service.url) {
+            let path = self
+                .root_dir
+                .join(format!("etc/zypp/credentials.d/{}", file));
+            self.config_files.retain(|f| f != &path);
+            Self::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    fn remove_file(path: &Utf8Path) -> RegistrationResult<()> {
+        if path.exists() {
+            tracing::info!("Removing registration file {path}");
+            std::fs::remove_file(path).map_err(|e| RegistrationError::IO(path.to_string(), e))?;
+        }
+        Ok(())
+    }
+
     // Activates the product with the given code.
     fn activate_product(
         &mut self,
@@ -126,7 +254,7 @@ impl Registration {
         params.token = code.map(ToString::to_string);
 
         tracing::debug!("Registering product {product:?}");
-        let service = suseconnect_agama::activate_product(
+        let service = self.backend.activate_product(
             product,
             params,
             self.connect_params.email.as_deref().unwrap_or(""),
@@ -141,7 +269,7 @@ impl Registration {
                 &service.name,
                 &path
             );
-            suseconnect_agama::create_credentials_file(
+            self.backend.create_credentials_file(
                 &self.creds.login,
                 &self.creds.password,
                 path.as_str(),
@@ -215,7 +343,12 @@ impl Registration {
     ///
     /// Beware that, if a certificate was imported, it is copied by the agama-security service.
     pub fn finish(&mut self, install_dir: &Utf8PathBuf) -> Result<(), RegistrationError> {
-        suseconnect_agama::write_config(self.connect_params.clone())?;
+        self.backend.write_config(self.connect_params.clone())?;
+        if !self.backend.writes_files() {
+            tracing::info!("The registration backend does not write any file, nothing to copy");
+            return Ok(());
+        }
+
         self.config_files
             .push(suseconnect_agama::DEFAULT_CONFIG_FILE.into());
         self.copy_files(install_dir)?;
@@ -241,7 +374,7 @@ impl Registration {
     }
 
     fn base_product(&self) -> RegistrationResult<suseconnect_agama::Product> {
-        let product = suseconnect_agama::show_product(
+        let product = self.backend.show_product(
             self.base_product_specification(),
             self.connect_params.clone(),
         )?;
@@ -306,6 +439,7 @@ pub struct RegistrationBuilder {
     code: Option<String>,
     email: Option<String>,
     url: Option<Url>,
+    backend: Arc<dyn RegistrationBackend>,
 }
 
 impl RegistrationBuilder {
@@ -324,6 +458,7 @@ impl RegistrationBuilder {
             code: None,
             email: None,
             url: None,
+            backend: Arc::new(SUSEConnectBackend),
         }
     }
 
@@ -351,6 +486,16 @@ impl RegistrationBuilder {
         self
     }
 
+    /// Sets the backend to talk to the registration server.
+    ///
+    /// By default, it uses SUSEConnect-ng.
+    ///
+    /// * `backend`: registration backend.
+    pub fn with_backend(mut self, backend: Arc<dyn RegistrationBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
     /// Registers the system and return a [Registration] object.
     ///
     /// It announces the system, gets the credentials and registers the base product.
@@ -374,7 +519,8 @@ impl RegistrationBuilder {
         let target_distro = format!("{}-{}-{}", self.product, version, arch);
         tracing::debug!("Announcing system {target_distro}");
         let creds = handle_registration_error(
-            || suseconnect_agama::announce_system(params.clone(), &target_distro),
+            || self.backend.announce_system(params.clone(), &target_distro),
+            self.backend.as_ref(),
             security_srv,
         )?;
 
@@ -384,7 +530,7 @@ impl RegistrationBuilder {
             "Creating the base credentials file at {}",
             suseconnect_agama::GLOBAL_CREDENTIALS_FILE
         );
-        suseconnect_agama::create_credentials_file(
+        self.backend.create_credentials_file(
             &creds.login,
             &creds.password,
             suseconnect_agama::GLOBAL_CREDENTIALS_FILE,
@@ -400,6 +546,7 @@ impl RegistrationBuilder {
             services: vec![],
             addons: vec![],
             config_files: vec![suseconnect_agama::GLOBAL_CREDENTIALS_FILE.into()],
+            backend: Arc::clone(&self.backend),
         };
 
         registration.activate_product(
@@ -423,6 +570,7 @@ impl RegistrationBuilder {
 /// other kind of error.
 fn handle_registration_error<T, F>(
     func: F,
+    backend: &dyn RegistrationBackend,
     security_srv: &Handler<security::Service>,
 ) -> Result<T, RegistrationError>
 where
@@ -441,7 +589,7 @@ where
                 let x509 = X509::from_pem(current_certificate.as_bytes()).unwrap();
                 match should_trust_certificate(&x509, security_srv) {
                     Ok(true) => {
-                        if let Err(error) = suseconnect_agama::reload_certificates() {
+                        if let Err(error) = backend.reload_certificates() {
                             tracing::error!("Could not reload the certificates: {error}");
                         }
                         continue;
@@ -478,3 +626,148 @@ pub fn should_trust_certificate(
     });
     rt.block_on(handle).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures_dir() -> Utf8PathBuf {
+        Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/share/registration")
+    }
+
+    fn registration(root_dir: &Utf8Path, backend: Arc<FileBackend>) -> Registration {
+        Registration {
+            root_dir: root_dir.to_path_buf(),
+            product: "SLES".to_string(),
+            version: "16.0".to_string(),
+            arch: Arch::X86_64,
+            connect_params: ConnectParams {
+                token: Some("REGCODE".to_string()),
+                ..Default::default()
+            },
+            creds: Credentials {
+                login: "SCC_0123456789abcdef".to_string(),
+                password: "s3cr3t".to_string(),
+            },
+            services: vec![],
+            addons: vec![],
+            config_files: vec![],
+            backend,
+        }
+    }
+
+    /// Activates the given product in the fake server and adds its service.
+    fn activate(registration: &mut Registration, zypp: &zypp_agama::Zypp, id: &str) -> Utf8PathBuf {
+        let product = Registration::product_specification(id, "16.0", Arch::X86_64);
+        let service = registration
+            .backend
+            .activate_product(product, registration.connect_params.clone(), "")
+            .unwrap();
+        // use a local service instead of the one from the registration server
+        zypp.add_service(&service.name, "file:///service").unwrap();
+
+        let file = Registration::credentials_from_url(&service.url).unwrap();
+        let path = registration
+            .root_dir
+            .join(format!("etc/zypp/credentials.d/{}", file));
+        std::fs::write(&path, "username=SCC_0123456789abcdef\n").unwrap();
+        registration.config_files.push(path.clone());
+        registration.services.push(service);
+        path
+    }
+
+    #[test]
+    fn test_matches() {
+        let backend = Arc::new(FileBackend::new(fixtures_dir()));
+        let registration = registration(Utf8Path::new("/"), backend);
+        let mut state = RegistrationState {
+            product: "SLES".to_string(),
+            version: "16.0".to_string(),
+            code: Some("REGCODE".to_string()),
+            email: None,
+            url: None,
+            addons: vec![],
+        };
+        assert!(registration.matches(&state));
+
+        state.code = Some("OTHER".to_string());
+        assert!(!registration.matches(&state));
+
+        state.code = Some("REGCODE".to_string());
+        state.url = Some(Url::parse("https://rmt.example.net").unwrap());
+        assert!(!registration.matches(&state));
+
+        state.url = None;
+        state.version = "16.1".to_string();
+        assert!(!registration.matches(&state));
+    }
+
+    #[test]
+    fn test_deregister() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let root_dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        let fixtures = Utf8Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../zypp-agama/fixtures/zypp_services_root");
+        agama_utils::helpers::copy_dir_all(&fixtures, root_dir).unwrap();
+        std::fs::create_dir_all(root_dir.join("etc/zypp/credentials.d")).unwrap();
+        let zypp = zypp_agama::Zypp::init_target(root_dir.as_str(), |_, _, _| {}).unwrap();
+
+        let backend = Arc::new(FileBackend::new(fixtures_dir()));
+        let mut registration = registration(root_dir, Arc::clone(&backend));
+        let base_credentials = activate(&mut registration, &zypp, "SLES");
+        let addon_credentials = activate(&mut registration, &zypp, "sle-ha");
+        registration.addons.push(Addon::new("sle-ha", None, None));
+
+        registration.deregister_addon(&zypp, "sle-ha").unwrap();
+        assert!(registration.registered_addons().is_empty());
+        assert!(registration.addon_product_service_names().is_empty());
+        assert!(!addon_credentials.exists());
+        assert_eq!(backend.activated_products(), vec!["SLES".to_string()]);
+
+        // add-ons which are not registered are ignored
+        registration.deregister_addon(&zypp, "PackageHub").unwrap();
+
+        registration.deregister(&zypp).unwrap();
+        assert!(registration.base_product_service_name().is_none());
+        assert!(registration.config_files.is_empty());
+        assert!(!base_credentials.exists());
+        assert!(backend.activated_products().is_empty());
+    }
+
+    #[test]
+    fn test_copy_files() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let root_dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        let credentials_dir = root_dir.join("etc/zypp/credentials.d");
+        std::fs::create_dir_all(&credentials_dir).unwrap();
+        let target_dir = root_dir.join("mnt");
+        std::fs::create_dir_all(target_dir.join("etc/zypp/credentials.d")).unwrap();
+
+        let backend = Arc::new(FileBackend::new(fixtures_dir()));
+        let mut registration = registration(root_dir, backend);
+        let path = credentials_dir.join("SLES");
+        std::fs::write(&path, "username=SCC_0123456789abcdef\n").unwrap();
+        registration.config_files.push(path);
+        registration.copy_files(&target_dir).unwrap();
+        assert!(target_dir.join("etc/zypp/credentials.d/SLES").exists());
+
+        // a missing file is not silently skipped
+        registration
+            .config_files
+            .push(credentials_dir.join("missing"));
+        let result = registration.copy_files(&target_dir);
+        assert!(matches!(result, Err(RegistrationError::IO(_, _))));
+    }
+
+    #[test]
+    fn test_finish_without_files() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let root_dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+
+        // the fake backend does not write any file, so there is nothing to copy
+        let backend = Arc::new(FileBackend::new(fixtures_dir()));
+        let mut registration = registration(root_dir, backend);
+        registration.finish(&root_dir.join("mnt")).unwrap();
+        assert!(registration.config_files.is_empty());
+    }
+}
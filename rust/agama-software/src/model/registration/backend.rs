@@ -0,0 +1,164 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Backends to talk to the registration server.
+//!
+//! By default, Agama uses SUSEConnect-ng (through the [suseconnect_agama] crate) to talk to
+//! SCC or RMT. The [RegistrationBackend] trait allows replacing it (e.g., with the
+//! [FileBackend](super::fake::FileBackend) for testing).
+
+use suseconnect_agama::{
+    ConnectParams, Credentials, Error, Product, ProductSpecification, Service,
+};
+
+/// Registration server operations used by Agama.
+///
+/// The functions mirror the ones offered by [suseconnect_agama].
+pub trait RegistrationBackend: std::fmt::Debug + Send + Sync {
+    /// Announces the system to get the registration credentials.
+    ///
+    /// * `params`: connection parameters.
+    /// * `target_distro`: target distribution (e.g., "SLES-16-x86_64").
+    fn announce_system(
+        &self,
+        params: ConnectParams,
+        target_distro: &str,
+    ) -> Result<Credentials, Error>;
+
+    /// Activates a product and returns the service to add to libzypp.
+    ///
+    /// * `product`: product to activate.
+    /// * `params`: connection parameters.
+    /// * `email`: e-mail to associate with the activation. It can be empty.
+    fn activate_product(
+        &self,
+        product: ProductSpecification,
+        params: ConnectParams,
+        email: &str,
+    ) -> Result<Service, Error>;
+
+    /// Deactivates a product and returns the service that was associated to it.
+    ///
+    /// * `product`: product to deactivate.
+    /// * `params`: connection parameters.
+    fn deactivate_product(
+        &self,
+        product: ProductSpecification,
+        params: ConnectParams,
+    ) -> Result<Service, Error>;
+
+    /// Deregisters the system.
+    ///
+    /// * `params`: connection parameters.
+    fn deactivate_system(&self, params: ConnectParams) -> Result<(), Error>;
+
+    /// Returns the product information, including the available extensions.
+    ///
+    /// * `product`: product to get the information for.
+    /// * `params`: connection parameters.
+    fn show_product(
+        &self,
+        product: ProductSpecification,
+        params: ConnectParams,
+    ) -> Result<Product, Error>;
+
+    /// Writes a credentials file.
+    ///
+    /// * `login`: user name.
+    /// * `password`: password.
+    /// * `path`: path of the credentials file.
+    fn create_credentials_file(&self, login: &str, password: &str, path: &str)
+        -> Result<(), Error>;
+
+    /// Reloads the certificates after importing a new one.
+    fn reload_certificates(&self) -> Result<(), Error>;
+
+    /// Writes the SUSEConnect configuration.
+    ///
+    /// * `params`: connection parameters.
+    fn write_config(&self, params: ConnectParams) -> Result<(), Error>;
+
+    /// Whether the credentials and the configuration are written to the file system.
+    ///
+    /// If they are not, there is nothing to copy to the target system.
+    fn writes_files(&self) -> bool {
+        true
+    }
+}
+
+/// Backend talking to SCC or RMT through SUSEConnect-ng.
+#[derive(Debug, Default)]
+pub struct SUSEConnectBackend;
+
+impl RegistrationBackend for SUSEConnectBackend {
+    fn announce_system(
+        &self,
+        params: ConnectParams,
+        target_distro: &str,
+    ) -> Result<Credentials, Error> {
+        suseconnect_agama::announce_system(params, target_distro)
+    }
+
+    fn activate_product(
+        &self,
+        product: ProductSpecification,
+        params: ConnectParams,
+        email: &str,
+    ) -> Result<Service, Error> {
+        suseconnect_agama::activate_product(product, params, email)
+    }
+
+    fn deactivate_product(
+        &self,
+        product: ProductSpecification,
+        params: ConnectParams,
+    ) -> Result<Service, Error> {
+        suseconnect_agama::deactivate_product(product, params)
+    }
+
+    fn deactivate_system(&self, params: ConnectParams) -> Result<(), Error> {
+        suseconnect_agama::deactivate_system(params)
+    }
+
+    fn show_product(
+        &self,
+        product: ProductSpecification,
+        params: ConnectParams,
+    ) -> Result<Product, Error> {
+        suseconnect_agama::show_product(product, params)
+    }
+
+    fn create_credentials_file(
+        &self,
+        login: &str,
+        password: &str,
+        path: &str,
+    ) -> Result<(), Error> {
+        suseconnect_agama::create_credentials_file(login, password, path)
+    }
+
+    fn reload_certificates(&self) -> Result<(), Error> {
+        suseconnect_agama::reload_certificates()
+    }
+
+    fn write_config(&self, params: ConnectParams) -> Result<(), Error> {
+        suseconnect_agama::write_config(params)
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! File-backed registration backend.
+//!
+//! It emulates a registration server (SCC or RMT) using canned responses stored in a directory,
+//! so the registration flows can run without network access. The directory might contain:
+//!
+//! * `announce.json`: response to the system announcement.
+//! * `activate/<product>.json`: response to the activation of a product (the libzypp service).
+//!   It is also used as the response to the deactivation.
+//! * `show/<product>.json`: product information, including the available extensions.
+//! * `certificate.pem`: server certificate. If it exists, all the calls fail with an SSL error
+//!   until the certificates are reloaded, emulating a server using a self-signed certificate.
+//!
+//! The responses use the same JSON format as SUSEConnect-ng, so they might contain errors too.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use suseconnect_agama::{
+    ConnectParams, Credentials, Error, Product, ProductSpecification, SSLErrorCode, Service,
+};
+
+use super::backend::RegistrationBackend;
+
+/// Registration backend returning canned responses from a directory.
+///
+/// Instead of writing the credentials and the configuration, it keeps them in memory so they
+/// can be inspected later.
+#[derive(Debug)]
+pub struct FileBackend {
+    dir: Utf8PathBuf,
+    certificate_trusted: AtomicBool,
+    activated: Mutex<Vec<ProductSpecification>>,
+    credentials: Mutex<HashMap<String, Credentials>>,
+    config: Mutex<Option<ConnectParams>>,
+}
+
+impl FileBackend {
+    /// Creates a backend reading the responses from the given directory.
+    ///
+    /// * `dir`: directory containing the canned responses.
+    pub fn new<P: AsRef<Utf8Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            certificate_trusted: AtomicBool::new(false),
+            activated: Mutex::new(vec![]),
+            credentials: Mutex::new(HashMap::new()),
+            config: Mutex::new(None),
+        }
+    }
+
+    /// Returns the identifiers of the activated products, in activation order.
+    pub fn activated_products(&self) -> Vec<String> {
+        let activated = self.activated.lock().unwrap();
+        activated.iter().map(|p| p.identifier.clone()).collect()
+    }
+
+    /// Returns the credentials written to the given path, if any.
+    ///
+    /// * `path`: path of the credentials file.
+    pub fn credentials(&self, path: &str) -> Option<Credentials> {
+        self.credentials.lock().unwrap().get(path).cloned()
+    }
+
+    /// Returns the SUSEConnect configuration, if it was written.
+    pub fn config(&self) -> Option<ConnectParams> {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Fails with an SSL error if the server certificate is not trusted yet.
+    fn check_certificate(&self) -> Result<(), Error> {
+        if self.certificate_trusted.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let path = self.dir.join("certificate.pem");
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let current_certificate = std::fs::read_to_string(&path)
+            .map_err(|e| Error::Unknown(format!("Could not read {path}: {e}")))?;
+        Err(Error::SSL {
+            message: "certificate signed by unknown authority".to_string(),
+            code: SSLErrorCode::SelfSignedCert,
+            current_certificate,
+        })
+    }
+
+    /// Reads the canned response from the given file.
+    ///
+    /// * `name`: file name, relative to the backend directory.
+    fn read_response(&self, name: &str) -> Result<String, Error> {
+        self.check_certificate()?;
+
+        let path = self.dir.join(name);
+        if !path.exists() {
+            return Err(Error::SCCApi {
+                message: format!("No canned response at {path}"),
+                code: 404,
+            });
+        }
+
+        std::fs::read_to_string(&path)
+            .map_err(|e| Error::Unknown(format!("Could not read {path}: {e}")))
+    }
+}
+
+impl RegistrationBackend for FileBackend {
+    fn announce_system(
+        &self,
+        _params: ConnectParams,
+        target_distro: &str,
+    ) -> Result<Credentials, Error> {
+        tracing::info!("Announcing system {target_distro} (fake)");
+        suseconnect_agama::parse_response(&self.read_response("announce.json")?)
+    }
+
+    fn activate_product(
+        &self,
+        product: ProductSpecification,
+        _params: ConnectParams,
+        _email: &str,
+    ) -> Result<Service, Error> {
+        let name = format!("activate/{}.json", product.identifier);
+        let service = suseconnect_agama::parse_response(&self.read_response(&name)?)?;
+        self.activated.lock().unwrap().push(product);
+        Ok(service)
+    }
+
+    fn deactivate_product(
+        &self,
+        product: ProductSpecification,
+        _params: ConnectParams,
+    ) -> Result<Service, Error> {
+        self.check_certificate()?;
+        let mut activated = self.activated.lock().unwrap();
+        let Some(index) = activated
+            .iter()
+            .position(|p| p.identifier == product.identifier)
+        else {
+            return Err(Error::SCCApi {
+                message: format!("Product {} is not activated", product.identifier),
+                code: 422,
+            });
+        };
+
+        let name = format!("activate/{}.json", product.identifier);
+        let service = suseconnect_agama::parse_response(&self.read_response(&name)?)?;
+        activated.remove(index);
+        Ok(service)
+    }
+
+    fn deactivate_system(&self, _params: ConnectParams) -> Result<(), Error> {
+        self.check_certificate()?;
+        self.activated.lock().unwrap().clear();
+        self.credentials.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn show_product(
+        &self,
+        product: ProductSpecification,
+        _params: ConnectParams,
+    ) -> Result<Product, Error> {
+        let name = format!("show/{}.json", product.identifier);
+        suseconnect_agama::parse_response(&self.read_response(&name)?)
+    }
+
+    fn create_credentials_file(
+        &self,
+        login: &str,
+        password: &str,
+        path: &str,
+    ) -> Result<(), Error> {
+        let credentials = Credentials {
+            login: login.to_string(),
+            password: password.to_string(),
+        };
+        self.credentials
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), credentials);
+        Ok(())
+    }
+
+    fn reload_certificates(&self) -> Result<(), Error> {
+        self.certificate_trusted.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn write_config(&self, params: ConnectParams) -> Result<(), Error> {
+        *self.config.lock().unwrap() = Some(params);
+        Ok(())
+    }
+
+    fn writes_files(&self) -> bool {
+        false
+    }
+}
@@ -28,14 +28,21 @@ use std::fmt;
 use agama_utils::{
     api::{
         self,
-        software::{Config, PatternsConfig, ProductConfig, RepositoryConfig, SoftwareConfig},
+        files::FileSource,
+        software::{
+            Config, KernelConfig, Manifest, PatternsConfig, ProductConfig, RepositoryConfig,
+            SoftwareConfig,
+        },
     },
     kernel_cmdline::KernelCmdline,
     products::{ProductSpec, UserPatternSpec},
 };
 use url::Url;
 
-use crate::{model::software_selection::SoftwareSelection, Resolvable, ResolvableType};
+use crate::{
+    callbacks::RetryPolicy, model::software_selection::SoftwareSelection, Resolvable,
+    ResolvableType,
+};
 
 #[derive(Clone, Debug)]
 pub struct RepoKey {
@@ -59,7 +66,17 @@ pub struct SoftwareState {
     pub registration: Option<RegistrationState>,
     pub allow_registration: bool,
     pub trusted_gpg_keys: Vec<RepoKey>,
+    /// GPG keys to import, both into the installer and into the target system.
+    pub gpg_keys: Vec<FileSource>,
     pub unsigned_repos: Vec<String>,
+    /// Exact set of packages to install. When set, it replaces the regular resolvables
+    /// selection.
+    pub manifest: Option<Manifest>,
+    /// Container image to deploy. When set, the system is unpacked from the image instead of
+    /// installing RPM packages.
+    pub image: Option<String>,
+    /// Mount points of the storage proposal, used to check the available disk space.
+    pub mount_points: Vec<api::storage::MountPoint>,
 }
 
 impl SoftwareState {
@@ -73,7 +90,11 @@ impl SoftwareState {
             registration: None,
             allow_registration: false,
             trusted_gpg_keys: vec![],
+            gpg_keys: vec![],
             unsigned_repos: vec![],
+            manifest: None,
+            image: None,
+            mount_points: vec![],
         }
     }
 }
@@ -207,24 +228,28 @@ impl<'a> SoftwareStateBuilder<'a> {
     /// Adds the elements from the user software configuration.
     fn add_user_software_config(&self, state: &mut SoftwareState, config: &SoftwareConfig) {
         if let Some(repositories) = &config.extra_repositories {
-            let extra = repositories.iter().map(Repository::from);
-            state.repositories.extend(extra);
-
-            // create map for gpg signatures
-            for repo in repositories {
-                if let Some(gpg_fingerprints) = &repo.gpg_fingerprints {
-                    for fingerprint in gpg_fingerprints {
-                        state.trusted_gpg_keys.push(RepoKey {
-                            alias: repo.alias.clone(),
-                            // remove all whitespaces to sanitize input
-                            fingerprint: fingerprint.replace(" ", ""),
-                        });
-                    }
-                }
+            Self::add_repositories(state, repositories);
+        }
 
-                if repo.allow_unsigned == Some(true) {
-                    state.unsigned_repos.push(repo.alias.clone());
-                }
+        if let Some(gpg_keys) = &config.gpg_keys {
+            state.gpg_keys.extend(gpg_keys.iter().cloned());
+        }
+
+        if let Some(kernel) = &config.kernel {
+            self.add_kernel_config(state, kernel);
+        }
+
+        if let Some(retry_policy) = &config.retry_policy {
+            state.options.retry_policy = retry_policy.into();
+        }
+
+        if let Some(install_updates) = config.install_updates {
+            state.options.install_updates = install_updates;
+        }
+
+        if state.options.install_updates {
+            if let Some(repositories) = &config.update_repositories {
+                Self::add_repositories(state, repositories);
             }
         }
 
@@ -270,6 +295,57 @@ impl<'a> SoftwareStateBuilder<'a> {
         if let Some(only_required) = config.only_required {
             state.options.only_required = only_required;
         }
+
+        if let Some(manifest) = &config.manifest {
+            state.manifest = Some(manifest.clone());
+        }
+
+        if let Some(image) = &config.image {
+            state.image = Some(image.clone());
+        }
+    }
+
+    /// Adds the kernel settings, replacing the kernel from the product definition if needed.
+    fn add_kernel_config(&self, state: &mut SoftwareState, config: &KernelConfig) {
+        if let Some(flavor) = &config.flavor {
+            state
+                .resolvables
+                .remove(self.product.software.kernel(), ResolvableType::Package);
+            state.resolvables.add_or_replace(
+                &flavor.package_name(),
+                ResolvableType::Package,
+                ResolvableSelection::AutoSelected {
+                    skip_if_missing: false,
+                },
+            );
+        }
+
+        if let Some(multiversion) = &config.multiversion {
+            state.options.multiversion_kernels = multiversion.clone();
+        }
+    }
+
+    /// Adds the given repositories, including their GPG settings.
+    fn add_repositories(state: &mut SoftwareState, repositories: &[RepositoryConfig]) {
+        let extra = repositories.iter().map(Repository::from);
+        state.repositories.extend(extra);
+
+        // create map for gpg signatures
+        for repo in repositories {
+            if let Some(gpg_fingerprints) = &repo.gpg_fingerprints {
+                for fingerprint in gpg_fingerprints {
+                    state.trusted_gpg_keys.push(RepoKey {
+                        alias: repo.alias.clone(),
+                        // remove all whitespaces to sanitize input
+                        fingerprint: fingerprint.replace(" ", ""),
+                    });
+                }
+            }
+
+            if repo.allow_unsigned == Some(true) {
+                state.unsigned_repos.push(repo.alias.clone());
+            }
+        }
     }
 
     /// It adds the software selection from Agama modules.
@@ -284,12 +360,13 @@ impl<'a> SoftwareStateBuilder<'a> {
         }
     }
 
-    fn build_repo(i: usize, url: String) -> Repository {
+    fn build_repo(i: usize, url: String, mirrors: Vec<String>) -> Repository {
         let alias = format!("{AGAMA_REPO_PREFIX}{i}");
         Repository {
             name: alias.clone(),
             alias,
             url,
+            mirrors,
             enabled: true,
             priority: None,
         }
@@ -309,7 +386,7 @@ impl<'a> SoftwareStateBuilder<'a> {
             kernel_repos
                 .split(",")
                 .enumerate()
-                .map(|(i, url)| Self::build_repo(i, url.to_string()))
+                .map(|(i, url)| Self::build_repo(i, url.to_string(), vec![]))
                 .collect()
         } else if self.has_local_install_media() {
             tracing::info!(
@@ -321,7 +398,7 @@ impl<'a> SoftwareStateBuilder<'a> {
                 .repositories()
                 .into_iter()
                 .enumerate()
-                .map(|(i, r)| Self::build_repo(i, r.url.clone()))
+                .map(|(i, r)| Self::build_repo(i, r.url.clone(), r.mirrors.clone()))
                 .collect()
         };
 
@@ -427,7 +504,11 @@ impl<'a> SoftwareStateBuilder<'a> {
             options: Default::default(),
             allow_registration: self.product.registration,
             trusted_gpg_keys: vec![],
+            gpg_keys: vec![],
             unsigned_repos: vec![],
+            manifest: None,
+            image: self.product.software.image.clone(),
+            mount_points: vec![],
         }
     }
 }
@@ -454,6 +535,8 @@ pub struct Repository {
     pub alias: String,
     pub name: String,
     pub url: String,
+    /// Alternative URLs serving the same repository.
+    pub mirrors: Vec<String>,
     pub enabled: bool,
     pub priority: Option<u32>,
 }
@@ -464,6 +547,7 @@ impl From<&RepositoryConfig> for Repository {
             name: value.name.as_ref().unwrap_or(&value.alias).clone(),
             alias: value.alias.clone(),
             url: value.url.clone(),
+            mirrors: vec![],
             enabled: value.enabled.unwrap_or(true),
             priority: value.priority.and_then(|p| u32::try_from(p).ok()),
         }
@@ -476,6 +560,7 @@ impl From<&agama_utils::api::software::Repository> for Repository {
             name: value.name.clone(),
             alias: value.alias.clone(),
             url: value.url.clone(),
+            mirrors: vec![],
             enabled: value.enabled,
             priority: None,
         }
@@ -542,6 +627,14 @@ impl ResolvablesState {
         }
     }
 
+    /// Removes the resolvable with the given name and type, whatever its state is.
+    ///
+    /// * `name`: resolvable name.
+    /// * `r#type`: resolvable type.
+    pub fn remove(&mut self, name: &str, r#type: ResolvableType) {
+        self.0.remove(&(name.to_string(), r#type));
+    }
+
     /// Reset the list of user selected patterns. It is useful if product preselects some and
     /// user then specify exact list of packages he wants.
     ///
@@ -617,6 +710,12 @@ impl From<ResolvableSelection> for zypp_agama::ResolvableSelected {
 pub struct SoftwareOptions {
     /// Install only required packages (not recommended ones).
     pub only_required: bool,
+    /// Install the needed patches from the update repositories.
+    pub install_updates: bool,
+    /// How to handle the package problems before asking the user.
+    pub retry_policy: RetryPolicy,
+    /// Kernel versions to keep installed (`multiversion.kernels` in zypp.conf).
+    pub multiversion_kernels: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -662,12 +761,13 @@ impl Addon {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{path::PathBuf, time::Duration};
 
     use agama_utils::{
         api::software::{
-            AddonConfig, Config, PatternsConfig, PatternsMap, ProductConfig, Repository,
-            RepositoryConfig, SoftwareConfig,
+            AddonConfig, Config, KernelConfig, KernelFlavor, Manifest, ManifestPackage,
+            PatternsConfig, PatternsMap, ProductConfig, Repository, RepositoryConfig,
+            RetryPolicyConfig, SoftwareConfig,
         },
         kernel_cmdline::KernelCmdline,
         products::{ProductSpec, ProductTemplate},
@@ -858,6 +958,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_manifest() {
+        let product = build_product_spec("tumbleweed", None);
+        let mut config = build_user_config(None);
+        let manifest = Manifest {
+            packages: vec![ManifestPackage {
+                name: "vim".to_string(),
+                version: "9.1.1176-1.1".to_string(),
+                arch: "x86_64".to_string(),
+                repository: "agama-0".to_string(),
+                checksum: None,
+            }],
+        };
+        config.software.as_mut().unwrap().manifest = Some(manifest.clone());
+
+        let state = SoftwareStateBuilder::for_product(&product)
+            .with_config(&config)
+            .build();
+
+        assert_eq!(state.manifest, Some(manifest));
+    }
+
+    #[test]
+    fn test_add_image() {
+        let product = build_product_spec("tumbleweed", None);
+        let state = SoftwareStateBuilder::for_product(&product).build();
+        assert_eq!(state.image, None);
+
+        let mut config = build_user_config(None);
+        config.software.as_mut().unwrap().image =
+            Some("registry.opensuse.org/opensuse/microos:latest".to_string());
+        let state = SoftwareStateBuilder::for_product(&product)
+            .with_config(&config)
+            .build();
+
+        assert_eq!(
+            state.image,
+            Some("registry.opensuse.org/opensuse/microos:latest".to_string())
+        );
+    }
+
+    #[test]
+    fn test_add_update_repositories() {
+        let product = build_product_spec("tumbleweed", None);
+        let update_repo = RepositoryConfig {
+            alias: "updates".to_string(),
+            name: None,
+            url: "https://download.opensuse.org/update/tumbleweed/".to_string(),
+            product_dir: None,
+            enabled: None,
+            priority: None,
+            allow_unsigned: None,
+            gpg_fingerprints: Some(vec!["0123 4567".to_string()]),
+        };
+
+        let mut config = build_user_config(None);
+        config.software.as_mut().unwrap().update_repositories = Some(vec![update_repo]);
+        let state = SoftwareStateBuilder::for_product(&product)
+            .with_config(&config)
+            .build();
+        assert!(!state.options.install_updates);
+        assert!(!state.repositories.iter().any(|r| r.alias == "updates"));
+
+        config.software.as_mut().unwrap().install_updates = Some(true);
+        let state = SoftwareStateBuilder::for_product(&product)
+            .with_config(&config)
+            .build();
+        assert!(state.options.install_updates);
+        assert!(state.repositories.iter().any(|r| r.alias == "updates"));
+        assert!(state
+            .trusted_gpg_keys
+            .iter()
+            .any(|k| k.alias == "updates" && k.fingerprint == "01234567"));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let product = build_product_spec("tumbleweed", None);
+        let mut config = build_user_config(None);
+        config.software.as_mut().unwrap().retry_policy = Some(RetryPolicyConfig {
+            max_retries: Some(3),
+            backoff: Some(2),
+            skip_optional: Some(true),
+            abort_on_core_failure: None,
+        });
+        let state = SoftwareStateBuilder::for_product(&product)
+            .with_config(&config)
+            .build();
+        assert_eq!(state.options.retry_policy.max_retries, 3);
+        assert_eq!(state.options.retry_policy.backoff, Duration::from_secs(2));
+        assert!(state.options.retry_policy.skip_optional);
+        assert!(!state.options.retry_policy.abort_on_core_failure);
+    }
+
     #[test]
     fn test_add_registration() {
         let product = build_product_spec("sles_161", Some("standard"));
@@ -1217,6 +1411,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_user_kernel_flavor() {
+        let product = build_product_spec("tumbleweed", None);
+        let mut config = build_user_config(None);
+        config.software.as_mut().unwrap().kernel = Some(KernelConfig {
+            flavor: Some(KernelFlavor::Rt),
+            multiversion: Some(vec!["latest".to_string(), "latest-1".to_string()]),
+        });
+
+        let state = SoftwareStateBuilder::for_product(&product)
+            .with_config(&config)
+            .build();
+
+        let kernels: Vec<_> = state
+            .resolvables
+            .to_vec()
+            .into_iter()
+            .filter(|(name, r#type, _)| {
+                name.starts_with("kernel-") && *r#type == ResolvableType::Package
+            })
+            .collect();
+
+        assert_eq!(
+            kernels,
+            vec![(
+                "kernel-rt".to_string(),
+                ResolvableType::Package,
+                ResolvableSelection::AutoSelected {
+                    skip_if_missing: false
+                }
+            )]
+        );
+        assert_eq!(
+            state.options.multiversion_kernels,
+            vec!["latest".to_string(), "latest-1".to_string()]
+        );
+    }
+
     #[test]
     fn test_repositories_from_kernel_cmdline() {
         let product = build_product_spec("tumbleweed", None);
@@ -1249,6 +1481,19 @@ mod tests {
         assert_eq!(state.repositories[0].alias, "agama-0");
     }
 
+    #[test]
+    fn test_repositories_mirrors() {
+        let product = build_product_spec("tumbleweed", None);
+
+        let state = SoftwareStateBuilder::for_product(&product).build();
+
+        assert_eq!(
+            state.repositories[0].mirrors,
+            vec!["https://mirror.example.net/tumbleweed/repo/oss/".to_string()]
+        );
+        assert!(state.repositories[1].mirrors.is_empty());
+    }
+
     #[test]
     fn test_repositories_fallback_to_product() {
         let product = build_product_spec("tumbleweed", None);
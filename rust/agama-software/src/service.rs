@@ -19,6 +19,7 @@
 // find current contact information at www.suse.com.
 
 use crate::{
+    image::{self, ImageSource},
     message,
     model::{
         software_selection::SoftwareSelection, state::SoftwareState, ModelAdapter, WriteIssues,
@@ -32,7 +33,8 @@ use agama_utils::{
     actor::{self, Actor, Handler, MessageHandler},
     api::{
         event::{self, Event},
-        software::{Config, Proposal, Repository, SystemInfo},
+        software::{Config, GpgKey, Manifest, Proposal, Repository, SystemInfo},
+        storage::MountPoint,
         Issue, Scope,
     },
     issue,
@@ -42,7 +44,11 @@ use agama_utils::{
 };
 use async_trait::async_trait;
 use gettextrs::gettext;
-use std::{path::PathBuf, process::Command, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+};
 use tokio::sync::{broadcast, Mutex, MutexGuard, RwLock};
 use url::Url;
 
@@ -70,6 +76,10 @@ pub enum Error {
     ZyppError(#[from] zypp_agama::errors::ZyppError),
     #[error("Software installation failed")]
     InstallationFailed,
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error(transparent)]
+    Progress(#[from] progress::service::Error),
 }
 
 impl From<tokio::sync::mpsc::error::SendError<SoftwareAction>> for Error {
@@ -129,6 +139,8 @@ impl Starter {
     const TARGET_DIR: &str = "/run/agama/zypp";
     // FIXME: it should be defined in a single place and injected where needed.
     const INSTALL_DIR: &str = "/mnt";
+    /// Directory to keep the downloaded container images.
+    const IMAGE_DIR: &str = "/run/agama/image";
 
     /// Starts the service and returns a handler to communicate with it.
     pub async fn start(self) -> Result<Handler<Service>, Error> {
@@ -152,6 +164,7 @@ impl Starter {
         let mut service = Service {
             model,
             selection: Default::default(),
+            mount_points: vec![],
             state,
             events: self.events,
             issues: self.issues,
@@ -181,6 +194,7 @@ pub struct Service {
     state: Arc<RwLock<ServiceState>>,
     product: Option<Arc<RwLock<ProductSpec>>>,
     selection: SoftwareSelection,
+    mount_points: Vec<MountPoint>,
     kernel_cmdline: KernelCmdline,
 }
 
@@ -239,7 +253,7 @@ impl Service {
             model.predefined_repositories()
         };
 
-        let new_state = {
+        let mut new_state = {
             let state = self.state.read().await;
             tracing::info!(
                 "computing state from {:?} and {:?} with repos {:?}",
@@ -254,6 +268,7 @@ impl Service {
                 predefined_repositories,
             )
         };
+        new_state.mount_points = self.mount_points.clone();
 
         tracing::info!("Wanted software state: {new_state:?}");
         {
@@ -285,6 +300,10 @@ impl Service {
             return Ok(());
         };
 
+        if let Some(image) = &wanted_state.image {
+            return self.update_image_proposal(image).await;
+        }
+
         tracing::info!("Synchronizing the wanted software state");
         let mut my_model = self.model.lock().await;
         let found_issues = my_model
@@ -317,6 +336,78 @@ impl Service {
         Ok(())
     }
 
+    /// Updates the proposal when the system is deployed from a container image.
+    ///
+    /// The packaging system is not involved at all, so it only checks the image reference.
+    async fn update_image_proposal(&self, image: &str) -> Result<(), Error> {
+        tracing::info!("Using the container image {image}");
+        let found_issues = match ImageSource::parse(image) {
+            Ok(_) => vec![],
+            Err(error) => vec![Issue::new(
+                "software.invalid_image",
+                &gettext("The container image reference is not valid."),
+            )
+            .with_details(&error.to_string())],
+        };
+
+        _ = self
+            .issues
+            .cast(issue::message::Set::new(Scope::Software, found_issues));
+        _ = self
+            .issues
+            .cast(issue::message::Set::new(Scope::Product, vec![]));
+
+        let my_model = self.model.lock().await;
+        Self::update_state(self.state.clone(), my_model, self.events.clone()).await;
+        Ok(())
+    }
+
+    /// Deploys the system from the given container image.
+    ///
+    /// * `image`: image reference.
+    async fn install_image(&self, image: &str) -> Result<(), Error> {
+        let source = ImageSource::parse(image)?;
+        let mut steps = vec![gettext("Unpacking the system image")];
+        if source.is_remote() {
+            steps.insert(0, gettext("Downloading the system image"));
+        }
+        self.progress
+            .call(progress::message::StartWithSteps::new(
+                Scope::Software,
+                steps,
+            ))
+            .await?;
+
+        let result = Self::deploy_image(&source, &self.progress).await;
+        _ = self
+            .progress
+            .cast(progress::message::Finish::new(Scope::Software));
+        result
+    }
+
+    async fn deploy_image(
+        source: &ImageSource,
+        progress: &Handler<progress::Service>,
+    ) -> Result<(), Error> {
+        let (layout, tag) = match source {
+            ImageSource::Layout { path, tag } => (path.clone(), tag.clone()),
+            ImageSource::Remote(reference) => {
+                let downloaded = image::download(reference, Path::new(Starter::IMAGE_DIR)).await?;
+                progress.cast(progress::message::Next::new(Scope::Software))?;
+                downloaded
+            }
+        };
+
+        image::unpack(&layout, &tag, Path::new(Starter::INSTALL_DIR)).await?;
+        Ok(())
+    }
+
+    /// Returns the container image to deploy, if any.
+    async fn image(&self) -> Option<String> {
+        let state = self.state.read().await;
+        state.state.as_ref().and_then(|s| s.image.clone())
+    }
+
     /// Ancillary function to updates the service state with the information from the model.
     ///
     /// FIXME: emit events only when the proposal or the system information change.
@@ -441,6 +532,41 @@ impl MessageHandler<message::GetProposal> for Service {
     }
 }
 
+#[async_trait]
+impl MessageHandler<message::GetManifest> for Service {
+    async fn handle(&mut self, _message: message::GetManifest) -> Result<Manifest, Error> {
+        self.model.lock().await.manifest().await
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::CreateSolverTestcase> for Service {
+    async fn handle(&mut self, _message: message::CreateSolverTestcase) -> Result<PathBuf, Error> {
+        self.model.lock().await.solver_testcase().await
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::ListGpgKeys> for Service {
+    async fn handle(&mut self, _message: message::ListGpgKeys) -> Result<Vec<GpgKey>, Error> {
+        self.model.lock().await.gpg_keys().await
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::ImportGpgKey> for Service {
+    async fn handle(&mut self, message: message::ImportGpgKey) -> Result<(), Error> {
+        self.model.lock().await.import_gpg_key(message.source).await
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::RemoveGpgKey> for Service {
+    async fn handle(&mut self, message: message::RemoveGpgKey) -> Result<(), Error> {
+        self.model.lock().await.remove_gpg_key(&message.id).await
+    }
+}
+
 #[async_trait]
 impl MessageHandler<message::Probe> for Service {
     async fn handle(&mut self, _message: message::Probe) -> Result<(), Error> {
@@ -451,6 +577,10 @@ impl MessageHandler<message::Probe> for Service {
 #[async_trait]
 impl MessageHandler<message::Install> for Service {
     async fn handle(&mut self, _message: message::Install) -> Result<(), Error> {
+        if let Some(image) = self.image().await {
+            return self.install_image(&image).await;
+        }
+
         if !self.model.lock().await.install().await? {
             return Err(Error::InstallationFailed);
         }
@@ -461,6 +591,11 @@ impl MessageHandler<message::Install> for Service {
 #[async_trait]
 impl MessageHandler<message::Finish> for Service {
     async fn handle(&mut self, _message: message::Finish) -> Result<(), Error> {
+        // the repositories and the libzypp configuration do not apply to image-based systems
+        if self.image().await.is_some() {
+            return Ok(());
+        }
+
         self.model.lock().await.finish().await?;
         Ok(())
     }
@@ -475,6 +610,15 @@ impl MessageHandler<message::SetResolvables> for Service {
     }
 }
 
+#[async_trait]
+impl MessageHandler<message::SetMountPoints> for Service {
+    async fn handle(&mut self, message: message::SetMountPoints) -> Result<(), Error> {
+        self.mount_points = message.mount_points;
+        // Proposal will be triggered by SetConfig instead
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl MessageHandler<message::SetLocale> for Service {
     async fn handle(&mut self, _message: message::SetLocale) -> Result<(), Error> {
@@ -23,13 +23,15 @@ use agama_utils::{
     actor::Handler,
     api::{
         self, event,
-        software::{SoftwareProposal, SystemInfo},
+        files::FileSource,
+        software::{GpgKey, Manifest, SoftwareProposal, SystemInfo},
     },
     issue,
     products::ProductSpec,
     progress, question,
 };
 use async_trait::async_trait;
+use std::path::PathBuf;
 
 use crate::{
     model::{state::SoftwareState, WriteIssues},
@@ -55,9 +57,33 @@ impl ModelAdapter for TestModel {
         Ok(SoftwareProposal {
             used_space: 1048576,
             patterns: Default::default(),
+            mirrors: Default::default(),
+            disk_usage: Default::default(),
+            patches_count: None,
+            expanded_packages: Default::default(),
         })
     }
 
+    async fn manifest(&self) -> Result<Manifest, service::Error> {
+        Ok(Manifest::default())
+    }
+
+    async fn solver_testcase(&self) -> Result<PathBuf, service::Error> {
+        Ok(PathBuf::from("/run/agama/solver-testcase.tar.gz"))
+    }
+
+    async fn gpg_keys(&self) -> Result<Vec<GpgKey>, service::Error> {
+        Ok(vec![])
+    }
+
+    async fn import_gpg_key(&self, _source: FileSource) -> Result<(), service::Error> {
+        Ok(())
+    }
+
+    async fn remove_gpg_key(&self, _id: &str) -> Result<(), service::Error> {
+        Ok(())
+    }
+
     /// Refresh repositories information.
     async fn refresh(&mut self) -> Result<(), service::Error> {
         Ok(())
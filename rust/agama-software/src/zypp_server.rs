@@ -22,9 +22,14 @@ use agama_security as security;
 use agama_utils::{
     actor::Handler,
     api::{
+        files::{FileSource, FileSourceError},
         l10n,
         question::QuestionSpec,
-        software::{Pattern, SelectedBy, SoftwareProposal, SystemInfo},
+        software::{
+            DiskUsage, GpgKey, Manifest, ManifestPackage, Pattern, SelectedBy, SoftwareProposal,
+            SystemInfo,
+        },
+        storage::MountPoint,
         Issue, Progress, Scope,
     },
     helpers::copy_dir_all,
@@ -34,7 +39,12 @@ use agama_utils::{
 };
 use camino::{Utf8Path, Utf8PathBuf};
 use gettextrs::gettext;
-use std::collections::HashMap;
+use i18n_format::i18n_format;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
     oneshot,
@@ -44,8 +54,9 @@ use zypp_agama::{errors::ZyppResult, ZyppError};
 use crate::{
     callbacks::{self, ask_software_question},
     model::{
-        packages::ResolvableTypeExt,
-        registration::RegistrationError,
+        mirrors,
+        packages::{PackageExpression, ResolvableTypeExt},
+        registration::{FileBackend, RegistrationBackend, RegistrationError, SUSEConnectBackend},
         state::{self, SoftwareState, AGAMA_REPO_PREFIX},
         WriteIssues,
     },
@@ -56,12 +67,29 @@ use crate::{
 
 const GPG_KEYS: &str = "/usr/lib/rpm/gnupg/keys/gpg-*";
 
+/// Directory to store the GPG keys fetched from the configuration or through the API.
+const GPG_KEYS_DIR: &str = "/run/agama/gpg-keys";
+
+/// Archive containing the solver testcase created on demand.
+const SOLVER_TESTCASE_ARCHIVE: &str = "/run/agama/solver-testcase.tar.gz";
+
+const MIB: u64 = 1024 * 1024;
+
 /// Whether the repository with the given alias is an installer-only repository
 /// that must not end up in the target system.
 fn is_installation_repo(alias: &str) -> bool {
     alias.starts_with(AGAMA_REPO_PREFIX) || alias == DUD_REPO_ALIAS
 }
 
+/// Whether the repository with the given alias is an update repository from a registration
+/// service (e.g., "SLE-Product-SLES-16.0-Updates").
+///
+/// The debuginfo and source update repositories are not considered.
+fn is_update_repo(alias: &str) -> bool {
+    let alias = alias.to_lowercase();
+    alias.ends_with("updates") && !alias.contains("debug") && !alias.contains("source")
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ZyppDispatchError {
     #[error(transparent)]
@@ -104,6 +132,12 @@ pub enum ZyppServerError {
 
     #[error("Failed to copy to target system: {0}")]
     IO(#[from] std::io::Error),
+
+    #[error("Could not create the solver testcase: {0}")]
+    SolverTestcase(String),
+
+    #[error("Could not read the GPG key: {0}")]
+    GpgKeySource(#[from] FileSourceError),
 }
 
 impl From<mpsc::error::SendError<SoftwareAction>> for ZyppServerError {
@@ -126,6 +160,11 @@ pub enum SoftwareAction {
         ProductSpec,
         oneshot::Sender<ZyppServerResult<SoftwareProposal>>,
     ),
+    GetManifest(oneshot::Sender<ZyppServerResult<Manifest>>),
+    CreateSolverTestcase(oneshot::Sender<ZyppServerResult<PathBuf>>),
+    ListGpgKeys(oneshot::Sender<ZyppServerResult<Vec<GpgKey>>>),
+    ImportGpgKey(FileSource, oneshot::Sender<ZyppServerResult<()>>),
+    RemoveGpgKey(String, oneshot::Sender<ZyppServerResult<()>>),
     Write {
         state: SoftwareState,
         progress: Handler<progress::Service>,
@@ -152,9 +191,35 @@ pub struct ZyppServer {
     root_dir: Utf8PathBuf,
     install_dir: Utf8PathBuf,
     trusted_keys: Vec<RepoKey>,
+    /// GPG keys from the configuration, already imported into the keyring.
+    gpg_keys: Vec<FileSource>,
+    /// Files containing the GPG keys from the configuration. They are imported into the target
+    /// system at the end of the installation.
+    gpg_key_files: Vec<PathBuf>,
     unsigned_repos: Vec<String>,
     only_required: bool,
+    /// Kernel versions to keep installed in the target system.
+    multiversion_kernels: Vec<String>,
     save_solver_testcase: bool,
+    /// Candidate URLs for the repositories offering mirrors, sorted by preference. The key is the
+    /// repository alias.
+    mirrors: HashMap<String, Vec<String>>,
+    /// Mirror in use for each repository offering mirrors. The key is the repository alias.
+    selected_mirrors: HashMap<String, String>,
+    /// Backend to talk to the registration server.
+    registration_backend: Arc<dyn RegistrationBackend>,
+    /// Space required and available on each mount point of the storage proposal.
+    disk_usage: Vec<DiskUsage>,
+    /// Number of patches selected for installation, if installing updates.
+    patches_count: Option<u32>,
+    /// Packages selected through an expression. The key is the expression.
+    expanded_packages: HashMap<String, Vec<String>>,
+    /// How to handle the package problems before asking the user.
+    retry_policy: callbacks::RetryPolicy,
+    /// Packages explicitly required by the user, the product or Agama.
+    core_packages: HashSet<String>,
+    /// Packages which can be skipped if they are not available.
+    optional_packages: HashSet<String>,
 }
 
 impl ZyppServer {
@@ -174,9 +239,21 @@ impl ZyppServer {
             install_dir: install_dir.as_ref().to_path_buf(),
             registration: Default::default(),
             trusted_keys: vec![],
+            gpg_keys: vec![],
+            gpg_key_files: vec![],
             unsigned_repos: vec![],
             only_required: false,
+            multiversion_kernels: vec![],
             save_solver_testcase: cmdline.get_last("inst.solver_testcase") == Some("1".to_string()),
+            mirrors: HashMap::new(),
+            selected_mirrors: HashMap::new(),
+            registration_backend: Self::registration_backend(cmdline),
+            disk_usage: vec![],
+            patches_count: None,
+            expanded_packages: HashMap::new(),
+            retry_policy: Default::default(),
+            core_packages: HashSet::new(),
+            optional_packages: HashSet::new(),
         };
 
         // drop the returned JoinHandle: the thread will be detached
@@ -185,6 +262,20 @@ impl ZyppServer {
         Ok(sender)
     }
 
+    /// Returns the registration backend to use.
+    ///
+    /// Setting `inst.registration_fake=<dir>` replaces the registration server with canned
+    /// responses from the given directory (see [FileBackend]).
+    fn registration_backend(cmdline: &KernelCmdline) -> Arc<dyn RegistrationBackend> {
+        match cmdline.get_last("inst.registration_fake") {
+            Some(dir) => {
+                tracing::warn!("Using a fake registration server from {dir}");
+                Arc::new(FileBackend::new(dir))
+            }
+            None => Arc::new(SUSEConnectBackend),
+        }
+    }
+
     /// Runs the server dispatching the actions received through the input channel.
     fn run(mut self) -> Result<(), ZyppDispatchError> {
         let zypp = self.initialize_target_dir()?;
@@ -268,20 +359,43 @@ impl ZyppServer {
             SoftwareAction::GetProposal(product_spec, sender) => {
                 self.proposal(product_spec, sender, zypp)?
             }
+            SoftwareAction::GetManifest(tx) => {
+                tx.send(self.manifest(zypp))
+                    .map_err(|_| ZyppDispatchError::ResponseChannelClosed)?;
+            }
+            SoftwareAction::CreateSolverTestcase(tx) => {
+                tx.send(self.solver_testcase(zypp))
+                    .map_err(|_| ZyppDispatchError::ResponseChannelClosed)?;
+            }
+            SoftwareAction::ListGpgKeys(tx) => {
+                tx.send(self.list_gpg_keys(zypp))
+                    .map_err(|_| ZyppDispatchError::ResponseChannelClosed)?;
+            }
+            SoftwareAction::ImportGpgKey(source, tx) => {
+                tx.send(self.import_gpg_key(zypp, &source))
+                    .map_err(|_| ZyppDispatchError::ResponseChannelClosed)?;
+            }
+            SoftwareAction::RemoveGpgKey(id, tx) => {
+                tx.send(zypp.remove_gpg_key(&id).map_err(ZyppServerError::from))
+                    .map_err(|_| ZyppDispatchError::ResponseChannelClosed)?;
+            }
         }
         Ok(true)
     }
 
     // Install rpms
     fn install(
-        &self,
+        &mut self,
         zypp: &zypp_agama::Zypp,
         progress: Handler<progress::Service>,
         question: Handler<question::Service>,
     ) -> ZyppServerResult<bool> {
         let mut download_callback =
             callbacks::CommitDownload::new(progress.clone(), question.clone());
+        let throughput_callback = download_callback.clone();
         let mut install_callback = callbacks::Install::new(progress.clone(), question.clone());
+        download_callback.set_retries(self.retries());
+        install_callback.set_retries(self.retries());
         let mut security_callback = callbacks::Security::new(question.clone());
         security_callback.set_trusted_gpg_keys(self.trusted_keys.clone());
         security_callback.set_unsigned_repos(self.unsigned_repos.clone());
@@ -290,17 +404,36 @@ impl ZyppServer {
         // use packages count *2 as we need to download package and also install it
         let steps = (packages_count * 2) as usize;
 
+        // the byte-level progress covers both downloading and installing the packages
+        let packages = zypp.list_packages_to_install()?;
+        let installed_sizes: HashMap<_, _> = packages
+            .iter()
+            .map(|p| {
+                let key = (p.name.clone(), p.edition.clone(), p.arch.clone());
+                (key, p.installed_size)
+            })
+            .collect();
+        let total_bytes: u64 = packages
+            .iter()
+            .map(|p| p.download_size + p.installed_size)
+            .sum();
+        install_callback.set_installed_sizes(installed_sizes);
+
         zypp.switch_target(self.install_dir.as_ref())?;
         let mut result;
         loop {
             // cast set progress as if we retry it can result in DupliciteProgress
-            let _ = progress.cast(progress::message::SetProgress::new(Progress::new(
-                Scope::Software,
-                steps,
-                gettext("Starting packages installation"),
-            )));
+            let _ = progress.cast(progress::message::SetProgress::new(
+                Progress::new(
+                    Scope::Software,
+                    steps,
+                    gettext("Starting packages installation"),
+                )
+                .with_total_bytes(total_bytes),
+            ));
             result = zypp.commit(
                 &mut download_callback,
+                &throughput_callback,
                 &mut install_callback,
                 &mut security_callback,
             )?;
@@ -309,6 +442,11 @@ impl ZyppServer {
                 break;
             }
 
+            if self.switch_to_next_mirrors(zypp, &mut security_callback) {
+                tracing::info!("Retrying the installation using the next mirrors");
+                continue;
+            }
+
             let text =
                 gettext("Packages download and installation failed. Would you like to retry?");
             let question_spec =
@@ -339,6 +477,7 @@ impl ZyppServer {
                 name: repo.user_name,
                 alias: repo.alias,
                 url: repo.url,
+                mirrors: vec![],
                 enabled: repo.enabled,
                 priority: None,
             })
@@ -364,6 +503,8 @@ impl ZyppServer {
         zypp: &zypp_agama::Zypp,
     ) -> Result<(), ZyppDispatchError> {
         let mut issues = WriteIssues::default();
+        self.patches_count = None;
+        self.expanded_packages.clear();
         let mut steps = vec![
             gettext("Updating the list of repositories"),
             gettext("Refreshing metadata from the repositories"),
@@ -395,13 +536,25 @@ impl ZyppServer {
 
             // registration phase finishes
             progress.cast(progress::message::Next::new(Scope::Software))?;
+        } else {
+            self.deregister_system(zypp, &mut issues);
+
+            if !issues.is_empty() {
+                return Self::send_issues_and_finish(issues, tx, progress);
+            }
         }
 
+        self.retry_policy = state.options.retry_policy.clone();
+        self.classify_packages(&state.resolvables);
+
+        self.import_config_gpg_keys(zypp, state.gpg_keys, &mut issues);
         self.trusted_keys = state.trusted_gpg_keys;
         security.set_trusted_gpg_keys(self.trusted_keys.clone());
         self.unsigned_repos = state.unsigned_repos;
         security.set_unsigned_repos(self.unsigned_repos.clone());
 
+        let changed_mirrors = self.sort_mirrors(&state.repositories);
+
         let old_aliases: Vec<_> = old_state
             .repositories
             .iter()
@@ -421,11 +574,16 @@ impl ZyppServer {
             .filter(|r| !aliases.contains(&r.alias))
             .collect();
         for repo in &to_add {
-            let result =
-                zypp.add_repository(&repo.alias, &repo.url, repo.priority, |percent, alias| {
-                    tracing::info!("Adding repository {} ({}%)", alias, percent);
-                    true
-                });
+            // use the selected mirror or the fastest one, if any
+            let url = self
+                .selected_mirrors
+                .get(&repo.alias)
+                .or_else(|| self.mirrors.get(&repo.alias).and_then(|urls| urls.first()))
+                .unwrap_or(&repo.url);
+            let result = zypp.add_repository(&repo.alias, url, repo.priority, |percent, alias| {
+                tracing::info!("Adding repository {} ({}%)", alias, percent);
+                true
+            });
 
             if let Err(error) = result {
                 let message = format!("Could not add the repository {}", repo.alias);
@@ -452,14 +610,47 @@ impl ZyppServer {
             }
         }
 
+        if state.options.install_updates {
+            self.enable_update_repos(zypp, &mut issues);
+        }
+
         // all repos are added or removed as needed
         progress.cast(progress::message::Next::new(Scope::Software))?;
-        let result = zypp.load_source(
+
+        // the repositories which do not have mirrors anymore go back to their own URL
+        for repo in &state.repositories {
+            if changed_mirrors.contains(&repo.alias)
+                && !self.mirrors.contains_key(&repo.alias)
+                && old_aliases.contains(&repo.alias)
+            {
+                if let Err(error) = zypp.set_repository_url(&repo.alias, &repo.url) {
+                    tracing::warn!("Could not reset the URL of {}: {}", repo.alias, error);
+                }
+            }
+        }
+
+        // pick a mirror for the repositories which do not have a working one yet (new
+        // repositories or changed list of mirrors); the rest keep their selected mirror
+        let mirrored: Vec<_> = self
+            .mirrors
+            .keys()
+            .filter(|alias| !self.selected_mirrors.contains_key(*alias))
+            .cloned()
+            .collect();
+        let mut refreshed = vec![];
+        for alias in mirrored {
+            if self.refresh_with_failover(zypp, &alias, security) {
+                refreshed.push(alias);
+            }
+        }
+
+        let result = zypp.load_source_skipping_refresh(
             |percent, alias| {
                 tracing::info!("Refreshing repositories: {} ({}%)", alias, percent);
                 true
             },
             security,
+            &refreshed,
         );
 
         if let Err(error) = result {
@@ -475,6 +666,18 @@ impl ZyppServer {
         // reset everything to start from scratch
         zypp.reset_resolvables();
 
+        // replay a previously exported manifest instead of the regular selection
+        if let Some(manifest) = &state.manifest {
+            self.only_required = true;
+            issues.software.append(&mut self.select_manifest(
+                zypp,
+                manifest,
+                state.options.install_updates,
+            ));
+            self.check_disk_space(zypp, &state.mount_points, &mut issues);
+            return Self::send_issues_and_finish(issues, tx, progress);
+        }
+
         tracing::info!("Selecting base product: {}", &state.product);
 
         // FIXME: hotfix/workaround for bsc#1259311 - this should be removed after fixing the solver
@@ -552,6 +755,7 @@ impl ZyppServer {
 
         self.only_required = state.options.only_required;
         tracing::info!("Install only required packages: {}", self.only_required);
+        self.multiversion_kernels = state.options.multiversion_kernels.clone();
 
         // run the solver to select the dependencies, ignore the errors, the solver runs again later
         // do not save the solver testcase in this intermediate step
@@ -564,6 +768,10 @@ impl ZyppServer {
             };
         }
 
+        if state.options.install_updates {
+            issues.software.append(&mut self.select_patches(zypp));
+        }
+
         if let Ok(false) = zypp.run_solver(self.only_required, self.save_solver_testcase) {
             let message = gettext("There are conflicts in the software selection");
             issues
@@ -571,17 +779,342 @@ impl ZyppServer {
                 .push(Issue::new("software.conflict", &message));
         }
 
+        self.check_disk_space(zypp, &state.mount_points, &mut issues);
         Self::send_issues_and_finish(issues, tx, progress)
     }
 
-    fn select_resolvable(
+    /// Classifies the wanted packages as core or optional ones for the retry policy.
+    fn classify_packages(&mut self, resolvables: &ResolvablesState) {
+        self.core_packages.clear();
+        self.optional_packages.clear();
+
+        for (name, r#type, selection) in resolvables.to_vec() {
+            if r#type != ResolvableType::Package {
+                continue;
+            }
+
+            match selection {
+                ResolvableSelection::Selected
+                | ResolvableSelection::AutoSelected {
+                    skip_if_missing: false,
+                } => {
+                    self.core_packages.insert(name.to_string());
+                }
+                ResolvableSelection::AutoSelected {
+                    skip_if_missing: true,
+                } => {
+                    self.optional_packages.insert(name.to_string());
+                }
+                ResolvableSelection::Removed => {}
+            }
+        }
+    }
+
+    /// Returns a new tracker to apply the retry policy.
+    fn retries(&self) -> callbacks::Retries {
+        callbacks::Retries::new(
+            self.retry_policy.clone(),
+            self.core_packages.clone(),
+            self.optional_packages.clone(),
+        )
+    }
+
+    /// Enables the update repositories from the registration services.
+    ///
+    /// The repositories are refreshed later, together with the rest of enabled repositories.
+    fn enable_update_repos(&self, zypp: &zypp_agama::Zypp, issues: &mut WriteIssues) {
+        let RegistrationStatus::Registered(registration) = &self.registration else {
+            return;
+        };
+
+        let mut services = registration.addon_product_service_names();
+        services.extend(registration.base_product_service_name());
+
+        let repos = match zypp.list_repositories() {
+            Ok(repos) => repos,
+            Err(error) => {
+                tracing::error!("Could not read the repositories: {error}");
+                return;
+            }
+        };
+
+        let update_repos = repos.iter().filter(|r| {
+            !r.enabled
+                && is_update_repo(&r.alias)
+                && r.service.as_ref().is_some_and(|s| services.contains(s))
+        });
+
+        for repo in update_repos {
+            tracing::info!("Enabling the update repository {}", repo.alias);
+            if let Err(error) = zypp.enable_repository(&repo.alias) {
+                // TRANSLATORS: %s is the alias of the repository.
+                let message = gettext("Could not enable the update repository %s")
+                    .as_str()
+                    .replace("%s", &repo.alias);
+                issues.software.push(
+                    Issue::new("software.enable_repo", &message).with_details(&error.to_string()),
+                );
+            }
+        }
+    }
+
+    /// Selects the needed patches from the update repositories.
+    ///
+    /// The solver must run before to find out which patches are needed.
+    fn select_patches(&mut self, zypp: &zypp_agama::Zypp) -> Vec<Issue> {
+        let mut issues = vec![];
+        match zypp.select_needed_patches() {
+            Ok(count) => {
+                tracing::info!("Selected {count} patches for installation");
+                self.patches_count = Some(count);
+            }
+            Err(error) => {
+                let message = gettext("Could not select the patches to install");
+                issues.push(
+                    Issue::new("software.select_patches", &message)
+                        .with_details(&error.to_string()),
+                );
+            }
+        }
+        issues
+    }
+
+    /// Checks whether the selected packages fit in the mount points of the storage proposal.
+    ///
+    /// It adds an issue if the space required on any mount point exceeds the size of its file
+    /// system. The check is skipped when the mount points are unknown.
+    fn check_disk_space(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        mount_points: &[MountPoint],
+        issues: &mut WriteIssues,
+    ) {
+        self.disk_usage = match Self::compute_disk_usage(zypp, mount_points) {
+            Ok(disk_usage) => disk_usage,
+            Err(error) => {
+                tracing::error!("Could not compute the disk usage: {error}");
+                vec![]
+            }
+        };
+
+        let exceeded: Vec<_> = self
+            .disk_usage
+            .iter()
+            .filter(|u| u.is_exceeded())
+            .map(|u| {
+                i18n_format!(
+                    // TRANSLATORS: substituting: mount path (e.g., "/usr"), required and
+                    // available space in MiB
+                    "{0}: {1} MiB required, {2} MiB available",
+                    &u.mount_path,
+                    &u.required.div_ceil(MIB).to_string(),
+                    &(u.available / MIB).to_string()
+                )
+            })
+            .collect();
+
+        if !exceeded.is_empty() {
+            let message =
+                gettext("There is not enough disk space to install the selected software");
+            issues.software.push(
+                Issue::new("software.not_enough_space", &message)
+                    .with_details(&exceeded.join("\n")),
+            );
+        }
+    }
+
+    /// Computes the space required by the selected packages on each mount point.
+    fn compute_disk_usage(
+        zypp: &zypp_agama::Zypp,
+        mount_points: &[MountPoint],
+    ) -> ZyppServerResult<Vec<DiskUsage>> {
+        if mount_points.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let zypp_mount_points = mount_points
+            .iter()
+            .map(|mp| zypp_agama::MountPoint {
+                directory: mp.path.clone(),
+                // libzypp only knows about the plain file system types
+                filesystem: if mp.is_btrfs() {
+                    "btrfs".to_string()
+                } else {
+                    mp.filesystem.clone()
+                },
+                grow_only: false,
+                used_size: 0,
+            })
+            .collect();
+        let computed = zypp.count_disk_usage(zypp_mount_points)?;
+
+        let disk_usage = mount_points
+            .iter()
+            .zip(computed)
+            .map(|(mp, computed)| DiskUsage {
+                mount_path: mp.path.clone(),
+                // libzypp reports the used space in KiB
+                required: computed.used_size.max(0) as u64 * 1024,
+                available: mp.size,
+            })
+            .collect();
+        Ok(disk_usage)
+    }
+
+    /// Sorts the mirrors of the given repositories by speed.
+    ///
+    /// The mirrors are probed again only when the list of candidates changes. It returns the
+    /// aliases of the repositories whose list of mirrors changed (including the removed ones).
+    /// Their selected mirror is forgotten, so a new one is picked when refreshing them.
+    fn sort_mirrors(&mut self, repositories: &[state::Repository]) -> Vec<String> {
+        let mut all_mirrors = HashMap::new();
+        let mut changed = vec![];
+
+        for repo in repositories {
+            if repo.mirrors.is_empty() || !repo.enabled {
+                continue;
+            }
+
+            let mut candidates = vec![repo.url.clone()];
+            candidates.extend(repo.mirrors.iter().cloned());
+
+            let sorted = match self.mirrors.remove(&repo.alias) {
+                Some(known) if Self::same_candidates(&known, &candidates) => known,
+                _ => {
+                    changed.push(repo.alias.clone());
+                    mirrors::sort_by_speed(&candidates)
+                }
+            };
+            all_mirrors.insert(repo.alias.clone(), sorted);
+        }
+
+        // the remaining ones do not have mirrors anymore
+        changed.extend(self.mirrors.keys().cloned());
+
+        self.selected_mirrors
+            .retain(|alias, _| all_mirrors.contains_key(alias) && !changed.contains(alias));
+        self.mirrors = all_mirrors;
+        changed
+    }
+
+    fn same_candidates(known: &[String], candidates: &[String]) -> bool {
+        let mut known = known.to_vec();
+        let mut candidates = candidates.to_vec();
+        known.sort();
+        candidates.sort();
+        known == candidates
+    }
+
+    /// Refreshes the repository trying its mirrors in order until one of them works.
+    ///
+    /// The working mirror is recorded in the proposal. If all of them fail, the problem is
+    /// reported when loading the repositories. It returns whether the repository was refreshed.
+    fn refresh_with_failover(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        alias: &str,
+        security: &mut callbacks::Security,
+    ) -> bool {
+        let Some(urls) = self.mirrors.get(alias) else {
+            return false;
+        };
+
+        for url in urls {
+            if self.use_mirror(zypp, alias, url, security) {
+                self.selected_mirrors.insert(alias.to_string(), url.clone());
+                return true;
+            }
+        }
+
+        self.selected_mirrors.remove(alias);
+        false
+    }
+
+    /// Switches the repositories to their next mirror after a failed installation.
+    ///
+    /// It returns whether any repository was switched. The repositories which already use
+    /// their last mirror are kept untouched.
+    fn switch_to_next_mirrors(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        security: &mut callbacks::Security,
+    ) -> bool {
+        let mut switched = false;
+
+        for (alias, urls) in &self.mirrors {
+            let Some(current) = self.selected_mirrors.get(alias) else {
+                continue;
+            };
+            let Some(position) = urls.iter().position(|u| u == current) else {
+                continue;
+            };
+
+            for url in urls.iter().skip(position + 1) {
+                if self.use_mirror(zypp, alias, url, security) {
+                    self.selected_mirrors.insert(alias.clone(), url.clone());
+                    switched = true;
+                    break;
+                }
+            }
+        }
+
+        switched
+    }
+
+    /// Points the repository to the given mirror and refreshes it.
+    ///
+    /// It returns whether the mirror works.
+    fn use_mirror(
         &self,
         zypp: &zypp_agama::Zypp,
+        alias: &str,
+        url: &str,
+        security: &mut callbacks::Security,
+    ) -> bool {
+        // libzypp updates the repository information in the pool too, so the packages are
+        // downloaded from the new mirror without reloading the repository
+        if let Err(error) = zypp.set_repository_url(alias, url) {
+            tracing::warn!("Could not set mirror {} for {}: {}", url, alias, error);
+            return false;
+        }
+
+        match zypp.refresh_repository(
+            alias,
+            &zypp_agama::callbacks::download_progress::EmptyCallback,
+            security,
+        ) {
+            Ok(()) => {
+                tracing::info!("Using mirror {} for {}", url, alias);
+                true
+            }
+            Err(error) => {
+                tracing::warn!("Could not refresh {} from mirror {}: {}", alias, url, error);
+                false
+            }
+        }
+    }
+
+    fn select_resolvable(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
         name: &str,
         r#type: ResolvableType,
         reason: zypp_agama::ResolvableSelected,
         skip_if_missing: bool,
     ) -> Vec<Issue> {
+        if r#type == ResolvableType::Package {
+            let expression = PackageExpression::parse(name);
+            if !matches!(expression, PackageExpression::Name(_)) {
+                return self.select_package_expression(
+                    zypp,
+                    name,
+                    &expression,
+                    reason,
+                    skip_if_missing,
+                );
+            }
+        }
+
         let mut issues = vec![];
         let result = zypp.select_resolvable(name, r#type.to_zypp_kind(), reason);
 
@@ -607,13 +1140,185 @@ impl ZyppServer {
         issues
     }
 
+    /// Selects exactly the packages listed in the manifest.
+    ///
+    /// The solver runs in the "only required" mode and it is not allowed to add any package on
+    /// top of the manifest. Missing packages, checksum mismatches and additional packages are
+    /// reported as issues.
+    ///
+    /// * `install_updates`: whether to select the needed patches too.
+    fn select_manifest(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        manifest: &Manifest,
+        install_updates: bool,
+    ) -> Vec<Issue> {
+        let mut issues = vec![];
+        let mut missing = vec![];
+
+        for package in &manifest.packages {
+            let result = zypp.select_package_build(
+                &package.name,
+                &package.version,
+                &package.arch,
+                Some(&package.repository),
+                package.checksum.as_deref(),
+            );
+
+            if let Err(error) = result {
+                tracing::error!("Could not select {} from the manifest: {}", package, error);
+                missing.push(error.to_string());
+            }
+        }
+
+        if !missing.is_empty() {
+            let message = gettext("Some packages from the software manifest are not available");
+            issues.push(
+                Issue::new("software.manifest_missing", &message).with_details(&missing.join("\n")),
+            );
+            return issues;
+        }
+
+        if install_updates {
+            // the solver must run before to find out which patches are needed
+            let _ = zypp.run_solver(self.only_required, false);
+            issues.append(&mut self.select_patches(zypp));
+        }
+
+        if let Ok(false) = zypp.run_solver(self.only_required, self.save_solver_testcase) {
+            let message = gettext("There are conflicts in the software selection");
+            issues.push(Issue::new("software.conflict", &message));
+            return issues;
+        }
+
+        let extra: Vec<_> = match zypp.list_packages_to_install() {
+            Ok(packages) => packages
+                .into_iter()
+                .filter(|p| !manifest.contains(&p.name, &p.edition, &p.arch))
+                .map(|p| format!("{}-{}.{}", p.name, p.edition, p.arch))
+                .collect(),
+            Err(error) => {
+                let message = gettext("Could not read the list of packages to install");
+                issues.push(
+                    Issue::new("software.manifest_mismatch", &message)
+                        .with_details(&error.to_string()),
+                );
+                return issues;
+            }
+        };
+
+        if !extra.is_empty() {
+            let message =
+                gettext("The software manifest is incomplete, additional packages are required");
+            issues.push(
+                Issue::new("software.manifest_mismatch", &message).with_details(&extra.join("\n")),
+            );
+        }
+
+        issues
+    }
+
+    /// Selects the packages matching an expression (e.g., "provides:webserver").
+    ///
+    /// The matching packages are kept to show the expansion in the proposal. The packages
+    /// are also considered by the retry policy as if they were listed one by one.
+    ///
+    /// * `entry`: package entry from the configuration.
+    /// * `expression`: parsed expression.
+    fn select_package_expression(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        entry: &str,
+        expression: &PackageExpression,
+        reason: zypp_agama::ResolvableSelected,
+        skip_if_missing: bool,
+    ) -> Vec<Issue> {
+        let names = match Self::find_packages(zypp, expression) {
+            Ok(names) => names,
+            Err(error) => {
+                // TRANSLATORS: %s is replaced by a package expression (e.g., "provides:webserver")
+                let message =
+                    gettext("Could not evaluate the package expression '%s'").replace("%s", entry);
+                return vec![Issue::new("software.package_expression", &message)
+                    .with_details(&error.to_string())];
+            }
+        };
+
+        if names.is_empty() {
+            if skip_if_missing {
+                tracing::info!("No package matches '{entry}' but it should be skipped if missing.");
+                return vec![];
+            }
+
+            // TRANSLATORS: %s is replaced by a package expression (e.g., "kernel-firmware-*")
+            let message = gettext("No package matches '%s'").replace("%s", entry);
+            return vec![Issue::new("software.package_expression", &message)];
+        }
+
+        tracing::info!("Package expression '{entry}' matches: {}", names.join(", "));
+        let mut issues = vec![];
+        for name in &names {
+            issues.append(&mut self.select_resolvable(
+                zypp,
+                name,
+                ResolvableType::Package,
+                reason,
+                skip_if_missing,
+            ));
+        }
+
+        let packages = if skip_if_missing {
+            &mut self.optional_packages
+        } else {
+            &mut self.core_packages
+        };
+        packages.extend(names.iter().cloned());
+        self.expanded_packages.insert(entry.to_string(), names);
+        issues
+    }
+
+    /// Returns the names of the packages matching the expression.
+    ///
+    /// For plain package names, it returns the name itself.
+    fn find_packages(
+        zypp: &zypp_agama::Zypp,
+        expression: &PackageExpression,
+    ) -> ZyppResult<Vec<String>> {
+        match expression {
+            PackageExpression::Name(name) => Ok(vec![name.clone()]),
+            PackageExpression::Provides(capability) => {
+                zypp.find_packages(capability, zypp_agama::PackageMatch::Provides)
+            }
+            PackageExpression::Glob(glob) => {
+                zypp.find_packages(glob, zypp_agama::PackageMatch::Glob)
+            }
+            PackageExpression::Regex(regex) => {
+                zypp.find_packages(regex, zypp_agama::PackageMatch::Regex)
+            }
+        }
+    }
+
     fn unselect_resolvable(&self, zypp: &zypp_agama::Zypp, name: &str, r#type: ResolvableType) {
-        if let Err(error) = zypp.unselect_resolvable(
-            name,
-            r#type.to_zypp_kind(),
-            zypp_agama::ResolvableSelected::User,
-        ) {
-            tracing::info!("Could not unselect '{name}': {error}");
+        let names = if r#type == ResolvableType::Package {
+            match Self::find_packages(zypp, &PackageExpression::parse(name)) {
+                Ok(names) => names,
+                Err(error) => {
+                    tracing::info!("Could not evaluate the package expression '{name}': {error}");
+                    return;
+                }
+            }
+        } else {
+            vec![name.to_string()]
+        };
+
+        for name in names {
+            if let Err(error) = zypp.unselect_resolvable(
+                &name,
+                r#type.to_zypp_kind(),
+                zypp_agama::ResolvableSelected::User,
+            ) {
+                tracing::info!("Could not unselect '{name}': {error}");
+            }
         }
     }
 
@@ -650,6 +1355,7 @@ impl ZyppServer {
                 .map_err(|_| ZyppDispatchError::ResponseChannelClosed)?;
             return Ok(());
         }
+        self.copy_gpg_keys();
 
         // if we fail to send ok, lets just ignore it
         let _ = tx.send(Ok(()));
@@ -675,6 +1381,30 @@ impl ZyppServer {
         Ok(())
     }
 
+    /// Imports the GPG keys from the configuration into the rpm keyring of the target system.
+    ///
+    /// A failure does not abort the installation, as the keys are also available in the
+    /// zypp keyring which has been copied to the target system.
+    fn copy_gpg_keys(&self) {
+        for path in &self.gpg_key_files {
+            let output = std::process::Command::new("rpm")
+                .arg("--root")
+                .arg(self.install_dir.as_str())
+                .arg("--import")
+                .arg(path)
+                .output();
+            match output {
+                Ok(output) if output.status.success() => {}
+                Ok(output) => tracing::warn!(
+                    "Failed to import the GPG key {} into the target system: {}",
+                    path.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Err(error) => tracing::warn!("Could not run rpm to import the GPG keys: {error}"),
+            }
+        }
+    }
+
     fn modify_full_repo(&self, zypp: &zypp_agama::Zypp) -> ZyppServerResult<()> {
         let repos = zypp.list_repositories()?;
         // if url is invalid, then do not disable it and do not touch it
@@ -741,14 +1471,31 @@ impl ZyppServer {
     }
 
     fn modify_zypp_conf(&self) {
+        let mut contents = String::new();
         // write only if different from default
         if self.only_required {
-            let contents = "# Use only hard dependencies as configured in installer\nsolver.onlyRequires = true\n";
-            let path = self.install_dir.join("etc/zypp/zypp.conf.d/installer.conf");
-            let write_result = std::fs::write(path.as_path(), contents);
-            if write_result.is_err() {
-                tracing::error!("Failed to write {path}: {write_result:?}");
-            }
+            contents.push_str(
+                "# Use only hard dependencies as configured in installer\nsolver.onlyRequires = true\n",
+            );
+        }
+
+        if !self.multiversion_kernels.is_empty() {
+            contents.push_str(&format!(
+                "# Kernels to keep installed as configured in installer\n\
+                multiversion = provides:multiversion(kernel)\n\
+                multiversion.kernels = {}\n",
+                self.multiversion_kernels.join(",")
+            ));
+        }
+
+        if contents.is_empty() {
+            return;
+        }
+
+        let path = self.install_dir.join("etc/zypp/zypp.conf.d/installer.conf");
+        let write_result = std::fs::write(path.as_path(), contents);
+        if write_result.is_err() {
+            tracing::error!("Failed to write {path}: {write_result:?}");
         }
     }
 
@@ -891,6 +1638,92 @@ impl ZyppServer {
         }
     }
 
+    /// Imports the GPG keys from the configuration into the trusted keyring.
+    ///
+    /// The keys are imported only when they change, to avoid fetching them on each write.
+    /// It adds an issue for each key that cannot be imported.
+    fn import_config_gpg_keys(
+        &mut self,
+        zypp: &zypp_agama::Zypp,
+        gpg_keys: Vec<FileSource>,
+        issues: &mut WriteIssues,
+    ) {
+        if gpg_keys == self.gpg_keys {
+            return;
+        }
+
+        self.gpg_key_files.clear();
+        for (index, source) in gpg_keys.iter().enumerate() {
+            let path = Path::new(GPG_KEYS_DIR).join(format!("config-{index}.asc"));
+            match self.import_gpg_key_from(zypp, source, &path) {
+                Ok(()) => self.gpg_key_files.push(path),
+                Err(error) => {
+                    let message = match source {
+                        FileSource::Remote { url } => i18n_format!(
+                            // TRANSLATORS: substituting: URL of the GPG key
+                            "Could not import the GPG key from {0}",
+                            url.as_str()
+                        ),
+                        FileSource::Text { .. } => gettext("Could not import a GPG key"),
+                    };
+                    issues.software.push(
+                        Issue::new("software.gpg_key", &message).with_details(&error.to_string()),
+                    );
+                }
+            }
+        }
+        self.gpg_keys = gpg_keys;
+    }
+
+    /// Imports a GPG key into the trusted keyring.
+    fn import_gpg_key(&self, zypp: &zypp_agama::Zypp, source: &FileSource) -> ZyppServerResult<()> {
+        let path = Path::new(GPG_KEYS_DIR).join("imported.asc");
+        self.import_gpg_key_from(zypp, source, &path)
+    }
+
+    /// Writes the GPG key to the given path and imports it into the trusted keyring.
+    fn import_gpg_key_from(
+        &self,
+        zypp: &zypp_agama::Zypp,
+        source: &FileSource,
+        path: &Path,
+    ) -> ZyppServerResult<()> {
+        std::fs::create_dir_all(GPG_KEYS_DIR)?;
+        // FileSource::write does not truncate the file
+        _ = std::fs::remove_file(path);
+        source.write(path, 0o600)?;
+        zypp.import_gpg_key(&path.to_string_lossy())?;
+        Ok(())
+    }
+
+    /// Returns the GPG keys known by libzypp.
+    ///
+    /// Each key includes the repositories which explicitly trust it.
+    fn list_gpg_keys(&self, zypp: &zypp_agama::Zypp) -> ZyppServerResult<Vec<GpgKey>> {
+        let keys = zypp
+            .list_gpg_keys()?
+            .into_iter()
+            .map(|key| {
+                let mut gpg_key = GpgKey {
+                    id: key.id,
+                    fingerprint: key.fingerprint,
+                    name: key.name,
+                    expires: key.expires,
+                    trusted: key.trusted,
+                    trusted_by: vec![],
+                };
+                gpg_key.trusted_by = self
+                    .trusted_keys
+                    .iter()
+                    .filter(|k| gpg_key.has_fingerprint(&k.fingerprint))
+                    .map(|k| k.alias.clone())
+                    .collect();
+                gpg_key
+            })
+            .collect();
+        Ok(keys)
+    }
+
     fn proposal(
         &self,
         product: ProductSpec,
@@ -900,6 +1733,10 @@ impl ZyppServer {
         let proposal = SoftwareProposal {
             used_space: self.used_space(zypp)?,
             patterns: self.patterns_selection(&product, zypp)?,
+            mirrors: self.selected_mirrors.clone(),
+            disk_usage: self.disk_usage.clone(),
+            patches_count: self.patches_count,
+            expanded_packages: self.expanded_packages.clone(),
         };
 
         tx.send(Ok(proposal))
@@ -907,6 +1744,60 @@ impl ZyppServer {
         Ok(())
     }
 
+    /// Builds the manifest of the packages selected for installation.
+    fn manifest(&self, zypp: &zypp_agama::Zypp) -> ZyppServerResult<Manifest> {
+        let packages = zypp
+            .list_packages_to_install()?
+            .into_iter()
+            .map(|p| ManifestPackage {
+                name: p.name,
+                version: p.edition,
+                arch: p.arch,
+                repository: p.repo_alias,
+                checksum: p.checksum,
+            })
+            .collect();
+
+        Ok(Manifest { packages })
+    }
+
+    /// Runs the solver saving the testcase and packs it into an archive.
+    ///
+    /// The solver uses the current selection, so the testcase reflects the current proposal.
+    fn solver_testcase(&self, zypp: &zypp_agama::Zypp) -> ZyppServerResult<PathBuf> {
+        if !zypp.run_solver(self.only_required, true)? {
+            tracing::info!("The solver testcase contains unresolved conflicts");
+        }
+
+        let testcase_dir = Path::new(zypp_agama::SOLVER_TESTCASE_DIR);
+        let (Some(parent), Some(name)) = (testcase_dir.parent(), testcase_dir.file_name()) else {
+            return Err(ZyppServerError::SolverTestcase(
+                "invalid testcase directory".to_string(),
+            ));
+        };
+        if !testcase_dir.exists() {
+            return Err(ZyppServerError::SolverTestcase(format!(
+                "{} was not created",
+                testcase_dir.display()
+            )));
+        }
+
+        let output = std::process::Command::new("tar")
+            .arg("-czf")
+            .arg(SOLVER_TESTCASE_ARCHIVE)
+            .arg("-C")
+            .arg(parent)
+            .arg(name)
+            .output()
+            .map_err(|e| ZyppServerError::SolverTestcase(e.to_string()))?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(ZyppServerError::SolverTestcase(error));
+        }
+
+        Ok(PathBuf::from(SOLVER_TESTCASE_ARCHIVE))
+    }
+
     fn used_space(&self, zypp: &zypp_agama::Zypp) -> Result<i64, ZyppServerError> {
         // TODO: for now it just compute total size, but it can get info about partitions from storage and pass it to libzypp
         let mount_points = vec![zypp_agama::MountPoint {
@@ -964,6 +1855,16 @@ impl ZyppServer {
         security_srv: &Handler<security::Service>,
         issues: &mut WriteIssues,
     ) {
+        if let RegistrationStatus::Registered(registration) = &self.registration {
+            if !registration.matches(state) {
+                tracing::info!("The registration settings changed. Registering the system again.");
+                self.deregister_system(zypp, issues);
+                if !issues.is_empty() {
+                    return;
+                }
+            }
+        }
+
         match &self.registration {
             RegistrationStatus::Failed(_) | RegistrationStatus::NotRegistered => {
                 self.register_base_system(state, zypp, security, security_srv, issues);
@@ -971,6 +1872,7 @@ impl ZyppServer {
             RegistrationStatus::Registered(_) => {}
         };
 
+        self.deregister_removed_addons(&state.addons, zypp, issues);
         if !state.addons.is_empty() {
             self.register_addons(&state.addons, zypp, security, issues);
         }
@@ -985,7 +1887,8 @@ impl ZyppServer {
         issues: &mut WriteIssues,
     ) {
         let mut registration =
-            Registration::builder(self.root_dir.clone(), &state.product, &state.version);
+            Registration::builder(self.root_dir.clone(), &state.product, &state.version)
+                .with_backend(Arc::clone(&self.registration_backend));
 
         if let Some(code) = &state.code {
             registration = registration.with_code(code);
@@ -1042,6 +1945,62 @@ impl ZyppServer {
         }
     }
 
+    /// Deregisters the system, removing the services added during the registration.
+    ///
+    /// It does nothing if the system is not registered.
+    ///
+    /// - `zypp`: zypp instance.
+    /// - `issues`: list of issues to update.
+    fn deregister_system(&mut self, zypp: &zypp_agama::Zypp, issues: &mut WriteIssues) {
+        let RegistrationStatus::Registered(registration) = &mut self.registration else {
+            return;
+        };
+
+        match registration.deregister(zypp) {
+            Ok(()) => {
+                self.registration = RegistrationStatus::NotRegistered;
+            }
+            Err(error) => {
+                issues.product.push(
+                    Issue::new(
+                        "system_deregistration_failed",
+                        &gettext("Failed to deregister the system"),
+                    )
+                    .with_details(&error.to_string()),
+                );
+            }
+        }
+    }
+
+    /// Deregisters the add-ons which are not wanted anymore.
+    ///
+    /// - `addons`: wanted add-ons.
+    /// - `zypp`: zypp instance.
+    /// - `issues`: list of issues to update.
+    fn deregister_removed_addons(
+        &mut self,
+        addons: &[Addon],
+        zypp: &zypp_agama::Zypp,
+        issues: &mut WriteIssues,
+    ) {
+        let RegistrationStatus::Registered(registration) = &mut self.registration else {
+            return;
+        };
+
+        for id in registration.registered_addons() {
+            if addons.iter().any(|a| a.id == id) {
+                continue;
+            }
+
+            if let Err(error) = registration.deregister_addon(zypp, &id) {
+                let message = format!("Failed to deregister the add-on {}", id);
+                let issue_id = format!("addon_deregistration_failed[{}]", id);
+                let issue = Issue::new(&issue_id, &message).with_details(&error.to_string());
+                issues.product.push(issue);
+            }
+        }
+    }
+
     /// Ancillary function to send the issues and finish the progress early.
     fn send_issues_and_finish(
         issues: WriteIssues,
@@ -1076,7 +2035,7 @@ impl ZyppServer {
 
 #[cfg(test)]
 mod tests {
-    use super::is_installation_repo;
+    use super::{is_installation_repo, is_update_repo};
 
     #[test]
     fn test_is_installation_repo() {
@@ -1094,4 +2053,16 @@ mod tests {
         assert!(!is_installation_repo("repo-agama-0"));
         assert!(!is_installation_repo(""));
     }
+
+    #[test]
+    fn test_is_update_repo() {
+        assert!(is_update_repo("SLE-Product-SLES-16.0-Updates"));
+        assert!(is_update_repo(
+            "SLES_16.0_x86_64:SLE-Module-Basesystem-16.0-Updates"
+        ));
+
+        assert!(!is_update_repo("SLE-Product-SLES-16.0-Pool"));
+        assert!(!is_update_repo("SLE-Product-SLES-16.0-Debuginfo-Updates"));
+        assert!(!is_update_repo("SLE-Product-SLES-16.0-Source-Updates"));
+    }
 }
@@ -0,0 +1,225 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Registration flows against a file-backed registration server.
+
+use agama_software::registration::{FileBackend, RegistrationBackend};
+use camino::{Utf8Path, Utf8PathBuf};
+use suseconnect_agama::{ConnectParams, Error, ProductSpecification, GLOBAL_CREDENTIALS_FILE};
+use url::Url;
+
+fn fixtures_dir() -> Utf8PathBuf {
+    Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/share/registration")
+}
+
+fn connect_params(code: Option<&str>) -> ConnectParams {
+    ConnectParams {
+        language: Some("en_US".to_string()),
+        url: None,
+        token: code.map(ToString::to_string),
+        email: None,
+    }
+}
+
+fn product_spec(identifier: &str) -> ProductSpecification {
+    ProductSpecification {
+        identifier: identifier.to_string(),
+        version: "16.0".to_string(),
+        arch: "x86_64".to_string(),
+    }
+}
+
+/// Copies the registration fixtures to a temporary directory including a server certificate.
+fn fixtures_with_certificate() -> tempfile::TempDir {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let target = Utf8Path::from_path(tmp_dir.path()).unwrap();
+    agama_utils::helpers::copy_dir_all(&fixtures_dir(), target).unwrap();
+    let certificate = Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/share/test.pem");
+    std::fs::copy(certificate, target.join("certificate.pem")).unwrap();
+    tmp_dir
+}
+
+#[test]
+fn test_announce_system() {
+    let backend = FileBackend::new(fixtures_dir());
+    let credentials = backend
+        .announce_system(connect_params(Some("REGCODE")), "SLES-16-x86_64")
+        .unwrap();
+
+    assert_eq!(credentials.login, "SCC_0123456789abcdef");
+    assert_eq!(credentials.password, "s3cr3t");
+}
+
+#[test]
+fn test_activate_product() {
+    let backend = FileBackend::new(fixtures_dir());
+    let params = connect_params(Some("REGCODE"));
+    let credentials = backend
+        .announce_system(params.clone(), "SLES-16-x86_64")
+        .unwrap();
+    backend
+        .create_credentials_file(
+            &credentials.login,
+            &credentials.password,
+            GLOBAL_CREDENTIALS_FILE,
+        )
+        .unwrap();
+
+    let service = backend
+        .activate_product(product_spec("SLES"), params, "")
+        .unwrap();
+
+    assert_eq!(service.name, "SUSE_Linux_Enterprise_Server_16.0_x86_64");
+    assert!(service
+        .url
+        .starts_with("https://scc.suse.com/access/services/"));
+    assert_eq!(
+        backend
+            .credentials(GLOBAL_CREDENTIALS_FILE)
+            .map(|c| c.login),
+        Some("SCC_0123456789abcdef".to_string())
+    );
+    assert_eq!(backend.activated_products(), vec!["SLES".to_string()]);
+}
+
+#[test]
+fn test_show_product_addons() {
+    let backend = FileBackend::new(fixtures_dir());
+    let product = backend
+        .show_product(product_spec("SLES"), ConnectParams::default())
+        .unwrap();
+
+    let addons: Vec<_> = product
+        .extensions
+        .iter()
+        .map(|e| e.identifier.as_str())
+        .collect();
+    assert_eq!(addons, vec!["sle-ha", "PackageHub"]);
+    assert!(product.extensions[1].free);
+}
+
+#[test]
+fn test_activate_addons() {
+    let backend = FileBackend::new(fixtures_dir());
+    let params = connect_params(Some("REGCODE"));
+    backend
+        .activate_product(product_spec("SLES"), params.clone(), "")
+        .unwrap();
+
+    let mut addon_params = params.clone();
+    addon_params.token = Some("HA-REGCODE".to_string());
+    let service = backend
+        .activate_product(product_spec("sle-ha"), addon_params, "")
+        .unwrap();
+    assert_eq!(
+        service.name,
+        "SUSE_Linux_Enterprise_High_Availability_Extension_16.0_x86_64"
+    );
+
+    let result = backend.activate_product(product_spec("PackageHub"), params, "");
+    assert!(matches!(result, Err(Error::SCCApi { code: 404, .. })));
+
+    assert_eq!(
+        backend.activated_products(),
+        vec!["SLES".to_string(), "sle-ha".to_string()]
+    );
+}
+
+#[test]
+fn test_rmt_with_self_signed_certificate() {
+    let tmp_dir = fixtures_with_certificate();
+    let backend = FileBackend::new(Utf8Path::from_path(tmp_dir.path()).unwrap());
+    let mut params = connect_params(None);
+    params.url = Some(Url::parse("https://rmt.example.net").unwrap());
+
+    let result = backend.announce_system(params.clone(), "SLES-16-x86_64");
+    let Err(Error::SSL {
+        code,
+        current_certificate,
+        ..
+    }) = result
+    else {
+        panic!("Expected an SSL error");
+    };
+    assert!(code.is_fixable_by_import());
+    assert!(current_certificate.starts_with("-----BEGIN CERTIFICATE-----"));
+
+    backend.reload_certificates().unwrap();
+    backend
+        .announce_system(params.clone(), "SLES-16-x86_64")
+        .unwrap();
+    backend
+        .activate_product(product_spec("SLES"), params.clone(), "")
+        .unwrap();
+
+    backend.write_config(params).unwrap();
+    let config = backend.config().unwrap();
+    assert_eq!(
+        config.url,
+        Some(Url::parse("https://rmt.example.net").unwrap())
+    );
+}
+
+#[test]
+fn test_deactivate_addon() {
+    let backend = FileBackend::new(fixtures_dir());
+    let params = connect_params(Some("REGCODE"));
+    backend
+        .activate_product(product_spec("SLES"), params.clone(), "")
+        .unwrap();
+    backend
+        .activate_product(product_spec("sle-ha"), params.clone(), "")
+        .unwrap();
+
+    let service = backend
+        .deactivate_product(product_spec("sle-ha"), params.clone())
+        .unwrap();
+    assert_eq!(
+        service.name,
+        "SUSE_Linux_Enterprise_High_Availability_Extension_16.0_x86_64"
+    );
+    assert_eq!(backend.activated_products(), vec!["SLES".to_string()]);
+
+    let result = backend.deactivate_product(product_spec("sle-ha"), params);
+    assert!(matches!(result, Err(Error::SCCApi { code: 422, .. })));
+}
+
+#[test]
+fn test_deactivate_system() {
+    let backend = FileBackend::new(fixtures_dir());
+    let params = connect_params(Some("REGCODE"));
+    let credentials = backend
+        .announce_system(params.clone(), "SLES-16-x86_64")
+        .unwrap();
+    backend
+        .create_credentials_file(
+            &credentials.login,
+            &credentials.password,
+            GLOBAL_CREDENTIALS_FILE,
+        )
+        .unwrap();
+    backend
+        .activate_product(product_spec("SLES"), params.clone(), "")
+        .unwrap();
+
+    backend.deactivate_system(params).unwrap();
+    assert!(backend.activated_products().is_empty());
+    assert!(backend.credentials(GLOBAL_CREDENTIALS_FILE).is_none());
+}
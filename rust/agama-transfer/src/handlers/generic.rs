@@ -18,7 +18,7 @@
 // To contact SUSE LLC about this file by physical or electronic mail, you may
 // find current contact information at www.suse.com.
 
-use std::io::Write;
+use std::{io::Write, time::Duration};
 
 use curl::easy::Easy;
 use url::Url;
@@ -29,14 +29,27 @@ use crate::{Error, TransferResult};
 ///
 /// It uses curl under the hood.
 #[derive(Default)]
-pub struct GenericHandler {}
+pub struct GenericHandler {
+    timeout: Option<Duration>,
+}
 
 impl GenericHandler {
+    /// Gives up if the transfer takes longer than the given time.
+    ///
+    /// * `timeout`: maximum time for the whole transfer.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     pub fn get(&self, url: Url, out_fd: &mut impl Write, insecure: bool) -> TransferResult<()> {
         let mut handle = Easy::new();
         handle.follow_location(true)?;
         handle.fail_on_error(true)?;
         handle.url(url.as_ref())?;
+        if let Some(timeout) = self.timeout {
+            handle.timeout(timeout)?;
+        }
 
         if insecure {
             // allow self-signed certificate, ignore verification failures
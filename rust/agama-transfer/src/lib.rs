@@ -43,7 +43,7 @@
 //! Transfer::get("label://OEMDRV/autoinst.xml", &mut std::io::stdout(), false).unwrap();
 //! ````
 
-use std::io::Write;
+use std::{io::Write, time::Duration};
 
 use url::Url;
 
@@ -94,4 +94,29 @@ impl Transfer {
             _ => GenericHandler::default().get(url, out_fd, insecure),
         }
     }
+
+    /// Retrieves and writes the data from an URL, giving up after the given time
+    ///
+    /// The timeout only applies to the URLs handled by curl (e.g., `http:`, `ftp:` or `file:`).
+    ///
+    /// * `url`: URL to get the data from.
+    /// * `out_fd`: where to write the data.
+    /// * `insecure`: ignore SSL problems in HTTPS downloads.
+    /// * `timeout`: maximum time for the whole transfer.
+    pub fn get_with_timeout(
+        url: &str,
+        out_fd: &mut impl Write,
+        insecure: bool,
+        timeout: Duration,
+    ) -> TransferResult<()> {
+        let url = Url::parse(url).map_err(|e| Error::ParseError(url.to_string(), e))?;
+        match url.scheme() {
+            "device" | "usb" | "label" | "cd" | "dvd" | "hd" => {
+                Self::get(url.as_str(), out_fd, insecure)
+            }
+            _ => GenericHandler::default()
+                .with_timeout(timeout)
+                .get(url, out_fd, insecure),
+        }
+    }
 }
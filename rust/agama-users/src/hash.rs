@@ -0,0 +1,96 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Hashes the plain text passwords of the users configuration.
+
+pub use agama_utils::password::{hash_password, HashError};
+use agama_utils::{api::users::Config, password::verify_password};
+
+/// Hashes the plain text passwords of the given configuration.
+///
+/// The hashes use a random salt, so the existing hash of an account is kept if it matches the
+/// plain text password. Otherwise, setting the same configuration again would always be a change.
+///
+/// * `config`: users configuration.
+/// * `old`: configuration containing the existing hashes.
+pub async fn hash_passwords(config: &mut Config, old: &Config) -> Result<(), HashError> {
+    let old_passwords = old.passwords();
+    for (name, password) in config.passwords_mut() {
+        if password.hashed_password || password.is_empty() {
+            continue;
+        }
+
+        let old_hash = old_passwords
+            .iter()
+            .find(|(n, p)| n == &name && p.hashed_password && !p.is_redacted())
+            .map(|(_, p)| p.password.as_str());
+        let unchanged = match old_hash {
+            Some(hash) => verify_password(&password.password, hash).await?,
+            None => false,
+        };
+        password.password = match old_hash {
+            Some(hash) if unchanged => hash.to_string(),
+            _ => hash_password(&password.password).await?,
+        };
+        password.hashed_password = true;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agama_utils::api::users::config::{RootUserConfig, UserPassword};
+
+    #[tokio::test]
+    async fn test_hash_passwords() {
+        let mut config = Config {
+            root: Some(RootUserConfig {
+                password: Some(UserPassword {
+                    password: "nots3cr3t".to_string(),
+                    hashed_password: false,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let plain = config.clone();
+        hash_passwords(&mut config, &Config::default())
+            .await
+            .unwrap();
+
+        let password = config.root.clone().unwrap().password.unwrap();
+        assert!(password.hashed_password);
+        assert!(password.password.starts_with("$6$"));
+
+        // the existing hash is kept if the password did not change
+        let mut same = plain.clone();
+        hash_passwords(&mut same, &config).await.unwrap();
+        assert_eq!(same, config);
+
+        let mut changed = plain;
+        changed.root.as_mut().unwrap().password = Some(UserPassword {
+            password: "n0ts3cr3t".to_string(),
+            hashed_password: false,
+        });
+        hash_passwords(&mut changed, &config).await.unwrap();
+        assert_ne!(changed, config);
+    }
+}
@@ -28,3 +28,11 @@ pub use model::{Model, ModelAdapter};
 
 mod password;
 pub use password::{PasswordCheckResult, PasswordChecker, PasswordCheckerError};
+
+mod report;
+pub use report::{InstallFailure, InstallReport};
+
+pub mod hash;
+pub use hash::{hash_password, HashError};
+
+mod validation;
@@ -20,10 +20,10 @@
 
 use agama_utils::{
     actor::Message,
-    api::{self},
+    api::{self, users::config::PasswordPolicy},
 };
 
-use crate::PasswordCheckResult;
+use crate::{InstallReport, PasswordCheckResult};
 
 #[derive(Clone)]
 pub struct SetSystem<T> {
@@ -42,11 +42,24 @@ impl Message for GetConfig {
 
 pub struct SetConfig<T> {
     pub config: Option<T>,
+    /// Password policy defined by the product, used if the configuration does not define one.
+    pub product_policy: Option<PasswordPolicy>,
 }
 
 impl<T> SetConfig<T> {
     pub fn new(config: Option<T>) -> Self {
-        Self { config }
+        Self {
+            config,
+            product_policy: None,
+        }
+    }
+
+    /// Sets the password policy defined by the product.
+    ///
+    /// * `policy`: product's password policy, if any.
+    pub fn with_product_policy(mut self, policy: Option<PasswordPolicy>) -> Self {
+        self.product_policy = policy;
+        self
     }
 }
 
@@ -63,7 +76,7 @@ impl Message for GetProposal {
 pub struct Install;
 
 impl Message for Install {
-    type Reply = ();
+    type Reply = InstallReport;
 }
 
 #[derive(Clone)]
@@ -85,11 +98,21 @@ impl Message for SetLocale {
 
 pub struct CheckPassword {
     pub password: String,
+    pub user_name: Option<String>,
 }
 
 impl CheckPassword {
     pub fn new(password: String) -> Self {
-        CheckPassword { password }
+        CheckPassword {
+            password,
+            user_name: None,
+        }
+    }
+
+    /// Sets the name of the user the password belongs to.
+    pub fn with_user_name(mut self, user_name: Option<String>) -> Self {
+        self.user_name = user_name;
+        self
     }
 }
 
@@ -18,8 +18,10 @@
 // To contact SUSE LLC about this file by physical or electronic mail, you may
 // find current contact information at www.suse.com.
 
-use crate::service;
-use agama_utils::api::users::config::{FirstUserConfig, RootUserConfig, UserPassword};
+use crate::{service, InstallReport};
+use agama_utils::api::users::config::{
+    FirstUserConfig, GroupConfig, RootUserConfig, SudoersConfig, UserConfig, UserPassword,
+};
 use agama_utils::api::users::Config;
 use agama_utils::command::ChrootCommand;
 use std::fs::{self, OpenOptions, Permissions};
@@ -34,8 +36,10 @@ use tokio::io::AsyncWriteExt;
 pub trait ModelAdapter: Send + Sync + 'static {
     /// Apply the changes to target system. It is expected to be called almost
     /// at the end of the installation.
-    async fn install(&self, _config: &Config) -> Result<(), service::Error> {
-        Ok(())
+    ///
+    /// It returns a report including the failures found when writing the configuration.
+    async fn install(&self, config: &Config) -> Result<InstallReport, service::Error> {
+        Ok(InstallReport::new(config.strictness.unwrap_or_default()))
     }
 }
 
@@ -51,12 +55,18 @@ impl Model {
         }
     }
 
-    async fn useradd(&self, user_name: &str) -> Result<(), service::Error> {
+    /// Creates a user.
+    ///
+    /// * `user_name`: user name.
+    /// * `options`: additional options for the useradd command.
+    async fn useradd(&self, user_name: &str, options: &[String]) -> Result<(), service::Error> {
         let useradd = ChrootCommand::new(self.install_dir.clone())?
             .cmd("useradd")
             // Explicitly enforce creating home here, so even if some product has as default no
             // home, we need it to be able to support user ssh keys.
-            .args(["-m", user_name])
+            .arg("-m")
+            .args(options)
+            .arg(user_name)
             .output()
             .await?;
 
@@ -72,14 +82,14 @@ impl Model {
     }
 
     /// Reads first user's data from given config and updates its setup accordingly
-    async fn add_first_user(&self, user: &FirstUserConfig) {
+    async fn add_first_user(&self, user: &FirstUserConfig, report: &mut InstallReport) {
         let Some(ref user_name) = user.user_name else {
             tracing::warn!("user name is missing in first user config");
             return;
         };
 
-        if let Err(err) = self.useradd(user_name).await {
-            tracing::error!("Failed to create first user: {:?}", err);
+        if let Err(err) = self.useradd(user_name, &[]).await {
+            report.add_failure(user_name, err);
             return;
         }
 
@@ -90,32 +100,197 @@ impl Model {
             .unwrap_or_default();
 
         let keys_path = PathBuf::from(format!("home/{}/.ssh/authorized_keys", user_name));
-        self.activate_ssh(&keys_path, &ssh_keys, Some(user_name))
+        let ssh_login = self
+            .activate_ssh(&keys_path, &ssh_keys, Some(user_name), report)
             .await;
 
-        self.set_user_group(user_name).await;
-        if let Some(ref user_password) = user.password {
-            if let Err(e) = self.set_user_password(user_name, user_password).await {
-                tracing::error!("Failed to set user password: {e}");
-            }
-        };
+        // The wheel group might not exist, so it is added on best effort basis.
+        if let Err(e) = self.set_user_group(user_name, "wheel").await {
+            tracing::warn!("{e}");
+        }
+        let password_login = self
+            .set_password(user_name, user.password.as_ref(), report)
+            .await;
         if let Err(e) = self.update_user_fullname(user).await {
-            tracing::error!("Failed to set user fullname: {e}");
+            report.add_failure(user_name, e);
+        }
+
+        if ssh_login || password_login {
+            report.add_login(user_name);
+        }
+    }
+
+    /// Creates a group.
+    async fn add_group(&self, group: &GroupConfig) -> Result<(), service::Error> {
+        let mut command = ChrootCommand::new(self.install_dir.clone())?.cmd("groupadd");
+        if let Some(gid) = group.gid {
+            command.arg("-g").arg(gid.to_string());
+        }
+        if group.is_system() {
+            command.arg("-r");
+        }
+
+        let groupadd = command.arg(&group.group_name).output().await?;
+        if !groupadd.status.success() {
+            tracing::error!("Group {} creation failed", &group.group_name);
+            return Err(service::Error::CommandFailed(format!(
+                "Group creation failed: {}",
+                groupadd.status
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Creates an additional user and sets it up according to the given config.
+    ///
+    /// * `user`: user settings.
+    /// * `groups`: groups defined in the configuration.
+    /// * `report`: report to register the failures and the login accounts.
+    async fn add_user(
+        &self,
+        user: &UserConfig,
+        groups: &[GroupConfig],
+        report: &mut InstallReport,
+    ) {
+        let user_name = &user.user_name;
+
+        let mut options = vec![];
+        if let Some(uid) = user.uid {
+            options.extend(["-u".to_string(), uid.to_string()]);
+        }
+        if let Some(gid) = user.gid {
+            options.extend(["-g".to_string(), gid.to_string()]);
+        } else if groups.iter().any(|g| &g.group_name == user_name) {
+            // useradd refuses to create the user's own group if it already exists.
+            options.extend(["-g".to_string(), user_name.clone()]);
+        }
+        if let Some(home) = &user.home {
+            options.extend(["-d".to_string(), home.clone()]);
+        }
+        if let Some(shell) = &user.shell {
+            options.extend(["-s".to_string(), shell.clone()]);
+        }
+        if let Some(full_name) = &user.full_name {
+            options.extend(["-c".to_string(), full_name.clone()]);
+        }
+        if let Some(date) = &user.expiration_date {
+            options.extend(["-e".to_string(), date.clone()]);
+        }
+        if user.is_system() {
+            options.push("-r".to_string());
+        }
+
+        if let Err(err) = self.useradd(user_name, &options).await {
+            report.add_failure(user_name, err);
+            return;
+        }
+
+        for group in user.groups.iter().flatten() {
+            if let Err(e) = self.set_user_group(user_name, group).await {
+                report.add_failure(user_name, e);
+            }
+        }
+
+        let password_login = self
+            .set_password(user_name, user.password.as_ref(), report)
+            .await;
+
+        if user.is_locked() {
+            if let Err(e) = self.lock_user(user_name).await {
+                report.add_failure(user_name, e);
+            }
+        }
+
+        let ssh_keys = user
+            .ssh_public_keys
+            .as_ref()
+            .map(|k| k.to_vec())
+            .unwrap_or_default();
+
+        let home = user.home_dir();
+        let keys_path = PathBuf::from(home.trim_start_matches('/')).join(".ssh/authorized_keys");
+        let ssh_login = self
+            .activate_ssh(&keys_path, &ssh_keys, Some(user_name), report)
+            .await;
+
+        if !user.is_locked() && (ssh_login || password_login) {
+            report.add_login(user_name);
+        }
+    }
+
+    /// Locks the given user account.
+    async fn lock_user(&self, user_name: &str) -> Result<(), service::Error> {
+        let usermod = ChrootCommand::new(self.install_dir.clone())?
+            .cmd("usermod")
+            .args(["-L", user_name])
+            .output()
+            .await?;
+
+        if !usermod.status.success() {
+            return Err(service::Error::CommandFailed(format!(
+                "Cannot lock user {}: {}",
+                user_name, usermod.status
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Writes a sudoers drop-in file.
+    ///
+    /// The file is checked with visudo and removed if it is not valid, so it does not break sudo
+    /// in the installed system.
+    async fn add_sudoers(&self, sudoers: &SudoersConfig) -> Result<(), service::Error> {
+        const SUDOERS_DIR: &str = "etc/sudoers.d";
+
+        let dir = self.install_dir.join(SUDOERS_DIR);
+        fs::create_dir_all(&dir)?;
+
+        let file_name = dir.join(&sudoers.name);
+        let mode = 0o440;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .mode(mode)
+            .open(&file_name)?;
+        fs::set_permissions(&file_name, Permissions::from_mode(mode))?;
+
+        for rule in &sudoers.rules {
+            writeln!(file, "{}", rule.trim())?;
         }
+        file.flush()?;
+
+        let target_path = format!("/{}/{}", SUDOERS_DIR, &sudoers.name);
+        let visudo = ChrootCommand::new(self.install_dir.clone())?
+            .cmd("visudo")
+            .args(["-c", "-f", target_path.as_str()])
+            .output()
+            .await?;
+
+        if !visudo.status.success() {
+            fs::remove_file(&file_name)?;
+            return Err(service::Error::CommandFailed(format!(
+                "Invalid sudoers file {}: {}",
+                &sudoers.name,
+                String::from_utf8_lossy(&visudo.stderr)
+            )));
+        }
+
+        Ok(())
     }
 
     /// Reads root's data from given config and updates root setup accordingly
-    async fn add_root_user(&self, root: &RootUserConfig) {
+    async fn add_root_user(&self, root: &RootUserConfig, report: &mut InstallReport) {
         if root.password.is_none() && root.ssh_public_keys.is_none() {
             return;
         };
 
         // set password for root if any
-        if let Some(ref root_password) = root.password {
-            if let Err(e) = self.set_user_password("root", root_password).await {
-                tracing::error!("Failed to set root password: {e}");
-            }
-        }
+        let password_login = self
+            .set_password("root", root.password.as_ref(), report)
+            .await;
 
         // store sshPublicKeys for root if any
         let ssh_keys = root
@@ -124,21 +299,64 @@ impl Model {
             .map(|k| k.to_vec())
             .unwrap_or_default();
 
-        self.activate_ssh(Path::new("root/.ssh/authorized_keys"), &ssh_keys, None)
+        let ssh_login = self
+            .activate_ssh(
+                Path::new("root/.ssh/authorized_keys"),
+                &ssh_keys,
+                None,
+                report,
+            )
             .await;
+
+        if ssh_login || password_login {
+            report.add_login("root");
+        }
     }
 
-    async fn activate_ssh(&self, path: &Path, ssh_keys: &[String], user: Option<&str>) {
+    /// Writes the SSH keys, if any, to the given authorized_keys file.
+    ///
+    /// It returns whether the keys were written.
+    async fn activate_ssh(
+        &self,
+        path: &Path,
+        ssh_keys: &[String],
+        user: Option<&str>,
+        report: &mut InstallReport,
+    ) -> bool {
         if ssh_keys.is_empty() {
-            return;
+            return false;
         }
 
         // if some SSH keys were defined
         // - update authorized_keys file
         // - open SSH port and enable SSH service
         if let Err(e) = self.update_authorized_keys(path, ssh_keys, user).await {
-            tracing::error!("Failed to update authorized_keys file: {e}");
+            report.add_failure(user.unwrap_or("root"), e);
+            return false;
         }
+
+        true
+    }
+
+    /// Sets the password, if any, for the given user.
+    ///
+    /// It returns whether a non-empty password was set.
+    async fn set_password(
+        &self,
+        user_name: &str,
+        user_password: Option<&UserPassword>,
+        report: &mut InstallReport,
+    ) -> bool {
+        let Some(user_password) = user_password else {
+            return false;
+        };
+
+        if let Err(e) = self.set_user_password(user_name, user_password).await {
+            report.add_failure(user_name, e);
+            return false;
+        }
+
+        !user_password.is_empty()
     }
 
     /// Sets password for given user name
@@ -179,32 +397,22 @@ impl Model {
         Ok(())
     }
 
-    /// Add user into the wheel group on best effort basis.
-    /// If the group doesn't exist, log the error and continue.
-    async fn set_user_group(&self, user_name: &str) {
-        let chroot = ChrootCommand::new(self.install_dir.clone());
-        let Ok(chroot) = chroot else {
-            tracing::error!("Failed to chroot: {:?}", chroot);
-            return;
-        };
-
-        let usermod = chroot
+    /// Adds the user into the given group.
+    async fn set_user_group(&self, user_name: &str, group: &str) -> Result<(), service::Error> {
+        let usermod = ChrootCommand::new(self.install_dir.clone())?
             .cmd("usermod")
-            .args(["-a", "-G", "wheel", user_name])
+            .args(["-a", "-G", group, user_name])
             .output()
-            .await;
-        let Ok(usermod) = usermod else {
-            tracing::error!("Failed to execute usermod {:?}", usermod);
-            return;
-        };
+            .await?;
 
         if !usermod.status.success() {
-            tracing::warn!(
-                "Adding user {} into the \"wheel\" group failed, code={}",
-                user_name,
-                usermod.status
-            );
+            return Err(service::Error::CommandFailed(format!(
+                "Adding user {} into the \"{}\" group failed, code={}",
+                user_name, group, usermod.status
+            )));
         }
+
+        Ok(())
     }
 
     /// Changes the owner and group of the target path inside the chroot environment.
@@ -308,14 +516,75 @@ impl Model {
 
 #[async_trait::async_trait]
 impl ModelAdapter for Model {
-    async fn install(&self, config: &Config) -> Result<(), service::Error> {
+    async fn install(&self, config: &Config) -> Result<InstallReport, service::Error> {
+        let mut report = InstallReport::new(config.strictness.unwrap_or_default());
+
+        for group in config.groups() {
+            if let Err(e) = self.add_group(group).await {
+                report.add_failure(&group.group_name, e);
+            }
+        }
         if let Some(first_user) = &config.first_user {
-            self.add_first_user(first_user).await;
+            self.add_first_user(first_user, &mut report).await;
+        }
+        let groups = config.groups.clone().unwrap_or_default();
+        for user in config.users() {
+            self.add_user(user, &groups, &mut report).await;
         }
         if let Some(root_user) = &config.root {
-            self.add_root_user(root_user).await;
+            self.add_root_user(root_user, &mut report).await;
+        }
+        for sudoers in config.sudoers() {
+            if let Err(e) = self.add_sudoers(sudoers).await {
+                report.add_failure(&sudoers.name, e);
+            }
         }
 
-        Ok(())
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agama_utils::api::users::config::Strictness;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_install_with_failing_commands() {
+        // the commands cannot run in an empty directory, so all of them fail
+        let install_dir = TempDir::new().unwrap();
+        let model = Model::new(install_dir.path());
+        let config = Config {
+            root: Some(RootUserConfig {
+                password: Some(UserPassword {
+                    password: "n0ts3cr3t".to_string(),
+                    hashed_password: false,
+                }),
+                ..Default::default()
+            }),
+            sudoers: Some(vec![SudoersConfig {
+                name: "admin".to_string(),
+                rules: vec!["admin ALL=(ALL) ALL".to_string()],
+            }]),
+            strictness: Some(Strictness::Lenient),
+            ..Default::default()
+        };
+
+        let report = model.install(&config).await.unwrap();
+        let subjects: Vec<_> = report.failures.iter().map(|f| f.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["root", "admin"]);
+        assert!(report.logins.is_empty());
+        assert!(!report.is_fatal());
+
+        // the sudoers file could not be validated, so it is removed
+        assert!(!install_dir.path().join("etc/sudoers.d/admin").exists());
+
+        let config = Config {
+            strictness: Some(Strictness::Login),
+            ..config
+        };
+        let report = model.install(&config).await.unwrap();
+        assert!(report.is_fatal());
     }
 }
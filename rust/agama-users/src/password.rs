@@ -20,99 +20,357 @@
 
 //! Implements a mechanism to check for the strength of a password.
 //!
-//! It relies on the pwscore tool included in the libpwquality-tools package.
-//!
-//! Ideally, it should use [libpwquality bindings](https://crates.io/crates/libpwquality).
-//! However, adding this crate causes pam-sys 0.8.0 to not build. See
-//! https://github.com/1wilkens/pam-sys/issues/29 for further information.
+//! The passwords are checked against a [PasswordPolicy], which can be defined by the product or
+//! in the profile. Additionally, it rejects too simplistic passwords (e.g., "12345678" or
+//! "aaaaaaaa") and computes a score that gives an idea of the password strength.
 
+use agama_utils::api::users::config::PasswordPolicy;
+use gettextrs::gettext;
 use schemars::JsonSchema;
 use serde::Serialize;
 use std::{
-    io::Write,
-    process::{Command, Stdio},
+    collections::HashSet,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    sync::Arc,
 };
 
 #[derive(thiserror::Error, Debug)]
 pub enum PasswordCheckerError {
-    #[error("Could not run pwscore")]
-    CommandFailed(#[from] std::io::Error),
-    #[error("It was not possible to connect to pwscore")]
-    CommunicationFailed,
-    #[error("Could not parse the pwscore ")]
-    ParseError(String),
+    #[error("Could not read the dictionary {0}: {1}")]
+    Dictionary(String, #[source] Arc<io::Error>),
 }
 
 /// Password check result.
 ///
 /// * If the check passes, it returns the score (a number from 0 to 100).
 /// * If it does not passes, it returns the reason (e.g., it is based on a dictionary word).
-#[derive(Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum PasswordCheckResult {
     Success(i32),
     Failure(String),
 }
 
+/// Minimum length of the dictionary words to consider.
+const MIN_WORD_LENGTH: usize = 4;
+
+/// Word lists to use when the policy does not define a dictionary, in order of preference.
+const SYSTEM_DICTIONARIES: [&str; 2] = [
+    "/usr/share/dict/words",
+    "/usr/share/cracklib/cracklib-small",
+];
+
 /// Allow to check the strength of passwords.
 #[derive(Default)]
-pub struct PasswordChecker;
+pub struct PasswordChecker {
+    policy: PasswordPolicy,
+    dictionary: Option<Dictionary>,
+}
 
 impl PasswordChecker {
+    /// Creates a checker for the given policy.
+    ///
+    /// If the policy does not define a dictionary, it uses the system word list, if any.
+    ///
+    /// * `policy`: rules that the passwords must fulfill.
+    pub fn new(policy: PasswordPolicy) -> Self {
+        Self::with_system_dictionaries(policy, &SYSTEM_DICTIONARIES)
+    }
+
+    /// Creates a checker for the given policy, using the first existing system word list if the
+    /// policy does not define a dictionary.
+    ///
+    /// The dictionary is read only once, when creating the checker.
+    ///
+    /// * `policy`: rules that the passwords must fulfill.
+    /// * `system_dictionaries`: paths of the system word lists, in order of preference.
+    pub fn with_system_dictionaries(policy: PasswordPolicy, system_dictionaries: &[&str]) -> Self {
+        let path = policy.dictionary.clone().or_else(|| {
+            system_dictionaries
+                .iter()
+                .find(|p| Path::new(p).exists())
+                .map(ToString::to_string)
+        });
+        let dictionary = path.map(|p| Dictionary::read(&p));
+        Self { policy, dictionary }
+    }
+
+    /// Rules that the passwords must fulfill.
+    pub fn policy(&self) -> &PasswordPolicy {
+        &self.policy
+    }
+
     /// Check the strength of the given password.
     ///
     /// It returns a PasswordCheckResult struct which includes result of the check.
     ///
     /// * `password`: clear-text password.
-    pub fn check(&self, password: &str) -> Result<PasswordCheckResult, PasswordCheckerError> {
-        let child = Command::new("pwscore")
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn();
-
-        let mut child = child?;
-        let Some(mut stdin) = child.stdin.take() else {
-            return Err(PasswordCheckerError::CommunicationFailed);
-        };
+    /// * `user_name`: name of the user the password belongs to, if known.
+    pub fn check(
+        &self,
+        password: &str,
+        user_name: Option<&str>,
+    ) -> Result<PasswordCheckResult, PasswordCheckerError> {
+        let mut errors = vec![];
+
+        let length = password.chars().count();
+        let min_length = self.policy.min_length();
+        if length < min_length {
+            errors.push(
+                gettext("The password is shorter than %d characters")
+                    .replace("%d", &min_length.to_string()),
+            );
+        }
+
+        let classes = char_classes(password);
+        let min_classes = self.policy.min_classes();
+        if classes < min_classes {
+            errors.push(
+                gettext("The password contains less than %d character classes")
+                    .replace("%d", &min_classes.to_string()),
+            );
+        }
 
-        stdin.write_all(password.as_bytes())?;
-        drop(stdin);
+        if is_simplistic(password) {
+            errors.push(gettext("The password is too simplistic/systematic"));
+        }
 
-        let output = child.wait_with_output()?;
+        if let Some(user_name) = user_name.filter(|n| !n.is_empty()) {
+            if self.policy.forbid_user_name()
+                && password.to_lowercase().contains(&user_name.to_lowercase())
+            {
+                errors.push(gettext("The password contains the user name"));
+            }
+        }
 
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let score = stdout
-                .trim()
-                .parse::<i32>()
-                .map_err(|_| PasswordCheckerError::ParseError(stdout.to_string()))?;
-            Ok(PasswordCheckResult::Success(score))
+        if let Some(dictionary) = &self.dictionary {
+            if dictionary.is_based_on_word(password)? {
+                errors.push(gettext("The password is based on a dictionary word"));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(PasswordCheckResult::Success(score(length, classes)))
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let lines = stderr.lines().skip(1);
-            let error: Vec<_> = lines.map(|l| l.trim().to_string()).collect();
-            Ok(PasswordCheckResult::Failure(error.join("\n")))
+            Ok(PasswordCheckResult::Failure(errors.join("\n")))
         }
     }
 }
 
+/// Returns the number of character classes (lowercase, uppercase, digits and others) used in the
+/// password.
+fn char_classes(password: &str) -> usize {
+    let has = |check: fn(char) -> bool| password.chars().any(check);
+    [
+        has(char::is_lowercase),
+        has(char::is_uppercase),
+        has(|c| c.is_ascii_digit()),
+        has(|c| !c.is_lowercase() && !c.is_uppercase() && !c.is_ascii_digit()),
+    ]
+    .into_iter()
+    .filter(|found| *found)
+    .count()
+}
+
+/// Whether the password is a sequence of repeated, ascending or descending characters.
+fn is_simplistic(password: &str) -> bool {
+    let chars: Vec<u32> = password.chars().map(u32::from).collect();
+    if chars.len() < 2 {
+        return false;
+    }
+
+    let steps: Vec<i64> = chars
+        .windows(2)
+        .map(|w| i64::from(w[1]) - i64::from(w[0]))
+        .collect();
+    let step = steps[0];
+    step.abs() <= 1 && steps.iter().all(|s| *s == step)
+}
+
+/// Words the passwords cannot be based on.
+struct Dictionary {
+    path: String,
+    words: Result<HashSet<String>, Arc<io::Error>>,
+}
+
+impl Dictionary {
+    /// Reads the words from the given file, one per line.
+    ///
+    /// The error, if any, is kept to report it when checking a password.
+    ///
+    /// * `path`: path of the word list.
+    fn read(path: &str) -> Self {
+        let words = Self::read_words(path).map_err(Arc::new);
+        if let Err(error) = &words {
+            tracing::warn!("Could not read the dictionary {path}: {error}");
+        }
+        Self {
+            path: path.to_string(),
+            words,
+        }
+    }
+
+    fn read_words(path: &str) -> io::Result<HashSet<String>> {
+        let mut words = HashSet::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let word = line?.trim().to_lowercase();
+            if word.chars().count() >= MIN_WORD_LENGTH {
+                words.insert(word);
+            }
+        }
+        Ok(words)
+    }
+
+    /// Whether the password is based on a word from the dictionary.
+    ///
+    /// It considers the password as based on a word if, ignoring the case and the leading and
+    /// trailing digits and symbols, it matches a word from the dictionary.
+    fn is_based_on_word(&self, password: &str) -> Result<bool, PasswordCheckerError> {
+        let words = self
+            .words
+            .as_ref()
+            .map_err(|e| PasswordCheckerError::Dictionary(self.path.clone(), Arc::clone(e)))?;
+        let password = password.to_lowercase();
+        let base = password.trim_matches(|c: char| !c.is_alphabetic());
+        Ok(words.contains(&password) || words.contains(base))
+    }
+}
+
+/// Computes a score (from 0 to 100) for a password that fulfills the policy.
+fn score(length: usize, classes: usize) -> i32 {
+    let score = length * 5 + classes.saturating_sub(1) * 10;
+    score.min(100) as i32
+}
+
 #[cfg(test)]
 mod test {
     use super::{PasswordCheckResult, PasswordChecker};
+    use agama_utils::api::users::config::PasswordPolicy;
+    use std::io::Write;
 
     #[test]
-    #[cfg(not(ci))]
     fn test_passwords() {
-        let checker = PasswordChecker;
+        let checker = PasswordChecker::default();
 
-        let result = checker.check("nots3cr3t.").unwrap();
+        let result = checker.check("nots3cr3t.", None).unwrap();
         assert!(matches!(result, PasswordCheckResult::Success(_)));
 
-        let result = checker.check("12345678").unwrap();
+        let result = checker.check("12345678", None).unwrap();
         assert!(matches!(result, PasswordCheckResult::Failure(_)));
 
-        let result = checker.check("ab").unwrap();
+        let result = checker.check("ab", None).unwrap();
         assert!(matches!(result, PasswordCheckResult::Failure(_)));
     }
+
+    #[test]
+    fn test_policy() {
+        let policy = PasswordPolicy {
+            min_length: Some(10),
+            min_classes: Some(3),
+            ..Default::default()
+        };
+        let checker = PasswordChecker::new(policy);
+
+        let result = checker.check("nots3cr3t.", None).unwrap();
+        assert_eq!(result, PasswordCheckResult::Success(70));
+
+        let result = checker.check("nots3cr3t", None).unwrap();
+        assert_eq!(
+            result,
+            PasswordCheckResult::Failure(
+                "The password is shorter than 10 characters\n\
+                 The password contains less than 3 character classes"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_user_name() {
+        let checker = PasswordChecker::default();
+
+        let result = checker.check("Jane.2026", Some("jane")).unwrap();
+        assert_eq!(
+            result,
+            PasswordCheckResult::Failure("The password contains the user name".to_string())
+        );
+
+        let result = checker.check("Jane.2026", Some("john")).unwrap();
+        assert!(matches!(result, PasswordCheckResult::Success(_)));
+
+        let checker = PasswordChecker::with_system_dictionaries(
+            PasswordPolicy {
+                forbid_user_name: Some(false),
+                ..Default::default()
+            },
+            &[],
+        );
+        let result = checker.check("Jane.2026", Some("jane")).unwrap();
+        assert!(matches!(result, PasswordCheckResult::Success(_)));
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let tmp_dir = tempfile::TempDir::with_prefix("agama-tests-").unwrap();
+        let path = tmp_dir.path().join("words");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "dragon\nsunshine\nab").unwrap();
+
+        let checker = PasswordChecker::new(PasswordPolicy {
+            dictionary: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        });
+
+        let result = checker.check("Sunshine2026!", None).unwrap();
+        assert_eq!(
+            result,
+            PasswordCheckResult::Failure("The password is based on a dictionary word".to_string())
+        );
+
+        let result = checker.check("sun-shine-2026", None).unwrap();
+        assert!(matches!(result, PasswordCheckResult::Success(_)));
+
+        let checker = PasswordChecker::new(PasswordPolicy {
+            dictionary: Some(tmp_dir.path().join("missing").to_string_lossy().to_string()),
+            ..Default::default()
+        });
+        assert!(checker.check("Sunshine2026!", None).is_err());
+    }
+
+    #[test]
+    fn test_system_dictionary() {
+        let tmp_dir = tempfile::TempDir::with_prefix("agama-tests-").unwrap();
+        let path = tmp_dir.path().join("words");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "dragon\npassword").unwrap();
+        let missing = tmp_dir.path().join("missing");
+        let dictionaries = [missing.to_str().unwrap(), path.to_str().unwrap()];
+
+        let checker =
+            PasswordChecker::with_system_dictionaries(PasswordPolicy::default(), &dictionaries);
+        for password in ["password", "Password1"] {
+            let result = checker.check(password, None).unwrap();
+            assert_eq!(
+                result,
+                PasswordCheckResult::Failure(
+                    "The password is based on a dictionary word".to_string()
+                )
+            );
+        }
+
+        // the dictionary from the policy takes precedence
+        let other = tmp_dir.path().join("other");
+        std::fs::File::create(&other).unwrap();
+        let checker = PasswordChecker::with_system_dictionaries(
+            PasswordPolicy {
+                dictionary: Some(other.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            &dictionaries,
+        );
+        let result = checker.check("Password1", None).unwrap();
+        assert!(matches!(result, PasswordCheckResult::Success(_)));
+    }
 }
@@ -0,0 +1,138 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Summary of the problems found when writing the users configuration.
+
+use agama_utils::api::users::config::Strictness;
+use std::fmt;
+
+/// Problem found when writing part of the users configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstallFailure {
+    /// Account, group or file affected by the failure.
+    pub subject: String,
+    /// Error description.
+    pub error: String,
+}
+
+impl InstallFailure {
+    pub fn new(subject: &str, error: impl ToString) -> Self {
+        Self {
+            subject: subject.to_string(),
+            error: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for InstallFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.subject, self.error)
+    }
+}
+
+/// Result of writing the users configuration to the installed system.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstallReport {
+    /// How to handle the failures.
+    pub strictness: Strictness,
+    /// Failures found when writing the configuration.
+    pub failures: Vec<InstallFailure>,
+    /// Accounts which can be used to log into the installed system.
+    pub logins: Vec<String>,
+}
+
+impl InstallReport {
+    pub fn new(strictness: Strictness) -> Self {
+        Self {
+            strictness,
+            ..Default::default()
+        }
+    }
+
+    /// Registers a failure, logging it.
+    ///
+    /// * `subject`: account, group or file affected by the failure.
+    /// * `error`: error description.
+    pub fn add_failure(&mut self, subject: &str, error: impl ToString) {
+        let failure = InstallFailure::new(subject, error);
+        tracing::error!("Failed to write the users configuration: {failure}");
+        self.failures.push(failure);
+    }
+
+    /// Registers an account which can be used to log into the system.
+    pub fn add_login(&mut self, account: &str) {
+        if !self.logins.iter().any(|l| l == account) {
+            self.logins.push(account.to_string());
+        }
+    }
+
+    /// Whether the installation should fail according to the strictness.
+    pub fn is_fatal(&self) -> bool {
+        match self.strictness {
+            Strictness::Lenient => false,
+            Strictness::Login => self.logins.is_empty(),
+            Strictness::Strict => !self.failures.is_empty() || self.logins.is_empty(),
+        }
+    }
+
+    /// Returns a human-readable summary of the failures.
+    pub fn summary(&self) -> String {
+        let mut lines: Vec<_> = self.failures.iter().map(ToString::to_string).collect();
+        if self.logins.is_empty() {
+            lines.push("no account can be used to log into the system".to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fatal() {
+        let mut report = InstallReport::new(Strictness::Login);
+        assert!(report.is_fatal());
+
+        report.add_login("root");
+        report.add_failure("jane", "useradd failed");
+        assert!(!report.is_fatal());
+
+        report.strictness = Strictness::Strict;
+        assert!(report.is_fatal());
+
+        report.strictness = Strictness::Lenient;
+        report.logins.clear();
+        assert!(!report.is_fatal());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut report = InstallReport::new(Strictness::Login);
+        report.add_failure("jane", "useradd failed");
+        assert_eq!(
+            report.summary(),
+            "jane: useradd failed\nno account can be used to log into the system"
+        );
+
+        report.add_login("jane");
+        assert_eq!(report.summary(), "jane: useradd failed");
+    }
+}
@@ -19,15 +19,18 @@
 // find current contact information at www.suse.com.
 
 use crate::model::ModelAdapter;
-use crate::{message, PasswordCheckResult, PasswordChecker};
-use crate::{Model, PasswordCheckerError};
+use crate::validation::WeakPassword;
+use crate::{
+    hash, message, validation, InstallFailure, InstallReport, PasswordCheckResult, PasswordChecker,
+};
+use crate::{HashError, Model, PasswordCheckerError};
 use agama_utils::api::access;
 use agama_utils::{
     actor::{self, Actor, Handler, MessageHandler},
     api::{
         self,
         event::{self, Event},
-        users::Config,
+        users::{config::PasswordPolicy, Config},
         Issue, Scope,
     },
     issue,
@@ -56,6 +59,10 @@ pub enum Error {
     Actor(#[from] actor::Error),
     #[error(transparent)]
     PasswordChecker(#[from] PasswordCheckerError),
+    #[error(transparent)]
+    Hash(#[from] HashError),
+    #[error("Could not write the users configuration:\n{0}")]
+    InstallFailed(String),
 }
 
 /// Builds and spawns the users service.
@@ -96,6 +103,10 @@ impl Starter {
         };
         let service = Service {
             full_config: Config::new(),
+            product_policy: None,
+            checker: PasswordChecker::new(PasswordPolicy::default()),
+            install_failures: vec![],
+            weak_passwords: vec![],
             model,
             issues: self.issues,
             events: self.events,
@@ -110,8 +121,16 @@ impl Starter {
 
 /// Users service.
 pub struct Service {
-    // complete users config
+    // complete users config (passwords are always hashed)
     full_config: Config,
+    // password policy defined by the product (kept apart from the user configuration)
+    product_policy: Option<PasswordPolicy>,
+    // checker for the password policy in use
+    checker: PasswordChecker,
+    // passwords which do not fulfill the policy (checked before hashing them)
+    weak_passwords: Vec<WeakPassword>,
+    // failures found when writing the configuration
+    install_failures: Vec<InstallFailure>,
     // service's backend which gets data from real world
     model: Box<dyn ModelAdapter + Send + Sync + 'static>,
     // infrastructure stuff
@@ -133,6 +152,16 @@ impl Service {
         self.update_issues()
     }
 
+    /// Replaces the password checker if the policy changed, so the dictionary is read only once
+    /// per policy.
+    ///
+    /// * `policy`: password policy to use.
+    fn update_checker(&mut self, policy: PasswordPolicy) {
+        if self.checker.policy() != &policy {
+            self.checker = PasswordChecker::new(policy);
+        }
+    }
+
     fn get_proposal(&self) -> Option<api::users::Config> {
         if !self.full_config.is_empty() {
             return self.full_config.to_api();
@@ -161,6 +190,13 @@ impl Service {
             return true;
         }
 
+        if config
+            .users()
+            .any(|u| u.ssh_public_keys.as_ref().is_some_and(|k| !k.is_empty()))
+        {
+            return true;
+        }
+
         false
     }
 
@@ -192,6 +228,24 @@ impl Service {
             ));
         }
 
+        issues.extend(validation::validate(&self.full_config));
+        issues.extend(self.weak_passwords.iter().map(WeakPassword::to_issue));
+
+        if !self.install_failures.is_empty() {
+            let details: Vec<_> = self
+                .install_failures
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            issues.push(
+                Issue::new(
+                    "users.install_failed",
+                    &gettext("Some parts of the users configuration could not be written"),
+                )
+                .with_details(&details.join("\n")),
+            );
+        }
+
         self.issues
             .cast(issue::message::Set::new(Scope::Users, issues))?;
         Ok(())
@@ -212,7 +266,7 @@ impl Actor for Service {
 #[async_trait]
 impl MessageHandler<message::GetConfig> for Service {
     async fn handle(&mut self, _message: message::GetConfig) -> Result<api::users::Config, Error> {
-        Ok(self.full_config.clone())
+        Ok(self.full_config.redacted())
     }
 }
 
@@ -222,20 +276,40 @@ impl MessageHandler<message::SetConfig<api::users::Config>> for Service {
         &mut self,
         message: message::SetConfig<api::users::Config>,
     ) -> Result<(), Error> {
-        let mut base_config = Config::new();
-
-        let config = if let Some(config) = &message.config {
-            base_config = config.clone();
-            base_config
-        } else {
-            base_config
-        };
-
-        if config == self.full_config {
+        let mut config = message.config.clone().unwrap_or_default();
+
+        // The passwords must be checked before hashing them. The redacted ones were not changed,
+        // so the result of the previous check is kept.
+        let redacted: Vec<_> = config
+            .passwords_mut()
+            .into_iter()
+            .filter(|(_, p)| p.is_redacted())
+            .map(|(name, _)| name)
+            .collect();
+        let policy = config
+            .password_policy
+            .clone()
+            .or_else(|| message.product_policy.clone());
+        self.update_checker(policy.clone().unwrap_or_default());
+        let mut weak_passwords =
+            validation::check_passwords(&config, policy.as_ref().map(|_| &self.checker));
+        weak_passwords.extend(
+            self.weak_passwords
+                .iter()
+                .filter(|w| redacted.contains(&w.account))
+                .cloned(),
+        );
+
+        config.restore_redacted(&self.full_config);
+        hash::hash_passwords(&mut config, &self.full_config).await?;
+
+        if config == self.full_config && message.product_policy == self.product_policy {
             return Ok(());
         }
 
         self.full_config = config;
+        self.product_policy = message.product_policy;
+        self.weak_passwords = weak_passwords;
 
         let mut remote_config = access::Config::default();
         if self.need_ssh_access() {
@@ -270,22 +344,31 @@ impl MessageHandler<message::GetProposal> for Service {
         &mut self,
         _message: message::GetProposal,
     ) -> Result<Option<api::users::Config>, Error> {
-        Ok(self.get_proposal())
+        Ok(self.get_proposal().map(|c| c.redacted()))
     }
 }
 
 #[async_trait]
 impl MessageHandler<message::Install> for Service {
-    async fn handle(&mut self, _message: message::Install) -> Result<(), Error> {
-        if let Some(proposal) = self.get_proposal() {
-            if let Err(error) = self.model.install(&proposal).await {
-                tracing::error!("Failed to write users configuration: {error}");
+    async fn handle(&mut self, _message: message::Install) -> Result<InstallReport, Error> {
+        let strictness = self.full_config.strictness.unwrap_or_default();
+        let report = if let Some(proposal) = self.get_proposal() {
+            match self.model.install(&proposal).await {
+                Ok(report) => report,
+                Err(error) => {
+                    let mut report = InstallReport::new(strictness);
+                    report.add_failure("users", error);
+                    report
+                }
             }
         } else {
             tracing::error!("Missing authentication configuration");
+            InstallReport::new(strictness)
         };
 
-        Ok(())
+        self.install_failures = report.failures.clone();
+        self.update_issues()?;
+        Ok(report)
     }
 }
 
@@ -295,8 +378,9 @@ impl MessageHandler<message::CheckPassword> for Service {
         &mut self,
         message: message::CheckPassword,
     ) -> Result<PasswordCheckResult, Error> {
-        let checker = PasswordChecker;
-        Ok(checker.check(&message.password)?)
+        Ok(self
+            .checker
+            .check(&message.password, message.user_name.as_deref())?)
     }
 }
 
@@ -0,0 +1,567 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Validates the users configuration before installing it.
+//!
+//! The problems are reported as issues, so they can be fixed before starting the installation.
+
+use crate::{PasswordCheckResult, PasswordChecker};
+use agama_utils::api::{users::Config, Issue};
+use gettextrs::gettext;
+use std::collections::HashSet;
+
+/// Names of the users which are already defined in the installed system.
+const RESERVED_USER_NAMES: &[&str] = &[
+    "root",
+    "bin",
+    "daemon",
+    "adm",
+    "lp",
+    "sync",
+    "shutdown",
+    "halt",
+    "mail",
+    "news",
+    "uucp",
+    "operator",
+    "games",
+    "man",
+    "ftp",
+    "nobody",
+    "messagebus",
+    "polkitd",
+    "sshd",
+    "chrony",
+    "dnsmasq",
+    "nscd",
+    "rpc",
+    "statd",
+    "tss",
+];
+
+/// Names of the groups which are already defined in the installed system.
+const RESERVED_GROUP_NAMES: &[&str] = &[
+    "root",
+    "bin",
+    "daemon",
+    "sys",
+    "adm",
+    "tty",
+    "disk",
+    "lp",
+    "mem",
+    "kmem",
+    "wheel",
+    "mail",
+    "news",
+    "uucp",
+    "man",
+    "audio",
+    "video",
+    "cdrom",
+    "dialout",
+    "floppy",
+    "tape",
+    "utmp",
+    "shadow",
+    "users",
+    "nobody",
+    "nogroup",
+    "systemd-journal",
+    "trusted",
+    "input",
+    "render",
+    "kvm",
+    "lock",
+];
+
+/// Maximum length of user and group names.
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Validates the users configuration.
+///
+/// It returns the list of issues found in the configuration.
+///
+/// * `config`: users configuration.
+pub fn validate(config: &Config) -> Vec<Issue> {
+    let mut issues = vec![];
+    validate_users(config, &mut issues);
+    validate_groups(config, &mut issues);
+    validate_sudoers(config, &mut issues);
+    validate_redacted_passwords(config, &mut issues);
+    issues
+}
+
+/// Reports the redacted passwords which could not be restored (e.g., the user was renamed), so
+/// they must be set again.
+fn validate_redacted_passwords(config: &Config, issues: &mut Vec<Issue>) {
+    for (name, password) in config.passwords() {
+        if password.is_redacted() {
+            issues.push(name_issue(
+                "users.password_redacted",
+                &gettext("The password of '%s' is unknown, so it must be set again"),
+                &name,
+            ));
+        }
+    }
+}
+
+/// Password that does not fulfill the password policy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeakPassword {
+    /// Account the password belongs to.
+    pub account: String,
+    /// Reasons why the password does not fulfill the policy.
+    pub reason: String,
+}
+
+impl WeakPassword {
+    /// Builds an issue for the weak password.
+    pub fn to_issue(&self) -> Issue {
+        name_issue(
+            "users.weak_password",
+            &gettext("The password of '%s' does not fulfill the password policy"),
+            &self.account,
+        )
+        .with_details(&self.reason)
+    }
+}
+
+fn validate_users(config: &Config, issues: &mut Vec<Issue>) {
+    let mut names: HashSet<&str> = HashSet::new();
+    if let Some(name) = config
+        .first_user
+        .as_ref()
+        .and_then(|u| u.user_name.as_ref())
+    {
+        names.insert(name);
+    }
+    let mut uids = HashSet::new();
+
+    for user in config.users() {
+        let name = user.user_name.as_str();
+        if !is_valid_name(name) {
+            issues.push(name_issue(
+                "users.invalid_user_name",
+                &gettext("Invalid user name '%s'"),
+                name,
+            ));
+        } else if RESERVED_USER_NAMES.contains(&name) {
+            issues.push(name_issue(
+                "users.reserved_user_name",
+                &gettext("The user name '%s' is reserved"),
+                name,
+            ));
+        } else if !names.insert(name) {
+            issues.push(name_issue(
+                "users.duplicated_user",
+                &gettext("The user '%s' is defined more than once"),
+                name,
+            ));
+        }
+
+        if let Some(uid) = user.uid {
+            if uid == 0 {
+                issues.push(name_issue(
+                    "users.reserved_uid",
+                    &gettext("The user '%s' cannot use the UID 0"),
+                    name,
+                ));
+            } else if !uids.insert(uid) {
+                issues.push(name_issue(
+                    "users.duplicated_uid",
+                    &gettext("The UID %s is used by more than one user"),
+                    &uid.to_string(),
+                ));
+            }
+        }
+
+        for (path, message) in [
+            (
+                &user.home,
+                gettext("The home directory of '%s' is not an absolute path"),
+            ),
+            (
+                &user.shell,
+                gettext("The shell of '%s' is not an absolute path"),
+            ),
+        ] {
+            if path.as_ref().is_some_and(|p| !p.starts_with('/')) {
+                issues.push(name_issue("users.invalid_path", &message, name));
+            }
+        }
+
+        if user
+            .expiration_date
+            .as_ref()
+            .is_some_and(|d| !is_valid_date(d))
+        {
+            issues.push(name_issue(
+                "users.invalid_expiration_date",
+                &gettext("The expiration date of '%s' is not in the YYYY-MM-DD format"),
+                name,
+            ));
+        }
+    }
+}
+
+fn validate_groups(config: &Config, issues: &mut Vec<Issue>) {
+    let mut names = HashSet::new();
+    let mut gids = HashSet::new();
+
+    for group in config.groups() {
+        let name = group.group_name.as_str();
+        if !is_valid_name(name) {
+            issues.push(name_issue(
+                "users.invalid_group_name",
+                &gettext("Invalid group name '%s'"),
+                name,
+            ));
+        } else if RESERVED_GROUP_NAMES.contains(&name) {
+            issues.push(name_issue(
+                "users.reserved_group_name",
+                &gettext("The group name '%s' is reserved"),
+                name,
+            ));
+        } else if !names.insert(name) {
+            issues.push(name_issue(
+                "users.duplicated_group",
+                &gettext("The group '%s' is defined more than once"),
+                name,
+            ));
+        }
+
+        if let Some(gid) = group.gid {
+            if gid == 0 {
+                issues.push(name_issue(
+                    "users.reserved_gid",
+                    &gettext("The group '%s' cannot use the GID 0"),
+                    name,
+                ));
+            } else if !gids.insert(gid) {
+                issues.push(name_issue(
+                    "users.duplicated_gid",
+                    &gettext("The GID %s is used by more than one group"),
+                    &gid.to_string(),
+                ));
+            }
+        }
+    }
+}
+
+fn validate_sudoers(config: &Config, issues: &mut Vec<Issue>) {
+    let mut names = HashSet::new();
+
+    for sudoers in config.sudoers() {
+        let name = sudoers.name.as_str();
+        // sudo ignores the files containing a "." or ending with "~".
+        let is_valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid {
+            issues.push(name_issue(
+                "users.invalid_sudoers_name",
+                &gettext("Invalid sudoers file name '%s'"),
+                name,
+            ));
+        } else if !names.insert(name) {
+            issues.push(name_issue(
+                "users.duplicated_sudoers",
+                &gettext("The sudoers file '%s' is defined more than once"),
+                name,
+            ));
+        }
+
+        if sudoers.rules.iter().any(|r| r.contains('\n')) {
+            issues.push(name_issue(
+                "users.invalid_sudoers_rule",
+                &gettext("The sudoers file '%s' contains a multi-line rule"),
+                name,
+            ));
+        }
+    }
+}
+
+/// Checks the plain text passwords against the password policy, if any.
+///
+/// The hashed passwords cannot be checked, so they must be checked before hashing them.
+///
+/// * `config`: users configuration.
+/// * `checker`: checker for the password policy, if there is a policy.
+pub fn check_passwords(config: &Config, checker: Option<&PasswordChecker>) -> Vec<WeakPassword> {
+    let mut weak_passwords = vec![];
+    let Some(checker) = checker else {
+        return weak_passwords;
+    };
+
+    for (name, password) in config.passwords() {
+        // the first user might not have a name yet
+        if name.is_empty() || password.hashed_password || password.is_empty() {
+            continue;
+        }
+
+        match checker.check(&password.password, Some(&name)) {
+            Ok(PasswordCheckResult::Success(_)) => {}
+            Ok(PasswordCheckResult::Failure(reason)) => weak_passwords.push(WeakPassword {
+                account: name,
+                reason,
+            }),
+            Err(error) => {
+                tracing::warn!("Could not check the password of {name}: {error}");
+            }
+        }
+    }
+
+    weak_passwords
+}
+
+/// Builds an issue replacing the "%s" placeholder in the description.
+fn name_issue(class: &str, description: &str, name: &str) -> Issue {
+    Issue::new(class, &description.replace("%s", name))
+}
+
+/// Whether the name is a valid user or group name.
+///
+/// It follows the rules of the shadow utilities: it starts with a lowercase letter or an
+/// underscore, followed by lowercase letters, digits, underscores or dashes.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    name.len() <= MAX_NAME_LENGTH
+        && (first.is_ascii_lowercase() || first == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// Whether the date is in the YYYY-MM-DD format.
+fn is_valid_date(date: &str) -> bool {
+    let parts: Vec<_> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+
+    let is_number = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+    if !is_number(year, 4) || !is_number(month, 2) || !is_number(day, 2) {
+        return false;
+    }
+
+    matches!(month.parse::<u8>(), Ok(1..=12)) && matches!(day.parse::<u8>(), Ok(1..=31))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agama_utils::api::users::config::{
+        FirstUserConfig, GroupConfig, PasswordPolicy, RootUserConfig, SudoersConfig, UserConfig,
+        UserPassword,
+    };
+
+    fn user(name: &str, uid: Option<u32>) -> UserConfig {
+        UserConfig {
+            user_name: name.to_string(),
+            uid,
+            ..Default::default()
+        }
+    }
+
+    fn group(name: &str, gid: Option<u32>) -> GroupConfig {
+        GroupConfig {
+            group_name: name.to_string(),
+            gid,
+            ..Default::default()
+        }
+    }
+
+    fn classes(issues: &[Issue]) -> Vec<&str> {
+        issues.iter().map(|i| i.class.as_str()).collect()
+    }
+
+    #[test]
+    fn test_validate_valid_config() {
+        let config = Config {
+            users: Some(vec![user("backup", Some(900)), user("admin", None)]),
+            groups: Some(vec![group("backup", Some(900))]),
+            sudoers: Some(vec![SudoersConfig {
+                name: "admin".to_string(),
+                rules: vec!["admin ALL=(ALL) ALL".to_string()],
+            }]),
+            ..Default::default()
+        };
+        assert!(validate(&config).is_empty());
+    }
+
+    #[test]
+    fn test_validate_users() {
+        let mut with_paths = user("jane", Some(1001));
+        with_paths.home = Some("home/jane".to_string());
+        with_paths.expiration_date = Some("2026-13-01".to_string());
+
+        let config = Config {
+            first_user: Some(FirstUserConfig {
+                user_name: Some("admin".to_string()),
+                ..Default::default()
+            }),
+            users: Some(vec![
+                user("admin", None),
+                user("root", None),
+                user("Bad Name", None),
+                user("john", Some(1001)),
+                user("zero", Some(0)),
+                with_paths,
+            ]),
+            ..Default::default()
+        };
+        let issues = validate(&config);
+        assert_eq!(
+            classes(&issues),
+            vec![
+                "users.duplicated_user",
+                "users.reserved_user_name",
+                "users.invalid_user_name",
+                "users.reserved_uid",
+                "users.duplicated_uid",
+                "users.invalid_path",
+                "users.invalid_expiration_date",
+            ]
+        );
+        assert_eq!(
+            issues[0].description,
+            "The user 'admin' is defined more than once"
+        );
+    }
+
+    #[test]
+    fn test_validate_groups() {
+        let config = Config {
+            groups: Some(vec![
+                group("admins", Some(1000)),
+                group("admins", None),
+                group("wheel", None),
+                group("operators", Some(1000)),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            classes(&validate(&config)),
+            vec![
+                "users.duplicated_group",
+                "users.reserved_group_name",
+                "users.duplicated_gid",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_sudoers() {
+        let config = Config {
+            sudoers: Some(vec![
+                SudoersConfig {
+                    name: "admin.conf".to_string(),
+                    rules: vec![],
+                },
+                SudoersConfig {
+                    name: "ops".to_string(),
+                    rules: vec!["ops ALL=(ALL) ALL\nDefaults !env_reset".to_string()],
+                },
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            classes(&validate(&config)),
+            vec!["users.invalid_sudoers_name", "users.invalid_sudoers_rule"]
+        );
+    }
+
+    #[test]
+    fn test_validate_passwords() {
+        let password = |password: &str, hashed_password: bool| UserPassword {
+            password: password.to_string(),
+            hashed_password,
+        };
+
+        let mut jane = user("jane", None);
+        jane.password = Some(password("jane2026", false));
+        let mut john = user("john", None);
+        john.password = Some(password("$6$short", true));
+
+        let config = Config {
+            root: Some(RootUserConfig {
+                password: Some(password("linux", false)),
+                ..Default::default()
+            }),
+            users: Some(vec![jane, john]),
+            ..Default::default()
+        };
+        // passwords are not checked without a policy
+        assert!(check_passwords(&config, None).is_empty());
+
+        let checker = PasswordChecker::with_system_dictionaries(PasswordPolicy::default(), &[]);
+        let issues: Vec<_> = check_passwords(&config, Some(&checker))
+            .iter()
+            .map(WeakPassword::to_issue)
+            .collect();
+        assert_eq!(
+            classes(&issues),
+            vec!["users.weak_password", "users.weak_password"]
+        );
+        assert_eq!(
+            issues[0].description,
+            "The password of 'jane' does not fulfill the password policy"
+        );
+        assert_eq!(
+            issues[0].details.as_deref(),
+            Some("The password contains the user name")
+        );
+        assert_eq!(
+            issues[1].description,
+            "The password of 'root' does not fulfill the password policy"
+        );
+    }
+
+    #[test]
+    fn test_validate_redacted_passwords() {
+        let mut jane = user("jane", None);
+        jane.password = Some(UserPassword::redacted());
+        let mut admin = user("admin", None);
+        admin.password = Some(UserPassword::hashed("$6$admin"));
+        let config = Config {
+            users: Some(vec![jane, admin]),
+            ..Default::default()
+        };
+
+        let issues = validate(&config);
+        assert_eq!(classes(&issues), vec!["users.password_redacted"]);
+        assert_eq!(
+            issues[0].description,
+            "The password of 'jane' is unknown, so it must be set again"
+        );
+    }
+
+    #[test]
+    fn test_is_valid_date() {
+        assert!(is_valid_date("2026-12-31"));
+        assert!(!is_valid_date("2026-12-32"));
+        assert!(!is_valid_date("26-12-31"));
+        assert!(!is_valid_date("2026/12/31"));
+    }
+}
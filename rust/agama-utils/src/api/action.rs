@@ -26,6 +26,7 @@ use crate::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub enum Action {
@@ -41,6 +42,20 @@ pub enum Action {
     Probe { only: Option<Vec<Scope>> },
     #[serde(rename = "configureL10n")]
     ConfigureL10n(l10n::SystemConfig),
+    /// Deregisters the system, including the add-ons, and removes the registration settings.
+    #[serde(rename = "deregister")]
+    Deregister,
+    /// Deregisters the given add-on and removes it from the registration settings.
+    #[serde(rename = "deregisterAddon")]
+    DeregisterAddon { id: String },
+    /// Registers the system again using another registration server or code.
+    ///
+    /// If `url` is not given, the default server (SCC) is used.
+    #[serde(rename = "switchRegistrationServer")]
+    SwitchRegistrationServer {
+        url: Option<Url>,
+        code: Option<String>,
+    },
     #[serde(rename = "install")]
     Install,
     #[serde(rename = "finish")]
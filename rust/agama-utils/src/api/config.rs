@@ -77,6 +77,12 @@ impl Config {
         if let Some(files) = &mut config.files {
             files.resolve_urls(base_uri)?;
         }
+        if let Some(software) = &mut config.software {
+            software.resolve_urls(base_uri)?;
+        }
+        if let Some(network) = &mut config.network {
+            network.resolve_urls(base_uri)?;
+        }
         Ok(config)
     }
 
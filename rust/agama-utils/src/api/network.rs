@@ -23,6 +23,8 @@
 
 mod config;
 pub use config::Config;
+mod diagnostics;
+pub use diagnostics::*;
 mod proposal;
 pub use proposal::Proposal;
 mod settings;
@@ -20,7 +20,11 @@
 
 //! Representation of the network settings
 
-use crate::api::network::{NetworkConnectionsCollection, StateSettings};
+use crate::api::{
+    files::FileSourceError,
+    network::{ImportSettings, NetworkConnectionsCollection, StateSettings},
+};
+use fluent_uri::Uri;
 use merge::Merge;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -39,6 +43,22 @@ pub struct Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[merge(strategy = merge::option::recurse)]
     pub state: Option<StateSettings>,
+    /// Existing network configuration to import
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[merge(strategy = merge::option::overwrite_none)]
+    pub import: Option<ImportSettings>,
+}
+
+impl Config {
+    /// Resolve relative URLs in the network configuration.
+    ///
+    /// * `base_uri`: The base URI to resolve relative URLs against.
+    pub fn resolve_urls(&mut self, base_uri: &Uri<String>) -> Result<(), FileSourceError> {
+        if let Some(import) = &mut self.import {
+            import.resolve_urls(base_uri)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +86,7 @@ mod tests {
                 networking_enabled: Some(true),
                 copy_network: None,
             }),
+            import: None,
         };
 
         // The `original` config to merge from
@@ -97,6 +118,7 @@ mod tests {
                 networking_enabled: Some(false), // This should NOT overwrite updated's true
                 copy_network: Some(true), // This SHOULD overwrite updated's None
             }),
+            import: None,
         };
 
         // Perform the merge
@@ -123,6 +145,7 @@ mod tests {
                 ..Default::default()
             }])),
             state: None,
+            import: None,
         };
         let original1 = Config {
             connections: Some(NetworkConnectionsCollection(vec![NetworkConnection {
@@ -130,6 +153,7 @@ mod tests {
                 ..Default::default()
             }])), // empty vec
             state: None,
+            import: None,
         };
         let original2 = original1.clone();
 
@@ -143,6 +167,7 @@ mod tests {
         let mut updated2 = Config {
             connections: None,
             state: None,
+            import: None,
         };
         updated2.merge(original2);
         assert_eq!(updated2.connections.unwrap().0.len(), 1); // should be empty, as updated was None
@@ -160,11 +185,13 @@ mod tests {
                 connectivity: Some(ConnectivityState::Full),
                 ..Default::default()
             }),
+            import: None,
         };
         let updated_clone = updated.clone();
         let original = Config {
             connections: None,
             state: None,
+            import: None,
         };
 
         updated.merge(original);
@@ -175,6 +202,7 @@ mod tests {
         let mut updated1 = Config {
             connections: None,
             state: None,
+            import: None,
         };
         let original1 = Config {
             connections: Some(NetworkConnectionsCollection(vec![NetworkConnection {
@@ -185,6 +213,7 @@ mod tests {
                 wireless_enabled: Some(false),
                 ..Default::default()
             }),
+            import: None,
         };
         let original1_clone = original1.clone();
 
@@ -196,10 +225,12 @@ mod tests {
         let mut updated2 = Config {
             connections: None,
             state: None,
+            import: None,
         };
         let original2 = Config {
             connections: None,
             state: None,
+            import: None,
         };
         updated2.merge(original2);
         assert_eq!(updated2.connections, None);
@@ -0,0 +1,112 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Representation of the network diagnostics results.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+};
+
+/// Result of diagnosing the access to a URL.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlDiagnostics {
+    /// Diagnosed URL.
+    pub url: String,
+    /// Proxy used to reach the URL (without credentials).
+    pub proxy: Option<String>,
+    /// Whether all the steps succeeded.
+    pub success: bool,
+    /// Error preventing running the diagnostics (e.g., an invalid URL).
+    pub error: Option<String>,
+    /// Results of the steps, in the order they were run.
+    pub steps: Vec<DiagnosticStep>,
+}
+
+impl UrlDiagnostics {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Step of the diagnostics.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticStepKind {
+    /// Name resolution of the host (or the proxy).
+    Dns,
+    /// TCP connection to the host (or the proxy).
+    Tcp,
+    /// Tunnel through the proxy (HTTP CONNECT).
+    Tunnel,
+    /// TLS handshake.
+    Tls,
+    /// HTTP HEAD request.
+    Http,
+}
+
+/// Result of a diagnostics step.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticStep {
+    /// Step kind.
+    pub kind: DiagnosticStepKind,
+    /// Whether the step succeeded.
+    pub success: bool,
+    /// Time spent in the step, in milliseconds.
+    pub duration_ms: u64,
+    /// Error message, if the step failed.
+    pub error: Option<String>,
+    /// Resolved addresses (DNS step).
+    pub addresses: Option<Vec<IpAddr>>,
+    /// Address of the established connection (TCP step).
+    pub address: Option<SocketAddr>,
+    /// Summary of the peer certificate (TLS step).
+    ///
+    /// It is included even when the verification failed, so it is possible to find out,
+    /// for instance, whether a proxy intercepts the connection.
+    pub certificate: Option<HashMap<String, String>>,
+    /// HTTP status code (tunnel and HTTP steps).
+    pub status: Option<u16>,
+}
+
+impl DiagnosticStep {
+    pub fn new(kind: DiagnosticStepKind) -> Self {
+        Self {
+            kind,
+            success: false,
+            duration_ms: 0,
+            error: None,
+            addresses: None,
+            address: None,
+            certificate: None,
+            status: None,
+        }
+    }
+}
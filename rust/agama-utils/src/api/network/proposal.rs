@@ -34,4 +34,7 @@ pub struct Proposal {
     pub connections: NetworkConnectionsCollection,
     /// General network settings
     pub state: StateSettings,
+    /// IDs of the connections to write to the installed system
+    #[serde(default)]
+    pub target_connections: Vec<String>,
 }
@@ -21,10 +21,13 @@
 //! Representation of the network settings
 
 use super::types::{
-    ConnectionState, ConnectivityState, DeviceState, DeviceType, Ipv4Method, Ipv6Method, Status,
+    ConnectionState, ConnectivityState, DeviceState, DeviceType, Ipv4Method, Ipv6Method,
+    Persistence, Status,
 };
+use crate::api::files::{FileSource, FileSourceError};
 use crate::openapi::schemas;
 use cidr::IpInet;
+use fluent_uri::Uri;
 use merge::Merge;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -157,6 +160,113 @@ pub struct BridgeSettings {
     pub ports: Vec<String>,
 }
 
+/// VRF (Virtual Routing and Forwarding) configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VrfSettings {
+    /// Routing table associated to the VRF
+    pub table: u32,
+    /// Interfaces or connections enslaved to the VRF
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub ports: Vec<String>,
+}
+
+/// MACVLAN configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MacvlanSettings {
+    /// Parent interface
+    pub parent: String,
+    /// MACVLAN mode ("vepa", "bridge", "private", "passthru" or "source")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+/// IPVLAN configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IpvlanSettings {
+    /// Parent interface
+    pub parent: String,
+    /// IPVLAN mode ("l2", "l3" or "l3s")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+/// Virtual Ethernet (veth) configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VethSettings {
+    /// Name of the interface at the other end of the pair
+    pub peer: String,
+}
+
+/// Existing network configuration to import
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSettings {
+    /// Format of the configuration
+    pub format: ImportFormat,
+    /// Configuration content or URL
+    #[serde(flatten)]
+    pub source: FileSource,
+}
+
+impl ImportSettings {
+    /// Resolves the URL of the source if it is a relative one.
+    ///
+    /// * `base_uri`: The base URI to resolve relative URLs against.
+    pub fn resolve_urls(&mut self, base_uri: &Uri<String>) -> Result<(), FileSourceError> {
+        self.source = self.source.resolve_url(base_uri)?;
+        Ok(())
+    }
+}
+
+/// Format of a network configuration to import
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportFormat {
+    /// nmstate YAML document
+    Nmstate,
+    /// NetworkManager keyfile or tarball of keyfiles (*.nmconnection)
+    Keyfiles,
+}
+
+/// WireGuard configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WireguardSettings {
+    /// Base64 encoded private key of the local interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    /// UDP port to listen on (a random one is used if it is not set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listen_port: Option<u32>,
+    /// Peers to connect to
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub peers: Vec<WireguardPeerSettings>,
+}
+
+/// WireGuard peer configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WireguardPeerSettings {
+    /// Base64 encoded public key of the peer
+    pub public_key: String,
+    /// Base64 encoded pre-shared key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preshared_key: Option<String>,
+    /// Endpoint of the peer ("host:port")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Networks whose traffic is routed through the peer (e.g., "10.0.0.0/8")
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub allowed_ips: Vec<String>,
+    /// Interval, in seconds, to send keep-alive packets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_keepalive: Option<u32>,
+}
+
 /// VLAN flags controlling behavior
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -338,6 +448,21 @@ pub struct NetworkConnection {
     /// Bridge settings if part of a bridge
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bridge: Option<BridgeSettings>,
+    /// WireGuard settings for the connection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wireguard: Option<WireguardSettings>,
+    /// VRF settings for the connection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vrf: Option<VrfSettings>,
+    /// MACVLAN settings for the connection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub macvlan: Option<MacvlanSettings>,
+    /// IPVLAN settings for the connection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipvlan: Option<IpvlanSettings>,
+    /// Virtual Ethernet (veth) settings for the connection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub veth: Option<VethSettings>,
     /// Custom MAC address of the connection's interface
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_mac_address: Option<String>,
@@ -359,6 +484,10 @@ pub struct NetworkConnection {
     /// Specifies whether the connection should be persisted or not
     #[serde(skip_serializing_if = "Option::is_none")]
     pub persistent: Option<bool>,
+    /// Systems where the connection is used (installer, target or both). It takes precedence
+    /// over `persistent`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistence: Option<Persistence>,
 }
 
 fn is_zero<T: PartialEq + From<u16>>(u: &T) -> bool {
@@ -377,6 +506,16 @@ impl NetworkConnection {
             DeviceType::Bond
         } else if self.bridge.is_some() {
             DeviceType::Bridge
+        } else if self.wireguard.is_some() {
+            DeviceType::Wireguard
+        } else if self.vrf.is_some() {
+            DeviceType::Vrf
+        } else if self.macvlan.is_some() {
+            DeviceType::Macvlan
+        } else if self.ipvlan.is_some() {
+            DeviceType::Ipvlan
+        } else if self.veth.is_some() {
+            DeviceType::Veth
         } else {
             DeviceType::Ethernet
         }
@@ -457,4 +596,31 @@ mod tests {
         let conn2: NetworkConnection = serde_json::from_str(&serialized).unwrap();
         assert_eq!(conn.match_settings, conn2.match_settings);
     }
+
+    #[test]
+    fn test_network_connection_wireguard() {
+        let json = r#"{
+            "id": "wg0",
+            "wireguard": {
+                "privateKey": "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=",
+                "listenPort": 51820,
+                "peers": [
+                    {
+                        "publicKey": "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=",
+                        "endpoint": "mirror.example.net:51820",
+                        "allowedIps": ["10.10.0.0/16"]
+                    }
+                ]
+            }
+        }"#;
+        let conn: NetworkConnection = serde_json::from_str(json).unwrap();
+        assert_eq!(conn.device_type(), DeviceType::Wireguard);
+
+        let wireguard = conn.wireguard.unwrap();
+        assert_eq!(wireguard.listen_port, Some(51820));
+        let peer = wireguard.peers.first().unwrap();
+        assert_eq!(peer.endpoint, Some("mirror.example.net:51820".to_string()));
+        assert_eq!(peer.allowed_ips, vec!["10.10.0.0/16"]);
+        assert_eq!(peer.persistent_keepalive, None);
+    }
 }
@@ -64,6 +64,10 @@ pub struct Device {
     // Connection.id
     pub connection: Option<String>,
     pub state: DeviceState,
+    /// Persistent path of the device (udev's ID_PATH, e.g., "pci-0000:00:19.0").
+    pub path: Option<String>,
+    /// Name of the interface predicted for the installed system.
+    pub target_name: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, JsonSchema)]
@@ -186,6 +190,14 @@ pub struct IpConfig {
     pub routes4: Vec<IpRoute>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub routes6: Vec<IpRoute>,
+    /// Routing table for the IPv4 routes without an explicit table.
+    pub route_table4: Option<u32>,
+    /// Routing table for the IPv6 routes without an explicit table.
+    pub route_table6: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routing_rules4: Vec<RoutingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routing_rules6: Vec<RoutingRule>,
     pub dhcp4_settings: Option<Dhcp4Settings>,
     pub dhcp6_settings: Option<Dhcp6Settings>,
     pub ip6_privacy: Option<i32>,
@@ -380,6 +392,9 @@ pub struct IpRoute {
     pub next_hop: Option<IpAddr>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metric: Option<u32>,
+    /// Routing table to add the route to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<u32>,
 }
 
 impl From<&IpRoute> for HashMap<&str, Value<'_>> {
@@ -397,10 +412,36 @@ impl From<&IpRoute> for HashMap<&str, Value<'_>> {
         if let Some(metric) = route.metric {
             map.insert("metric", Value::new(metric));
         }
+        if let Some(table) = route.table {
+            map.insert("table", Value::new(table));
+        }
         map
     }
 }
 
+/// Policy routing rule.
+///
+/// It sends the traffic matching the given criteria to a routing table.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutingRule {
+    /// Rule priority (rules with lower values are evaluated first).
+    pub priority: u32,
+    /// Source network.
+    #[schemars(with = "Option<String>")]
+    pub from: Option<IpInet>,
+    /// Destination network.
+    #[schemars(with = "Option<String>")]
+    pub to: Option<IpInet>,
+    /// Incoming interface.
+    pub iif: Option<String>,
+    /// Routing table to look up.
+    pub table: u32,
+    /// Firewall mark.
+    pub fwmark: Option<u32>,
+}
+
 use strum::{Display, EnumString, VariantNames};
 
 #[derive(Debug, Error)]
@@ -504,6 +545,11 @@ pub enum DeviceType {
     Bond = 4,
     Vlan = 5,
     Bridge = 6,
+    Wireguard = 7,
+    Vrf = 8,
+    Macvlan = 9,
+    Ipvlan = 10,
+    Veth = 11,
 }
 
 /// Network device state.
@@ -601,6 +647,40 @@ pub enum ConnectivityState {
     Full,
 }
 
+/// Systems where a connection is used.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    JsonSchema,
+)]
+#[strum(serialize_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub enum Persistence {
+    /// The connection is used only during the installation (e.g., a temporary provisioning VLAN).
+    Installer,
+    /// The connection is not activated during the installation, it is only written to the
+    /// installed system.
+    Target,
+    /// The connection is used during the installation and written to the installed system.
+    #[default]
+    Both,
+}
+
+impl Persistence {
+    /// Whether the connection is written to the installed system.
+    pub fn is_persistent(&self) -> bool {
+        *self != Persistence::Installer
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum Status {
@@ -733,6 +813,11 @@ impl TryFrom<u8> for DeviceType {
             4 => Ok(DeviceType::Bond),
             5 => Ok(DeviceType::Vlan),
             6 => Ok(DeviceType::Bridge),
+            7 => Ok(DeviceType::Wireguard),
+            8 => Ok(DeviceType::Vrf),
+            9 => Ok(DeviceType::Macvlan),
+            10 => Ok(DeviceType::Ipvlan),
+            11 => Ok(DeviceType::Veth),
             _ => Err(InvalidDeviceType(value)),
         }
     }
@@ -23,6 +23,8 @@
 use crate::api::scope::Scope;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::time::Duration;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -30,6 +32,7 @@ pub enum Error {
     MissingStep(Scope),
 }
 
+#[skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Progress {
@@ -43,6 +46,14 @@ pub struct Progress {
     pub step: String,
     /// Index of the current step
     pub index: usize,
+    /// Total amount of bytes to process, if known
+    pub total_bytes: Option<u64>,
+    /// Amount of already processed bytes
+    pub done_bytes: Option<u64>,
+    /// Current download throughput in bytes per second
+    pub bytes_per_second: Option<u64>,
+    /// Estimated remaining time in seconds, based on the processed bytes so far
+    pub eta: Option<u64>,
 }
 
 impl Progress {
@@ -53,6 +64,10 @@ impl Progress {
             steps: Vec::new(),
             step,
             index: 1,
+            total_bytes: None,
+            done_bytes: None,
+            bytes_per_second: None,
+            eta: None,
         }
     }
 
@@ -63,9 +78,22 @@ impl Progress {
             steps: steps.clone(),
             step: steps.first().map_or(String::new(), |s| s.clone()),
             index: 1,
+            total_bytes: None,
+            done_bytes: None,
+            bytes_per_second: None,
+            eta: None,
         }
     }
 
+    /// Enables the byte-level progress.
+    ///
+    /// * `total`: amount of bytes to process.
+    pub fn with_total_bytes(mut self, total: u64) -> Self {
+        self.total_bytes = Some(total);
+        self.done_bytes = Some(0);
+        self
+    }
+
     pub fn advance(&mut self) -> Result<(), Error> {
         if self.index >= self.size {
             tracing::error!(
@@ -86,7 +114,90 @@ impl Progress {
         Ok(())
     }
 
+    /// Adds processed bytes and updates the estimated remaining time.
+    ///
+    /// The amount of processed bytes never exceeds the total. The estimation uses the average
+    /// rate of processed bytes, so it does not mix the download and the installation speeds.
+    ///
+    /// * `bytes`: processed bytes.
+    /// * `elapsed`: time since the byte-level progress started, if known.
+    pub fn add_bytes(&mut self, bytes: u64, elapsed: Option<Duration>) {
+        let done = self.done_bytes.unwrap_or_default().saturating_add(bytes);
+        let done = match self.total_bytes {
+            Some(total) => done.min(total),
+            None => done,
+        };
+        self.done_bytes = Some(done);
+
+        let elapsed = elapsed.map(|e| e.as_secs_f64()).unwrap_or_default();
+        self.eta = match self.total_bytes {
+            Some(total) if done > 0 && elapsed > 0.0 => {
+                let remaining = total.saturating_sub(done) as f64;
+                Some((remaining * elapsed / done as f64).ceil() as u64)
+            }
+            _ => None,
+        };
+    }
+
+    /// Sets the current download throughput.
+    ///
+    /// It is only informative, the estimated remaining time is not affected.
+    pub fn set_throughput(&mut self, bytes_per_second: u64) {
+        self.bytes_per_second = Some(bytes_per_second);
+    }
+
     fn get_step(&self, index: usize) -> Option<String> {
         self.steps.get(index - 1).cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_bytes() {
+        let progress = Progress::new(Scope::Software, 2, "Installing".to_string());
+        assert_eq!(progress.done_bytes, None);
+
+        let mut progress = progress.with_total_bytes(1000);
+        progress.add_bytes(400, None);
+        assert_eq!(progress.done_bytes, Some(400));
+        assert_eq!(progress.eta, None);
+
+        progress.add_bytes(800, None);
+        assert_eq!(progress.done_bytes, Some(1000));
+    }
+
+    #[test]
+    fn test_eta() {
+        let mut progress =
+            Progress::new(Scope::Software, 2, "Installing".to_string()).with_total_bytes(1000);
+        // 250 bytes in 2 seconds: 750 bytes remaining at 125 bytes per second
+        progress.add_bytes(250, Some(Duration::from_secs(2)));
+        assert_eq!(progress.eta, Some(6));
+
+        // the download throughput does not affect the estimation
+        progress.set_throughput(1000);
+        assert_eq!(progress.bytes_per_second, Some(1000));
+        assert_eq!(progress.eta, Some(6));
+
+        progress.add_bytes(700, Some(Duration::from_secs(10)));
+        assert_eq!(progress.eta, Some(1));
+
+        progress.add_bytes(50, Some(Duration::from_secs(11)));
+        assert_eq!(progress.eta, Some(0));
+    }
+
+    #[test]
+    fn test_serialize_without_bytes() {
+        let progress = Progress::new(Scope::Software, 2, "Installing".to_string());
+        let json = serde_json::to_value(&progress).unwrap();
+        assert!(json.get("totalBytes").is_none());
+
+        let progress = progress.with_total_bytes(10);
+        let json = serde_json::to_value(&progress).unwrap();
+        assert_eq!(json["totalBytes"], 10);
+        assert_eq!(json["doneBytes"], 0);
+    }
+}
@@ -24,5 +24,11 @@ pub use config::*;
 mod system_info;
 pub use system_info::*;
 
+mod manifest;
+pub use manifest::{Manifest, ManifestPackage};
+
+mod gpg_key;
+pub use gpg_key::GpgKey;
+
 mod proposal;
-pub use proposal::{Proposal, SelectedBy, SoftwareProposal};
+pub use proposal::{DiskUsage, Proposal, SelectedBy, SoftwareProposal};
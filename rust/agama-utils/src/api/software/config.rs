@@ -26,6 +26,10 @@ use serde_with::skip_serializing_none;
 use std::collections::HashMap;
 use url::Url;
 
+use super::Manifest;
+use crate::api::files::{FileSource, FileSourceError};
+use fluent_uri::Uri;
+
 /// User configuration for the localization of the target system.
 ///
 /// This configuration is provided by the user, so all the values are optional.
@@ -41,6 +45,18 @@ pub struct Config {
     pub software: Option<SoftwareConfig>,
 }
 
+impl Config {
+    /// Resolve relative URLs in the software configuration.
+    ///
+    /// * `base_uri`: The base URI to resolve relative URLs against.
+    pub fn resolve_urls(&mut self, base_uri: &Uri<String>) -> Result<(), FileSourceError> {
+        if let Some(software) = &mut self.software {
+            software.resolve_urls(base_uri)?;
+        }
+        Ok(())
+    }
+}
+
 /// Addon settings for registration
 #[skip_serializing_none]
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
@@ -77,6 +93,29 @@ impl ProductConfig {
             && self.registration_url.is_none()
             && self.addons.is_none()
     }
+
+    /// Removes the registration settings, including the add-ons.
+    pub fn clear_registration(&mut self) {
+        self.registration_code = None;
+        self.registration_email = None;
+        self.registration_url = None;
+        self.addons = None;
+    }
+
+    /// Removes the given add-on from the registration settings.
+    ///
+    /// It returns whether the add-on was found.
+    ///
+    /// * `id`: add-on identifier.
+    pub fn remove_addon(&mut self, id: &str) -> bool {
+        let Some(addons) = &mut self.addons else {
+            return false;
+        };
+
+        let len = addons.len();
+        addons.retain(|a| a.id != id);
+        addons.len() != len
+    }
 }
 
 /// Software settings for installation
@@ -88,11 +127,92 @@ pub struct SoftwareConfig {
     /// List of user selected patterns to install.
     pub patterns: Option<PatternsConfig>,
     /// List of user selected packages to install.
+    ///
+    /// Apart from package names, it accepts capabilities ("provides:webserver"), globs
+    /// ("kernel-firmware-*") and regular expressions ("regex:^kernel-firmware-(amd|intel)$").
     pub packages: Option<Vec<String>>,
     /// List of user specified repositories to use on top of default ones.
     pub extra_repositories: Option<Vec<RepositoryConfig>>,
     /// Flag indicating if only hard requirements should be used by solver.
     pub only_required: Option<bool>,
+    /// Previously exported manifest. If set, exactly the packages listed in it are installed.
+    pub manifest: Option<Manifest>,
+    /// Container image to deploy instead of installing RPM packages (e.g.,
+    /// "registry.opensuse.org/opensuse/microos:latest" or "oci:/srv/image:latest").
+    pub image: Option<String>,
+    /// Whether to install the available patches (maintenance updates) during installation.
+    pub install_updates: Option<bool>,
+    /// Repositories providing the updates. They are used only when `install_updates` is enabled,
+    /// on top of the update repositories from the registration services.
+    pub update_repositories: Option<Vec<RepositoryConfig>>,
+    /// How to handle the package download and installation problems before asking the user.
+    pub retry_policy: Option<RetryPolicyConfig>,
+    /// GPG keys to trust during the installation. They are imported into the target system too.
+    pub gpg_keys: Option<Vec<FileSource>>,
+    /// Kernel to install.
+    pub kernel: Option<KernelConfig>,
+}
+
+/// Kernel settings.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelConfig {
+    /// Kernel flavour. If not set, the kernel from the product definition is used.
+    pub flavor: Option<KernelFlavor>,
+    /// Kernel versions to keep installed (e.g., "latest", "latest-1", "running" or a specific
+    /// version like "6.4.0-150600.23.7"). It is written as `multiversion.kernels` to zypp.conf.
+    pub multiversion: Option<Vec<String>>,
+}
+
+/// Kernel flavour.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum KernelFlavor {
+    /// General purpose kernel (kernel-default).
+    Default,
+    /// Real-time kernel (kernel-rt).
+    Rt,
+    /// Long-term support kernel (kernel-longterm).
+    Longterm,
+    /// Kernel using 64KB memory pages (kernel-64kb).
+    #[serde(rename = "64kb")]
+    Kb64,
+}
+
+impl KernelFlavor {
+    /// Flavour identifier, as used in the package names (e.g., "rt").
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Rt => "rt",
+            Self::Longterm => "longterm",
+            Self::Kb64 => "64kb",
+        }
+    }
+
+    /// Name of the kernel package (e.g., "kernel-rt").
+    pub fn package_name(&self) -> String {
+        format!("kernel-{}", self.id())
+    }
+}
+
+/// Policy to handle the package download and installation problems.
+///
+/// It is applied before asking the user.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicyConfig {
+    /// Maximum number of automatic retries for each package (0 by default).
+    pub max_retries: Option<u32>,
+    /// Seconds to wait before the first retry. The delay is doubled on each retry.
+    pub backoff: Option<u64>,
+    /// Whether to skip the optional packages once the retries are exhausted.
+    pub skip_optional: Option<bool>,
+    /// Whether to abort the installation when a core package fails once the retries are
+    /// exhausted.
+    pub abort_on_core_failure: Option<bool>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
@@ -140,12 +260,31 @@ impl SoftwareConfig {
             && self.packages.is_none()
             && self.extra_repositories.is_none()
             && self.only_required.is_none()
+            && self.manifest.is_none()
+            && self.image.is_none()
+            && self.install_updates.is_none()
+            && self.update_repositories.is_none()
+            && self.retry_policy.is_none()
+            && self.gpg_keys.is_none()
+            && self.kernel.is_none()
         {
             None
         } else {
             Some(self)
         }
     }
+
+    /// Resolve relative URLs in the GPG keys.
+    ///
+    /// * `base_uri`: The base URI to resolve relative URLs against.
+    pub fn resolve_urls(&mut self, base_uri: &Uri<String>) -> Result<(), FileSourceError> {
+        if let Some(keys) = &mut self.gpg_keys {
+            for key in keys {
+                *key = key.resolve_url(base_uri)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Parameters for creating new a repository
@@ -176,6 +315,7 @@ pub struct RepositoryConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fluent_uri::UriRef;
 
     #[test]
     fn test_merge_config() {
@@ -207,6 +347,16 @@ mod tests {
                     gpg_fingerprints: Some(vec!["fp1".to_string()]),
                 }]),
                 only_required: Some(false),
+                manifest: None,
+                image: Some("oci:/srv/image1".to_string()),
+                install_updates: None,
+                update_repositories: None,
+                retry_policy: Some(RetryPolicyConfig {
+                    max_retries: Some(3),
+                    ..Default::default()
+                }),
+                gpg_keys: None,
+                kernel: None,
             }),
         };
 
@@ -238,6 +388,32 @@ mod tests {
                     gpg_fingerprints: Some(vec!["fp2".to_string(), "fp3".to_string()]),
                 }]),
                 only_required: Some(true),
+                manifest: Some(Manifest::default()),
+                image: Some("oci:/srv/image2".to_string()),
+                install_updates: Some(true),
+                update_repositories: Some(vec![RepositoryConfig {
+                    alias: "updates".to_string(),
+                    name: None,
+                    url: "http://updates.com".to_string(),
+                    product_dir: None,
+                    enabled: None,
+                    priority: None,
+                    allow_unsigned: None,
+                    gpg_fingerprints: None,
+                }]),
+                retry_policy: Some(RetryPolicyConfig {
+                    max_retries: Some(1),
+                    backoff: Some(5),
+                    skip_optional: Some(true),
+                    abort_on_core_failure: Some(true),
+                }),
+                gpg_keys: Some(vec![FileSource::Text {
+                    content: "key2".to_string(),
+                }]),
+                kernel: Some(KernelConfig {
+                    flavor: Some(KernelFlavor::Rt),
+                    multiversion: None,
+                }),
             }),
         };
 
@@ -271,6 +447,30 @@ mod tests {
                 gpg_fingerprints: Some(vec!["fp1".to_string()]),
             }]),
             only_required: Some(false),
+            manifest: Some(Manifest::default()),
+            image: Some("oci:/srv/image1".to_string()),
+            install_updates: Some(true),
+            update_repositories: Some(vec![RepositoryConfig {
+                alias: "updates".to_string(),
+                name: None,
+                url: "http://updates.com".to_string(),
+                product_dir: None,
+                enabled: None,
+                priority: None,
+                allow_unsigned: None,
+                gpg_fingerprints: None,
+            }]),
+            retry_policy: Some(RetryPolicyConfig {
+                max_retries: Some(3),
+                ..Default::default()
+            }),
+            gpg_keys: Some(vec![FileSource::Text {
+                content: "key2".to_string(),
+            }]),
+            kernel: Some(KernelConfig {
+                flavor: Some(KernelFlavor::Rt),
+                multiversion: None,
+            }),
         };
 
         assert_eq!(updated.product, Some(expected_product));
@@ -325,4 +525,63 @@ mod tests {
         assert_eq!(updated.product, original_clone.product);
         assert_eq!(updated.software, updated_clone.software);
     }
+
+    #[test]
+    fn test_resolve_gpg_keys_urls() {
+        let mut config = SoftwareConfig {
+            gpg_keys: Some(vec![
+                FileSource::Remote {
+                    url: UriRef::parse("keys/custom.asc").unwrap().to_owned(),
+                },
+                FileSource::Text {
+                    content: "-----BEGIN PGP PUBLIC KEY BLOCK-----".to_string(),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let base_url = Uri::parse("http://example.lan/profile.json")
+            .unwrap()
+            .to_owned();
+        config.resolve_urls(&base_url).unwrap();
+
+        let keys = config.gpg_keys.unwrap();
+        assert!(matches!(
+            &keys[0],
+            FileSource::Remote { url } if url.as_str() == "http://example.lan/keys/custom.asc"
+        ));
+        assert!(matches!(&keys[1], FileSource::Text { .. }));
+    }
+
+    #[test]
+    fn test_kernel_flavor() {
+        let config: KernelConfig = serde_json::from_str(r#"{ "flavor": "64kb" }"#).unwrap();
+        assert_eq!(config.flavor, Some(KernelFlavor::Kb64));
+        assert_eq!(KernelFlavor::Kb64.package_name(), "kernel-64kb");
+        assert_eq!(KernelFlavor::Rt.package_name(), "kernel-rt");
+    }
+
+    #[test]
+    fn test_remove_registration() {
+        let mut product = ProductConfig {
+            id: Some("SLES".to_string()),
+            registration_code: Some("REGCODE".to_string()),
+            registration_url: Some(Url::parse("https://rmt.example.net").unwrap()),
+            addons: Some(vec![AddonConfig {
+                id: "sle-ha".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(!product.remove_addon("PackageHub"));
+        assert!(product.remove_addon("sle-ha"));
+        assert_eq!(product.addons, Some(vec![]));
+
+        product.clear_registration();
+        assert_eq!(product.id, Some("SLES".to_string()));
+        assert!(product.registration_code.is_none());
+        assert!(product.registration_url.is_none());
+        assert!(product.addons.is_none());
+    }
 }
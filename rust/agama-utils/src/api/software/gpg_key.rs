@@ -0,0 +1,84 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Representation of the GPG keys used to verify the repositories and the packages.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// GPG key known by the package manager.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GpgKey {
+    /// Key ID (e.g., "29B700A4").
+    pub id: String,
+    /// Key fingerprint.
+    pub fingerprint: String,
+    /// Key owner (e.g., "openSUSE Project Signing Key <opensuse@opensuse.org>").
+    pub name: String,
+    /// Expiration date as a UNIX timestamp. It is not set if the key never expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<i64>,
+    /// Whether the key is in the trusted keyring.
+    pub trusted: bool,
+    /// Aliases of the repositories which explicitly trust the key (see
+    /// `RepositoryConfig::gpg_fingerprints`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_by: Vec<String>,
+}
+
+impl GpgKey {
+    /// Whether the key has the given fingerprint.
+    ///
+    /// The comparison ignores the case and the whitespaces, so it works with
+    /// fingerprints written in groups (e.g., "22C0 7BA5 3417 8CD0 2EFE").
+    ///
+    /// * `fingerprint`: fingerprint to compare with.
+    pub fn has_fingerprint(&self, fingerprint: &str) -> bool {
+        let normalize = |fp: &str| {
+            fp.chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_uppercase()
+        };
+        normalize(&self.fingerprint) == normalize(fingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GpgKey;
+
+    #[test]
+    fn test_has_fingerprint() {
+        let key = GpgKey {
+            id: "29B700A4".to_string(),
+            fingerprint: "AD485664E901B867051AB15F35A2F86E29B700A4".to_string(),
+            name: "openSUSE Project Signing Key <opensuse@opensuse.org>".to_string(),
+            expires: None,
+            trusted: true,
+            trusted_by: vec![],
+        };
+
+        assert!(key.has_fingerprint("AD48 5664 E901 B867 051A B15F 35A2 F86E 29B7 00A4"));
+        assert!(key.has_fingerprint("ad485664e901b867051ab15f35a2f86e29b700a4"));
+        assert!(!key.has_fingerprint("22C07BA534178CD02EFE22AAB88B2FD43DBDC284"));
+    }
+}
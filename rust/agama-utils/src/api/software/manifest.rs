@@ -0,0 +1,105 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Representation of the resolved software manifest.
+//!
+//! The manifest works like a lock file: it lists the exact package builds resolved
+//! for an installation, so the same set can be installed again later.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Resolved set of packages to install.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(rename = "software.Manifest")]
+pub struct Manifest {
+    /// Packages to install.
+    pub packages: Vec<ManifestPackage>,
+}
+
+impl Manifest {
+    /// Whether the manifest contains the given package build.
+    ///
+    /// * `name`: package name.
+    /// * `version`: package edition ("[epoch:]version-release").
+    /// * `arch`: package architecture.
+    pub fn contains(&self, name: &str, version: &str, arch: &str) -> bool {
+        self.packages
+            .iter()
+            .any(|p| p.name == name && p.version == version && p.arch == arch)
+    }
+}
+
+/// Exact package build included in the manifest.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestPackage {
+    /// Package name (e.g., "vim").
+    pub name: String,
+    /// Package edition in the "[epoch:]version-release" format (e.g., "9.1.1176-1.1").
+    pub version: String,
+    /// Package architecture (e.g., "x86_64" or "noarch").
+    pub arch: String,
+    /// Alias of the repository providing the package.
+    pub repository: String,
+    /// Package checksum in the "type:value" format (e.g., "sha256:0a1b...").
+    pub checksum: Option<String>,
+}
+
+impl std::fmt::Display for ManifestPackage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}.{}", self.name, self.version, self.arch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vim() -> ManifestPackage {
+        ManifestPackage {
+            name: "vim".to_string(),
+            version: "9.1.1176-1.1".to_string(),
+            arch: "x86_64".to_string(),
+            repository: "agama-sles".to_string(),
+            checksum: Some("sha256:abcdef".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let manifest = Manifest {
+            packages: vec![vim()],
+        };
+
+        assert!(manifest.contains("vim", "9.1.1176-1.1", "x86_64"));
+        assert!(!manifest.contains("vim", "9.1.1176-1.2", "x86_64"));
+        assert!(!manifest.contains("vim", "9.1.1176-1.1", "aarch64"));
+        assert!(!manifest.contains("emacs", "9.1.1176-1.1", "x86_64"));
+    }
+
+    #[test]
+    fn test_display_package() {
+        assert_eq!(vim().to_string(), "vim-9.1.1176-1.1.x86_64");
+    }
+}
@@ -45,6 +45,40 @@ pub struct SoftwareProposal {
     /// Patterns selection. It is represented as a hash map where the key is the pattern's name
     /// and the value why the pattern is selected.
     pub patterns: HashMap<String, SelectedBy>,
+    /// Mirror in use for each repository offering alternative URLs. The key is the repository
+    /// alias and the value is the URL of the selected mirror.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mirrors: HashMap<String, String>,
+    /// Space required by the packages on each mount point of the storage proposal.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disk_usage: Vec<DiskUsage>,
+    /// Number of patches selected for installation. It is only set when installing updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patches_count: Option<u32>,
+    /// Packages selected through an expression (e.g., "provides:webserver" or
+    /// "kernel-firmware-*"). The key is the expression and the value is the list of matching
+    /// packages.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub expanded_packages: HashMap<String, Vec<String>>,
+}
+
+/// Space required and available on a mount point.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    /// Mount path (e.g., "/usr").
+    pub mount_path: String,
+    /// Space required by the packages in bytes.
+    pub required: u64,
+    /// Size of the file system in bytes.
+    pub available: u64,
+}
+
+impl DiskUsage {
+    /// Whether there is not enough space on the mount point.
+    pub fn is_exceeded(&self) -> bool {
+        self.required > self.available
+    }
 }
 
 /// Describes what Agama proposes for the target system.
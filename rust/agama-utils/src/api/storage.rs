@@ -20,3 +20,6 @@
 
 mod config;
 pub use config::Config;
+
+mod mount_point;
+pub use mount_point::MountPoint;
@@ -0,0 +1,158 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Mount points of the target system as planned by the storage proposal.
+
+use serde_json::Value;
+
+/// Mount point of the target system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MountPoint {
+    /// Mount path (e.g., "/home").
+    pub path: String,
+    /// File system type as reported by the storage proposal (e.g., "btrfsSnapshots").
+    pub filesystem: String,
+    /// Size of the device in bytes.
+    pub size: u64,
+}
+
+impl MountPoint {
+    /// Extracts the mount points from the storage proposal.
+    ///
+    /// Swap devices and devices without a mount path are ignored.
+    ///
+    /// * `proposal`: storage proposal as returned by the storage service.
+    pub fn from_proposal(proposal: &Value) -> Vec<MountPoint> {
+        let mut mount_points = vec![];
+        if let Some(devices) = proposal.get("devices").and_then(Value::as_array) {
+            for device in devices {
+                Self::collect(device, &mut mount_points);
+            }
+        }
+        mount_points
+    }
+
+    /// Whether the file system is a Btrfs one.
+    pub fn is_btrfs(&self) -> bool {
+        self.filesystem.starts_with("btrfs")
+    }
+
+    fn collect(device: &Value, mount_points: &mut Vec<MountPoint>) {
+        let filesystem = device.get("filesystem");
+        let path = filesystem
+            .and_then(|f| f.get("mountPath"))
+            .and_then(Value::as_str);
+        let fs_type = filesystem
+            .and_then(|f| f.get("type"))
+            .and_then(Value::as_str);
+        let size = device
+            .get("block")
+            .and_then(|b| b.get("size"))
+            .and_then(Value::as_u64);
+
+        if let (Some(path), Some(fs_type), Some(size)) = (path, fs_type, size) {
+            if !path.is_empty() && fs_type != "swap" {
+                mount_points.push(MountPoint {
+                    path: path.to_string(),
+                    filesystem: fs_type.to_string(),
+                    size,
+                });
+            }
+        }
+
+        for key in ["partitions", "logicalVolumes"] {
+            if let Some(children) = device.get(key).and_then(Value::as_array) {
+                for child in children {
+                    Self::collect(child, mount_points);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MountPoint;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_proposal() {
+        let proposal = json!({
+            "devices": [
+                {
+                    "sid": 1,
+                    "name": "/dev/vda",
+                    "block": { "start": 0, "size": 53687091200u64, "shrinking": { "supported": false } },
+                    "partitions": [
+                        {
+                            "sid": 2,
+                            "name": "/dev/vda1",
+                            "block": { "start": 2048, "size": 2147483648u64, "shrinking": { "supported": false } },
+                            "filesystem": { "sid": 3, "type": "swap", "mountPath": "swap" }
+                        },
+                        {
+                            "sid": 4,
+                            "name": "/dev/vda2",
+                            "block": { "start": 4196352, "size": 21474836480u64, "shrinking": { "supported": false } },
+                            "filesystem": { "sid": 5, "type": "btrfsSnapshots", "mountPath": "/" }
+                        }
+                    ]
+                },
+                {
+                    "sid": 6,
+                    "name": "/dev/system",
+                    "logicalVolumes": [
+                        {
+                            "sid": 7,
+                            "name": "/dev/system/home",
+                            "block": { "start": 0, "size": 10737418240u64, "shrinking": { "supported": false } },
+                            "filesystem": { "sid": 8, "type": "xfs", "mountPath": "/home" }
+                        }
+                    ]
+                }
+            ],
+            "actions": []
+        });
+
+        let mount_points = MountPoint::from_proposal(&proposal);
+        assert_eq!(
+            mount_points,
+            vec![
+                MountPoint {
+                    path: "/".to_string(),
+                    filesystem: "btrfsSnapshots".to_string(),
+                    size: 21474836480
+                },
+                MountPoint {
+                    path: "/home".to_string(),
+                    filesystem: "xfs".to_string(),
+                    size: 10737418240
+                }
+            ]
+        );
+        assert!(mount_points[0].is_btrfs());
+        assert!(!mount_points[1].is_btrfs());
+    }
+
+    #[test]
+    fn test_from_empty_proposal() {
+        assert!(MountPoint::from_proposal(&json!({})).is_empty());
+    }
+}
@@ -36,6 +36,27 @@ pub struct Config {
     #[merge(strategy = merge::option::overwrite_none)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root: Option<RootUserConfig>,
+    /// Additional users to create
+    #[merge(strategy = merge::option::overwrite_none)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<Vec<UserConfig>>,
+    /// Groups to create
+    #[merge(strategy = merge::option::overwrite_none)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<GroupConfig>>,
+    /// sudoers drop-in files to write
+    #[merge(strategy = merge::option::overwrite_none)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sudoers: Option<Vec<SudoersConfig>>,
+    /// How to handle the failures when writing the users configuration
+    #[merge(strategy = merge::option::overwrite_none)]
+    #[serde(rename = "usersStrictness")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strictness: Option<Strictness>,
+    /// Rules that the plain text passwords must fulfill
+    #[merge(strategy = merge::option::overwrite_none)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_policy: Option<PasswordPolicy>,
 }
 
 impl Config {
@@ -43,11 +64,23 @@ impl Config {
         Self {
             first_user: None,
             root: None,
+            users: None,
+            groups: None,
+            sudoers: None,
+            strictness: None,
+            password_policy: None,
         }
     }
 
     pub fn to_api(&self) -> Option<Config> {
-        if self.root.is_none() && self.first_user.is_none() {
+        if self.root.is_none()
+            && self.first_user.is_none()
+            && self.users.is_none()
+            && self.groups.is_none()
+            && self.sudoers.is_none()
+            && self.strictness.is_none()
+            && self.password_policy.is_none()
+        {
             return None;
         }
 
@@ -63,8 +96,119 @@ impl Config {
             return false;
         }
 
+        if self.users().any(|u| u.has_authentication()) {
+            return false;
+        }
+
         true
     }
+
+    /// Returns an iterator over the additional users.
+    pub fn users(&self) -> impl Iterator<Item = &UserConfig> {
+        self.users.iter().flatten()
+    }
+
+    /// Returns an iterator over the groups.
+    pub fn groups(&self) -> impl Iterator<Item = &GroupConfig> {
+        self.groups.iter().flatten()
+    }
+
+    /// Returns an iterator over the sudoers drop-in files.
+    pub fn sudoers(&self) -> impl Iterator<Item = &SudoersConfig> {
+        self.sudoers.iter().flatten()
+    }
+
+    /// Returns the passwords of all the accounts, including the account name.
+    pub fn passwords(&self) -> Vec<(String, &UserPassword)> {
+        let mut passwords = vec![];
+        if let Some(user) = &self.first_user {
+            let name = user.user_name.clone().unwrap_or_default();
+            if let Some(password) = &user.password {
+                passwords.push((name, password));
+            }
+        }
+        for user in self.users() {
+            if let Some(password) = &user.password {
+                passwords.push((user.user_name.clone(), password));
+            }
+        }
+        if let Some(password) = self.root.as_ref().and_then(|r| r.password.as_ref()) {
+            passwords.push(("root".to_string(), password));
+        }
+        passwords
+    }
+
+    /// Returns the passwords of all the accounts, including the account name.
+    pub fn passwords_mut(&mut self) -> Vec<(String, &mut UserPassword)> {
+        let mut passwords = vec![];
+        if let Some(user) = &mut self.first_user {
+            let name = user.user_name.clone().unwrap_or_default();
+            if let Some(password) = &mut user.password {
+                passwords.push((name, password));
+            }
+        }
+        for user in self.users.iter_mut().flatten() {
+            if let Some(password) = &mut user.password {
+                passwords.push((user.user_name.clone(), password));
+            }
+        }
+        if let Some(password) = self.root.as_mut().and_then(|r| r.password.as_mut()) {
+            passwords.push(("root".to_string(), password));
+        }
+        passwords
+    }
+
+    /// Returns a copy of the configuration replacing the passwords with a marker.
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        for (_, password) in config.passwords_mut() {
+            if !password.is_empty() {
+                *password = UserPassword::redacted();
+            }
+        }
+        config
+    }
+
+    /// Replaces the redacted passwords with the ones from the given configuration.
+    ///
+    /// The passwords of the first user and root are restored from the same account, even if the
+    /// first user was renamed. The passwords of the additional users are restored by user name.
+    /// The passwords that cannot be found in the given configuration are kept redacted.
+    ///
+    /// * `old`: configuration containing the original passwords.
+    pub fn restore_redacted(&mut self, old: &Config) {
+        if let Some(user) = &mut self.first_user {
+            let old_password = old.first_user.as_ref().and_then(|u| u.password.as_ref());
+            restore_password(user.password.as_mut(), old_password);
+        }
+
+        for user in self.users.iter_mut().flatten() {
+            let old_password = old
+                .users()
+                .find(|u| u.user_name == user.user_name)
+                .and_then(|u| u.password.as_ref());
+            restore_password(user.password.as_mut(), old_password);
+        }
+
+        if let Some(root) = &mut self.root {
+            let old_password = old.root.as_ref().and_then(|r| r.password.as_ref());
+            restore_password(root.password.as_mut(), old_password);
+        }
+    }
+}
+
+/// Replaces a redacted password with the original one, if it is known.
+///
+/// * `password`: password to restore.
+/// * `old_password`: original password.
+fn restore_password(password: Option<&mut UserPassword>, old_password: Option<&UserPassword>) {
+    let Some(password) = password.filter(|p| p.is_redacted()) else {
+        return;
+    };
+
+    if let Some(old_password) = old_password.filter(|p| !p.is_redacted()) {
+        *password = old_password.clone();
+    }
 }
 
 /// First user settings
@@ -103,10 +247,179 @@ impl FirstUserConfig {
     }
 }
 
+/// Strictness when writing the users configuration to the installed system.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Strictness {
+    /// The failures are reported, but the installation goes on.
+    Lenient,
+    /// The installation fails if no account can be used to log into the system.
+    #[default]
+    Login,
+    /// The installation fails if any part of the configuration could not be written.
+    Strict,
+}
+
+/// Password policy
+///
+/// Rules that the passwords must fulfill. It can be defined by the product or in the profile.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordPolicy {
+    /// Minimum length (8 by default)
+    #[serde(alias = "min_length")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Minimum number of character classes: lowercase, uppercase, digits and others (1 by default)
+    #[serde(alias = "min_classes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_classes: Option<usize>,
+    /// Path of a file containing forbidden words, one per line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<String>,
+    /// Whether the password cannot contain the user name (true by default)
+    #[serde(alias = "forbid_user_name")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forbid_user_name: Option<bool>,
+}
+
+impl PasswordPolicy {
+    pub const DEFAULT_MIN_LENGTH: usize = 8;
+
+    /// Minimum length of the password.
+    pub fn min_length(&self) -> usize {
+        self.min_length.unwrap_or(Self::DEFAULT_MIN_LENGTH)
+    }
+
+    /// Minimum number of character classes.
+    pub fn min_classes(&self) -> usize {
+        self.min_classes.unwrap_or(1)
+    }
+
+    /// Whether the password cannot contain the user name.
+    pub fn forbid_user_name(&self) -> bool {
+        self.forbid_user_name.unwrap_or(true)
+    }
+}
+
+/// Additional user settings
+///
+/// Holds the settings for a user to create, like a service or an admin account.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserConfig {
+    /// User's login name
+    pub user_name: String,
+    /// User's full name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    /// User password
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<UserPassword>,
+    /// User ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    /// ID of the primary group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+    /// Login shell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    /// Home directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home: Option<String>,
+    /// Supplementary groups
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<String>>,
+    /// Whether it is a system account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<bool>,
+    /// Whether the account is locked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    /// Expiration date of the account (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "sshPublicKey")]
+    pub ssh_public_keys: Option<StringOrList>,
+}
+
+impl UserConfig {
+    /// Whether it is an empty user.
+    pub fn is_empty(&self) -> bool {
+        self.user_name.is_empty()
+    }
+
+    /// Whether the user can authenticate, that is, the account is not locked and it has a
+    /// password or an SSH public key.
+    pub fn has_authentication(&self) -> bool {
+        if self.is_empty() || self.is_locked() {
+            return false;
+        }
+
+        self.password.as_ref().is_some_and(|p| !p.is_empty())
+            || self.ssh_public_keys.as_ref().is_some_and(|k| !k.is_empty())
+    }
+
+    /// Whether the account is locked.
+    pub fn is_locked(&self) -> bool {
+        self.locked.unwrap_or_default()
+    }
+
+    /// Whether it is a system account.
+    pub fn is_system(&self) -> bool {
+        self.system.unwrap_or_default()
+    }
+
+    /// Home directory of the user.
+    pub fn home_dir(&self) -> String {
+        self.home
+            .clone()
+            .unwrap_or_else(|| format!("/home/{}", self.user_name))
+    }
+}
+
+/// Group settings
+///
+/// Holds the settings for a group to create.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupConfig {
+    /// Group name
+    pub group_name: String,
+    /// Group ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+    /// Whether it is a system group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<bool>,
+}
+
+impl GroupConfig {
+    /// Whether it is a system group.
+    pub fn is_system(&self) -> bool {
+        self.system.unwrap_or_default()
+    }
+}
+
+/// sudoers drop-in settings
+///
+/// Holds the rules to write to a file in the `/etc/sudoers.d` directory.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SudoersConfig {
+    /// File name
+    pub name: String,
+    /// sudoers rules (e.g., "%admins ALL=(ALL) ALL")
+    pub rules: Vec<String>,
+}
+
 /// Represents a user password.
 ///
 /// It holds the password and whether it is a hashed or a plain text password.
-#[derive(Clone, Debug, Merge, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Clone, Debug, Default, Merge, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UserPassword {
     /// User password
@@ -119,9 +432,32 @@ pub struct UserPassword {
 }
 
 impl UserPassword {
+    /// Marker used instead of the password when reading the configuration.
+    pub const REDACTED: &'static str = "<redacted>";
+
+    /// Returns a hashed password.
+    ///
+    /// * `hash`: password hash (e.g., "$6$...").
+    pub fn hashed(hash: &str) -> Self {
+        Self {
+            password: hash.to_string(),
+            hashed_password: true,
+        }
+    }
+
+    /// Returns a redacted password.
+    pub fn redacted() -> Self {
+        Self::hashed(Self::REDACTED)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.password.is_empty()
     }
+
+    /// Whether the password was redacted.
+    pub fn is_redacted(&self) -> bool {
+        self.password == Self::REDACTED
+    }
 }
 
 fn overwrite_if_not_empty(old: &mut String, new: String) {
@@ -192,7 +528,10 @@ impl RootUserConfig {
 
 #[cfg(test)]
 mod test {
-    use super::{Config, FirstUserConfig, RootUserConfig, StringOrList, UserPassword};
+    use super::{
+        Config, FirstUserConfig, GroupConfig, RootUserConfig, Strictness, StringOrList,
+        SudoersConfig, UserConfig, UserPassword,
+    };
 
     #[test]
     fn test_parse_user_password() {
@@ -207,6 +546,128 @@ mod test {
         assert!(!password.hashed_password);
     }
 
+    #[test]
+    fn test_parse_users_and_groups() {
+        let config_str = r#"{
+            "users": [
+                {
+                    "userName": "backup",
+                    "uid": 900,
+                    "shell": "/sbin/nologin",
+                    "groups": ["backup"],
+                    "system": true,
+                    "locked": true
+                },
+                {
+                    "userName": "admin",
+                    "password": "$6$abc",
+                    "hashedPassword": true,
+                    "sshPublicKey": "ssh-ed25519 AAAA"
+                }
+            ],
+            "groups": [{ "groupName": "backup", "gid": 900 }],
+            "sudoers": [{ "name": "admin", "rules": ["admin ALL=(ALL) NOPASSWD: ALL"] }],
+            "usersStrictness": "strict",
+            "passwordPolicy": { "minLength": 12, "minClasses": 3 }
+        }"#;
+        let config: Config = serde_json::from_str(config_str).unwrap();
+
+        let users: Vec<_> = config.users().collect();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].uid, Some(900));
+        assert!(users[0].is_system());
+        assert!(users[0].is_locked());
+        assert_eq!(users[0].home_dir(), "/home/backup");
+        assert_eq!(
+            users[1].password,
+            Some(UserPassword {
+                password: "$6$abc".to_string(),
+                hashed_password: true
+            })
+        );
+        assert_eq!(
+            users[1].ssh_public_keys,
+            Some(StringOrList::Single("ssh-ed25519 AAAA".to_string()))
+        );
+
+        assert_eq!(
+            config.groups().collect::<Vec<_>>(),
+            vec![&GroupConfig {
+                group_name: "backup".to_string(),
+                gid: Some(900),
+                system: None
+            }]
+        );
+        assert_eq!(
+            config.sudoers().collect::<Vec<_>>(),
+            vec![&SudoersConfig {
+                name: "admin".to_string(),
+                rules: vec!["admin ALL=(ALL) NOPASSWD: ALL".to_string()]
+            }]
+        );
+        assert_eq!(config.strictness, Some(Strictness::Strict));
+        let policy = config.password_policy.unwrap();
+        assert_eq!(policy.min_length(), 12);
+        assert_eq!(policy.min_classes(), 3);
+        assert!(policy.forbid_user_name());
+        assert!(!config.is_empty());
+    }
+
+    #[test]
+    fn test_redacted() {
+        let mut config = Config {
+            first_user: Some(FirstUserConfig {
+                user_name: Some("jane".to_string()),
+                password: Some(UserPassword::hashed("$6$jane")),
+                ..Default::default()
+            }),
+            users: Some(vec![UserConfig {
+                user_name: "admin".to_string(),
+                password: Some(UserPassword::hashed("$6$admin")),
+                ..Default::default()
+            }]),
+            root: Some(RootUserConfig {
+                password: Some(UserPassword::hashed("$6$root")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut redacted = config.redacted();
+        assert!(redacted
+            .passwords_mut()
+            .iter()
+            .all(|(_, p)| p.is_redacted()));
+        let json = serde_json::to_string(&redacted).unwrap();
+        assert!(!json.contains("$6$"));
+
+        redacted.restore_redacted(&config);
+        assert_eq!(redacted, config);
+
+        // the first user was renamed, but it keeps its password
+        let mut redacted = config.redacted();
+        redacted.first_user.as_mut().unwrap().user_name = Some("john".to_string());
+        redacted.restore_redacted(&config);
+        let password = redacted.first_user.unwrap().password.unwrap();
+        assert_eq!(password, UserPassword::hashed("$6$jane"));
+
+        // the additional user was renamed, so its password is unknown
+        let mut redacted = config.redacted();
+        redacted.users.as_mut().unwrap()[0].user_name = "operator".to_string();
+        redacted.restore_redacted(&config);
+        let password = redacted.users.unwrap()[0].password.clone().unwrap();
+        assert!(password.is_redacted());
+
+        // a new password replaces the old one
+        config.root.as_mut().unwrap().password = Some(UserPassword {
+            password: "n0ts3cr3t".to_string(),
+            hashed_password: false,
+        });
+        let original = config.clone();
+        config.restore_redacted(&Config::default());
+        assert_eq!(config, original);
+    }
+
     #[test]
     fn test_is_empty() {
         assert!(Config::default().is_empty());
@@ -282,6 +743,56 @@ mod test {
             ..Default::default()
         };
         assert!(!root_with_ssh_keys_config.is_empty());
+
+        let user_without_auth = UserConfig {
+            user_name: "admin".to_string(),
+            ..Default::default()
+        };
+        let users_config = Config {
+            users: Some(vec![user_without_auth.clone()]),
+            ..Default::default()
+        };
+        assert!(users_config.is_empty());
+
+        let user_with_password = UserConfig {
+            password: Some(password.clone()),
+            ..user_without_auth.clone()
+        };
+        let users_config = Config {
+            users: Some(vec![user_with_password.clone()]),
+            ..Default::default()
+        };
+        assert!(!users_config.is_empty());
+
+        let user_with_ssh_key = UserConfig {
+            ssh_public_keys: Some(StringOrList::Single("12345678".to_string())),
+            ..user_without_auth.clone()
+        };
+        let users_config = Config {
+            users: Some(vec![user_with_ssh_key]),
+            ..Default::default()
+        };
+        assert!(!users_config.is_empty());
+
+        let user_with_empty_password = UserConfig {
+            password: Some(empty_password.clone()),
+            ..user_without_auth.clone()
+        };
+        let users_config = Config {
+            users: Some(vec![user_with_empty_password]),
+            ..Default::default()
+        };
+        assert!(users_config.is_empty());
+
+        let locked_user = UserConfig {
+            locked: Some(true),
+            ..user_with_password
+        };
+        let users_config = Config {
+            users: Some(vec![locked_user]),
+            ..Default::default()
+        };
+        assert!(users_config.is_empty());
     }
 
     #[test]
@@ -32,6 +32,7 @@ pub mod licenses;
 pub mod logging;
 pub mod message;
 pub mod openapi;
+pub mod password;
 pub mod products;
 pub mod progress;
 pub mod question;
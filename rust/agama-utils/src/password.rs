@@ -0,0 +1,137 @@
+// Copyright (c) [2026] SUSE LLC
+//
+// All Rights Reserved.
+//
+// This program is free software; you can redistribute it and/or modify it
+// under the terms of the GNU General Public License as published by the Free
+// Software Foundation; either version 2 of the License, or (at your option)
+// any later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+// FITNESS FOR A PARTICULAR PURPOSE.  See the GNU General Public License for
+// more details.
+//
+// You should have received a copy of the GNU General Public License along
+// with this program; if not, contact SUSE LLC.
+//
+// To contact SUSE LLC about this file by physical or electronic mail, you may
+// find current contact information at www.suse.com.
+
+//! Password hashing.
+//!
+//! The passwords are hashed with `openssl passwd -6` (SHA-512 crypt), so the hashes are
+//! compatible with crypt(3) and they can be used with `chpasswd -e`.
+
+use std::process::Stdio;
+use tokio::{io::AsyncWriteExt, process::Command};
+
+#[derive(thiserror::Error, Debug)]
+pub enum HashError {
+    #[error("The password cannot contain line breaks")]
+    LineBreak,
+    #[error("Could not run openssl: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("openssl failed: {0}")]
+    Failed(String),
+}
+
+/// Hashes the given password using a random salt.
+///
+/// * `password`: plain text password.
+pub async fn hash_password(password: &str) -> Result<String, HashError> {
+    hash_password_with_salt(password, None).await
+}
+
+/// Whether the given password matches a SHA-512 crypt hash.
+///
+/// It returns `false` if the hash is not a SHA-512 crypt hash with the default number of rounds.
+///
+/// * `password`: plain text password.
+/// * `hash`: hash to compare with (e.g., "$6$salt$...").
+pub async fn verify_password(password: &str, hash: &str) -> Result<bool, HashError> {
+    let Some((salt, _)) = hash.strip_prefix("$6$").and_then(|h| h.split_once('$')) else {
+        return Ok(false);
+    };
+    if salt.starts_with("rounds=") {
+        return Ok(false);
+    }
+
+    Ok(hash_password_with_salt(password, Some(salt)).await? == hash)
+}
+
+/// Hashes the given password using the given salt or a random one.
+///
+/// The password is written to the standard input, so it is not exposed in the process list.
+///
+/// * `password`: plain text password.
+/// * `salt`: salt to use. If it is not set, openssl generates a random one.
+async fn hash_password_with_salt(password: &str, salt: Option<&str>) -> Result<String, HashError> {
+    if password.contains(['\n', '\r']) {
+        return Err(HashError::LineBreak);
+    }
+
+    let mut command = Command::new("openssl");
+    command.args(["passwd", "-6", "-stdin"]);
+    if let Some(salt) = salt {
+        command.args(["-salt", salt]);
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(format!("{password}\n").as_bytes()).await?;
+        stdin.shutdown().await?;
+    }
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        return Err(HashError::Failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hash_password_with_salt() {
+        let hash = hash_password_with_salt("Hello world!", Some("saltstring"))
+            .await
+            .unwrap();
+        assert_eq!(
+            hash,
+            "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_password() {
+        let hash = hash_password("nots3cr3t").await.unwrap();
+        assert!(hash.starts_with("$6$"));
+        let salt = hash.split('$').nth(2).unwrap();
+        assert_eq!(
+            hash_password_with_salt("nots3cr3t", Some(salt))
+                .await
+                .unwrap(),
+            hash
+        );
+        assert_ne!(hash_password("nots3cr3t").await.unwrap(), hash);
+
+        assert!(verify_password("nots3cr3t", &hash).await.unwrap());
+        assert!(!verify_password("n0ts3cr3t", &hash).await.unwrap());
+        assert!(!verify_password("nots3cr3t", "$5$salt$hash").await.unwrap());
+
+        assert!(matches!(
+            hash_password("not\nsecret").await,
+            Err(HashError::LineBreak)
+        ));
+    }
+}
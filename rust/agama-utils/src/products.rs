@@ -45,6 +45,7 @@ use crate::{
     api::{
         l10n::Translations,
         manager::{system_info, Product, ProductMode},
+        users::config::PasswordPolicy,
     },
     arch::Arch,
 };
@@ -308,6 +309,8 @@ pub struct ProductTemplate {
     #[serde(default)]
     pub boot: BootSpec,
     #[serde(default)]
+    pub users: UsersSpec,
+    #[serde(default)]
     pub modes: Vec<ProductModeSpec>,
 }
 
@@ -353,6 +356,7 @@ impl ProductTemplate {
             software,
             storage,
             boot: self.boot.clone(),
+            users: self.users.clone(),
         })
     }
 
@@ -390,6 +394,7 @@ pub struct ProductSpec {
     pub software: SoftwareSpec,
     pub storage: StorageSpec,
     pub boot: BootSpec,
+    pub users: UsersSpec,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -427,6 +432,10 @@ pub struct SoftwareSpec {
     #[serde(default)]
     #[merge(strategy = merge::option::overwrite_none)]
     pub kernel: Option<String>,
+    /// Container image to deploy instead of installing RPM packages.
+    #[serde(default)]
+    #[merge(strategy = merge::option::overwrite_none)]
+    pub image: Option<String>,
 }
 
 impl SoftwareSpec {
@@ -494,6 +503,9 @@ pub struct UserPattern {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RepositorySpec {
     pub url: String,
+    /// Alternative URLs serving the same repository.
+    #[serde(default)]
+    pub mirrors: Vec<String>,
     #[serde(default)]
     #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
     pub archs: Vec<String>,
@@ -531,6 +543,12 @@ pub struct BootSpec {
     pub default_efi_bootloader: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct UsersSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_policy: Option<PasswordPolicy>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VolumeSpec {
     #[serde(default)]
@@ -693,6 +711,10 @@ mod test {
         let product = ctx.registry.find("SLES", None).unwrap();
         assert_eq!(&product.id, "SLES");
         assert_eq!(&product.mode.unwrap(), "standard");
+
+        let policy = product.users.password_policy.unwrap();
+        assert_eq!(policy.min_length(), 10);
+        assert_eq!(policy.min_classes(), 3);
     }
 
     #[test_context(Context)]
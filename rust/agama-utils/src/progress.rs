@@ -236,6 +236,67 @@ mod tests {
         Ok(())
     }
 
+    #[test_context(Context)]
+    #[tokio::test]
+    async fn test_set_step(ctx: &mut Context) -> Result<(), Box<dyn std::error::Error>> {
+        ctx.handler
+            .call(message::Start::new(Scope::Software, 3, "Downloading"))
+            .await?;
+        ctx.handler
+            .call(message::SetStep::new(Scope::Software, "Retrying"))
+            .await?;
+
+        let progresses = ctx.handler.call(message::GetProgress).await?;
+        let progress = progresses.first().unwrap();
+        assert_eq!(progress.step, "Retrying");
+        assert_eq!(progress.index, 1);
+
+        Ok(())
+    }
+
+    #[test_context(Context)]
+    #[tokio::test]
+    async fn test_progress_bytes(ctx: &mut Context) -> Result<(), Box<dyn std::error::Error>> {
+        let progress =
+            Progress::new(Scope::Software, 4, "Installing".to_string()).with_total_bytes(2048);
+        ctx.handler
+            .call(message::SetProgress::new(progress))
+            .await?;
+        _ = ctx.events_rx.recv().await.unwrap();
+
+        ctx.handler
+            .call(message::AddBytes::new(Scope::Software, 1024))
+            .await?;
+        ctx.handler
+            .call(message::SetThroughput::new(Scope::Software, 256))
+            .await?;
+
+        _ = ctx.events_rx.recv().await.unwrap();
+        let event = ctx.events_rx.recv().await.unwrap();
+        let Event::ProgressChanged {
+            progress: event_progress,
+        } = event
+        else {
+            panic!("Unexpected event: {:?}", event);
+        };
+
+        assert_eq!(event_progress.total_bytes, Some(2048));
+        assert_eq!(event_progress.done_bytes, Some(1024));
+        assert_eq!(event_progress.bytes_per_second, Some(256));
+        assert!(event_progress.eta.is_some());
+
+        let error = ctx
+            .handler
+            .call(message::AddBytes::new(Scope::L10n, 1024))
+            .await;
+        assert!(matches!(
+            error,
+            Err(service::Error::MissingProgress(Scope::L10n))
+        ));
+
+        Ok(())
+    }
+
     #[test_context(Context)]
     #[tokio::test]
     async fn test_several_progresses(ctx: &mut Context) -> Result<(), Box<dyn std::error::Error>> {
@@ -117,6 +117,60 @@ impl Message for NextWithStep {
     type Reply = ();
 }
 
+/// Changes the label of the current step without advancing the progress.
+pub struct SetStep {
+    pub scope: Scope,
+    pub step: String,
+}
+
+impl SetStep {
+    pub fn new(scope: Scope, step: &str) -> Self {
+        Self {
+            scope,
+            step: step.to_string(),
+        }
+    }
+}
+
+impl Message for SetStep {
+    type Reply = ();
+}
+
+/// Adds processed bytes to the byte-level progress.
+pub struct AddBytes {
+    pub scope: Scope,
+    pub bytes: u64,
+}
+
+impl AddBytes {
+    pub fn new(scope: Scope, bytes: u64) -> Self {
+        Self { scope, bytes }
+    }
+}
+
+impl Message for AddBytes {
+    type Reply = ();
+}
+
+/// Sets the current throughput (in bytes per second) of the byte-level progress.
+pub struct SetThroughput {
+    pub scope: Scope,
+    pub bytes_per_second: u64,
+}
+
+impl SetThroughput {
+    pub fn new(scope: Scope, bytes_per_second: u64) -> Self {
+        Self {
+            scope,
+            bytes_per_second,
+        }
+    }
+}
+
+impl Message for SetThroughput {
+    type Reply = ();
+}
+
 pub struct Finish {
     pub scope: Scope,
 }
@@ -28,6 +28,7 @@ use crate::{
     progress::message,
 };
 use async_trait::async_trait;
+use std::{collections::HashMap, time::Instant};
 use tokio::sync::broadcast;
 
 #[derive(thiserror::Error, Debug)]
@@ -60,6 +61,7 @@ impl Starter {
         let service = Service {
             events: self.event,
             status: Status::default(),
+            bytes_started: HashMap::new(),
         };
 
         actor::spawn(service)
@@ -69,6 +71,8 @@ impl Starter {
 pub struct Service {
     events: event::Sender,
     status: Status,
+    // when the byte-level progress started for each scope
+    bytes_started: HashMap<Scope, Instant>,
 }
 
 impl Service {
@@ -159,6 +163,11 @@ impl MessageHandler<message::GetProgress> for Service {
 impl MessageHandler<message::SetProgress> for Service {
     async fn handle(&mut self, message: message::SetProgress) -> Result<(), Error> {
         let progress = message.progress;
+        if progress.total_bytes.is_some() {
+            self.bytes_started.insert(progress.scope, Instant::now());
+        } else {
+            self.bytes_started.remove(&progress.scope);
+        }
         if let Some(index) = self.get_progress_index(progress.scope) {
             self.update_progress(index, progress.clone());
         } else {
@@ -236,6 +245,49 @@ impl MessageHandler<message::NextWithStep> for Service {
     }
 }
 
+#[async_trait]
+impl MessageHandler<message::SetStep> for Service {
+    async fn handle(&mut self, message: message::SetStep) -> Result<(), Error> {
+        let Some(progress) = self.get_mut_progress(message.scope) else {
+            tracing::error!("progress with scope #{:#?} not found", message.scope);
+            return Err(Error::MissingProgress(message.scope));
+        };
+        progress.step = message.step;
+        let progress = progress.clone();
+        self.send_progress_changed(progress)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::AddBytes> for Service {
+    async fn handle(&mut self, message: message::AddBytes) -> Result<(), Error> {
+        let elapsed = self.bytes_started.get(&message.scope).map(Instant::elapsed);
+        let Some(progress) = self.get_mut_progress(message.scope) else {
+            tracing::error!("progress with scope #{:#?} not found", message.scope);
+            return Err(Error::MissingProgress(message.scope));
+        };
+        progress.add_bytes(message.bytes, elapsed);
+        let progress = progress.clone();
+        self.send_progress_changed(progress)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageHandler<message::SetThroughput> for Service {
+    async fn handle(&mut self, message: message::SetThroughput) -> Result<(), Error> {
+        let Some(progress) = self.get_mut_progress(message.scope) else {
+            tracing::error!("progress with scope #{:#?} not found", message.scope);
+            return Err(Error::MissingProgress(message.scope));
+        };
+        progress.set_throughput(message.bytes_per_second);
+        let progress = progress.clone();
+        self.send_progress_changed(progress)?;
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl MessageHandler<message::Finish> for Service {
     async fn handle(&mut self, message: message::Finish) -> Result<(), Error> {
@@ -243,6 +295,7 @@ impl MessageHandler<message::Finish> for Service {
             .get_progress_index(message.scope)
             .ok_or(Error::MissingProgress(message.scope))?;
         self.remove_progress(index);
+        self.bytes_started.remove(&message.scope);
         self.events.send(Event::ProgressFinished {
             scope: message.scope,
         })?;
@@ -55,9 +55,6 @@ Requires:       util-linux-systemd
 BuildRequires:  jsonnet
 Requires:       jsonnet
 Requires:       lshw
-# required by the password checking
-BuildRequires:  libpwquality-tools
-Requires:       libpwquality-tools
 # required by "agama logs store"
 Requires:       gzip
 # required to compress the manual pages
@@ -76,6 +73,11 @@ Requires:       agama-yast
 Requires:       agama-common
 # required for importing SSL certificates
 Requires:       ca-certificates
+# required for hashing the users passwords
+Requires:       openssl
+# required for installing image-based products from container images
+Recommends:     skopeo
+Recommends:     umoci
 %ifarch s390x
 # s390 specific frame title that is read from readvalues from s390-tools
 # needed also for tests, so build require it
@@ -118,6 +120,8 @@ Summary:        Agama command-line interface
 License:        GPL-2.0-only
 Url:            https://github.com/agama-project/agama
 Requires:       agama-common
+# required by "agama users hash-password"
+Requires:       openssl
 
 %description -n agama-cli
 Command line program to interact with the Agama installer.
@@ -284,6 +284,23 @@ fn check_error(response: &Value) -> Result<(), Error> {
     Ok(())
 }
 
+/// Parses a SUSEConnect response.
+///
+/// It checks whether the response contains an error and, if it does not, converts it to
+/// the wanted type. It is also useful to process canned responses (e.g., when testing).
+///
+/// # Arguments
+///
+/// * `response` - JSON response from SUSEConnect.
+pub fn parse_response<T>(response: &str) -> Result<T, Error>
+where
+    T: TryFrom<Value, Error = Error>,
+{
+    let response: Value = serde_json::from_str(response)?;
+    check_error(&response)?;
+    response.try_into()
+}
+
 /// SCC/System credentails
 ///
 /// Data returned from announce call at https://github.com/SUSE/connect-ng/blob/main/third_party/yast/lib/suse/connect/yast.rb#L57
@@ -349,10 +366,7 @@ pub fn announce_system(params: ConnectParams, target_distro: &str) -> Result<Cre
         string_from_ptr(result_ptr)
     }?;
 
-    let response: Value = serde_json::from_str(&result_s)?;
-    check_error(&response)?;
-
-    response.try_into()
+    parse_response(&result_s)
 }
 
 /// Activates a product with SUSE Customer Center.
@@ -394,10 +408,71 @@ pub fn activate_product(
         string_from_ptr(result_ptr)
     }?;
 
-    let response: Value = serde_json::from_str(&result_s)?;
-    check_error(&response)?;
+    parse_response(&result_s)
+}
 
-    response.try_into()
+/// Deactivates a product, removing it from the registered system.
+///
+/// # Arguments
+///
+/// * `product` - The [ProductSpecification] to deactivate.
+/// * `params` - Parameters [ConnectParams] for the connection.
+///
+/// # Returns
+///
+/// On success, returns `Ok(Service)` containing the [Service] that was associated to
+/// the product, so it can be removed from libzypp.
+///
+/// # Errors
+///
+/// Returns an `Err` of type [Error] if deactivation fails.
+pub fn deactivate_product(
+    product: ProductSpecification,
+    params: ConnectParams,
+) -> Result<Service, Error> {
+    let result_s = unsafe {
+        let product_json = json!(product).to_string();
+        let params_json = json!(params).to_string();
+
+        let product_c_ptr = CString::new(product_json)?.into_raw();
+        let params_c_ptr = CString::new(params_json)?.into_raw();
+
+        let result_ptr = suseconnect_agama_sys::deactivate_product(params_c_ptr, product_c_ptr);
+
+        // Retake ownership to free memory
+        let _ = CString::from_raw(product_c_ptr);
+        let _ = CString::from_raw(params_c_ptr);
+
+        string_from_ptr(result_ptr)
+    }?;
+
+    parse_response(&result_s)
+}
+
+/// Deregisters the system from SUSE Customer Center (or RMT).
+///
+/// It deactivates all the products and removes the system from the server.
+///
+/// # Arguments
+///
+/// * `params` - Parameters [ConnectParams] for the connection.
+///
+/// # Errors
+///
+/// Returns an `Err` of type [Error] if deregistration fails.
+pub fn deactivate_system(params: ConnectParams) -> Result<(), Error> {
+    let result_s = unsafe {
+        let params_json = json!(params).to_string();
+        let params_c_ptr = CString::new(params_json)?.into_raw();
+
+        let result_ptr = suseconnect_agama_sys::deactivate_system(params_c_ptr);
+        let _ = CString::from_raw(params_c_ptr);
+
+        string_from_ptr(result_ptr)
+    }?;
+
+    let response: Value = serde_json::from_str(&result_s)?;
+    check_error(&response)
 }
 
 /// Default path to the global SUSE Customer Center credentials file.
@@ -508,10 +583,7 @@ pub fn show_product(
     }?;
 
     tracing::info!("show_product result: {result_s}");
-    let response: Value = serde_json::from_str(&result_s)?;
-    check_error(&response)?;
-
-    response.try_into()
+    parse_response(&result_s)
 }
 
 #[cfg(test)]
@@ -731,6 +803,18 @@ mod tests {
         assert!(matches!(result, Err(Error::UnexpectedResponse(_))));
     }
 
+    #[test]
+    fn test_parse_response() {
+        let credentials: Credentials =
+            parse_response(r#"{"credentials": ["user", "secret"]}"#).unwrap();
+        assert_eq!(credentials.login, "user");
+        assert_eq!(credentials.password, "secret");
+
+        let result: Result<Service, _> =
+            parse_response(r#"{"err_type": "APIError", "message": "Not found", "code": 404}"#);
+        assert!(matches!(result, Err(Error::SCCApi { code: 404, .. })));
+    }
+
     #[test]
     fn test_reload_certificates_smoke() {
         // This is a smoke test to ensure the FFI call can be executed
@@ -8,5 +8,7 @@ char * activate_product(char *client_params, char *product, char *email);
 char * write_config(char *client_params);
 char * show_product(char *product, char *client_params);
 char * reload_certificates();
+char * deactivate_product(char *client_params, char *product);
+char * deactivate_system(char *client_params);
 
 #endif
\ No newline at end of file
@@ -22,4 +22,11 @@ unsafe extern "C" {
         client_params: *mut ::std::os::raw::c_char,
     ) -> *mut ::std::os::raw::c_char;
     pub fn reload_certificates() -> *mut ::std::os::raw::c_char;
+    pub fn deactivate_product(
+        client_params: *mut ::std::os::raw::c_char,
+        product: *mut ::std::os::raw::c_char,
+    ) -> *mut ::std::os::raw::c_char;
+    pub fn deactivate_system(
+        client_params: *mut ::std::os::raw::c_char,
+    ) -> *mut ::std::os::raw::c_char;
 }
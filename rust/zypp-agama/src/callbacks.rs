@@ -13,6 +13,7 @@ pub fn empty_progress(_value: i64, _text: String) -> bool {
 // empty init target callback
 pub fn empty_init_target_progress(_text: String, _stage: u32, _total: u32) {}
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ProblemResponse {
     RETRY,
     ABORT,
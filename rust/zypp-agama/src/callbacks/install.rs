@@ -64,7 +64,10 @@ pub trait Callback {
     }
 
     /// Called when the installation of a package finishes.
-    fn package_finish(&self, _package_name: String) {}
+    ///
+    /// It receives the package name, edition ("[epoch:]version-release") and architecture, so
+    /// several versions of the same package (e.g., kernels) can be told apart.
+    fn package_finish(&self, _package_name: String, _edition: String, _arch: String) {}
 
     /// Executes a block of code with the callbacks configured.
     fn with<R, F>(&mut self, block: &mut F) -> R
@@ -81,7 +84,7 @@ pub trait Callback {
         let mut script_problem_call = |description| self.script_problem(description);
         let cb_script_problem = get_script_problem(&script_problem_call);
 
-        let mut finish_call = |name| self.package_finish(name);
+        let mut finish_call = |name, edition, arch| self.package_finish(name, edition, arch);
         let cb_finish = get_package_finish(&finish_call);
 
         let callbacks = zypp_agama_sys::InstallCallbacks {
@@ -159,17 +162,25 @@ where
     Some(script_problem::<F>)
 }
 
-unsafe extern "C" fn package_finish<F>(package_name: *const c_char, user_data: *mut c_void)
-where
-    F: FnMut(String),
+unsafe extern "C" fn package_finish<F>(
+    package_name: *const c_char,
+    edition: *const c_char,
+    arch: *const c_char,
+    user_data: *mut c_void,
+) where
+    F: FnMut(String, String, String),
 {
     let user_data = &mut *(user_data as *mut F);
-    user_data(string_from_ptr(package_name));
+    user_data(
+        string_from_ptr(package_name),
+        string_from_ptr(edition),
+        string_from_ptr(arch),
+    );
 }
 
 fn get_package_finish<F>(_closure: &F) -> zypp_agama_sys::ZyppInstallPackageFinishCallback
 where
-    F: FnMut(String),
+    F: FnMut(String, String, String),
 {
     Some(package_finish::<F>)
 }
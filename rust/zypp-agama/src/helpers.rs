@@ -1,10 +1,26 @@
-use std::os::raw::{c_char, c_void};
+use std::{
+    ffi::CString,
+    os::raw::{c_char, c_void},
+};
 
 // Safety requirements: inherited from https://doc.rust-lang.org/std/ffi/struct.CStr.html#method.from_ptr
 pub(crate) unsafe fn string_from_ptr(c_ptr: *const c_char) -> String {
     String::from_utf8_lossy(std::ffi::CStr::from_ptr(c_ptr).to_bytes()).into_owned()
 }
 
+/// Converts a string to a C string, reporting an error if it contains a NUL character.
+///
+/// * `field`: name of the value, used in the error message.
+/// * `value`: string to convert.
+pub(crate) fn to_c_string(field: &str, value: &str) -> Result<CString, crate::ZyppError> {
+    CString::new(value).map_err(|_| {
+        crate::ZyppError::new(&format!(
+            "Invalid {field} '{}': it contains a NUL character",
+            value.escape_debug()
+        ))
+    })
+}
+
 /// Helper to wrap data into C to be later used from rust callbacks.
 ///
 /// It takes a mutable reference to some data and casts it to a raw pointer
@@ -40,3 +56,19 @@ pub(crate) unsafe fn status_to_result_void(
 ) -> Result<(), crate::ZyppError> {
     status_to_result(status, ())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_c_string() {
+        assert_eq!(to_c_string("name", "vim").unwrap().as_bytes(), b"vim");
+
+        let error = to_c_string("package name", "vim\0").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid package name 'vim\\0': it contains a NUL character"
+        );
+    }
+}
@@ -12,7 +12,7 @@ pub mod errors;
 pub use errors::ZyppError;
 
 mod helpers;
-use helpers::{status_to_result, status_to_result_void, string_from_ptr};
+use helpers::{status_to_result, status_to_result_void, string_from_ptr, to_c_string};
 
 pub mod callbacks;
 
@@ -75,6 +75,34 @@ pub struct Pattern {
     pub selected: ResolvableSelected,
 }
 
+/// Package selected for installation
+#[derive(Debug)]
+pub struct Package {
+    pub name: String,
+    /// Package edition in the "[epoch:]version-release" format
+    pub edition: String,
+    pub arch: String,
+    pub repo_alias: String,
+    /// Package checksum in the "type:value" format, if known
+    pub checksum: Option<String>,
+    /// Size of the RPM file in bytes
+    pub download_size: u64,
+    /// Size of the installed files in bytes
+    pub installed_size: u64,
+}
+
+/// GPG key known by the zypp keyring
+#[derive(Debug)]
+pub struct GpgKey {
+    pub id: String,
+    pub fingerprint: String,
+    pub name: String,
+    /// Expiration as a UNIX timestamp, `None` if the key never expires
+    pub expires: Option<i64>,
+    /// Whether the key is in the trusted keyring
+    pub trusted: bool,
+}
+
 // TODO: is there better way how to use type from ProgressCallback binding type?
 unsafe extern "C" fn zypp_progress_callback<F>(
     zypp_data: ProgressData,
@@ -171,6 +199,7 @@ impl Zypp {
     pub fn commit(
         &self,
         report: &mut impl callbacks::pkg_download::Callback,
+        progress: &impl callbacks::download_progress::Callback,
         install: &mut impl callbacks::install::Callback,
         security: &mut impl callbacks::security::Callback,
     ) -> ZyppResult<bool> {
@@ -178,17 +207,23 @@ impl Zypp {
         let status_ptr = &mut status as *mut _;
         unsafe {
             let res = report.with(&mut |mut report_callback| {
-                install.with(&mut |mut install_callback| {
-                    security.with(&mut |mut sec_callback| {
-                        zypp_agama_sys::commit(
-                            self.ptr,
-                            status_ptr,
-                            &mut report_callback,
-                            &mut sec_callback,
-                            &mut install_callback,
-                        )
-                    })
-                })
+                callbacks::download_progress::with_callback(
+                    progress,
+                    &mut |mut progress_callback| {
+                        install.with(&mut |mut install_callback| {
+                            security.with(&mut |mut sec_callback| {
+                                zypp_agama_sys::commit(
+                                    self.ptr,
+                                    status_ptr,
+                                    &mut report_callback,
+                                    &mut progress_callback,
+                                    &mut sec_callback,
+                                    &mut install_callback,
+                                )
+                            })
+                        })
+                    },
+                )
             });
             helpers::status_to_result(status, res)
         }
@@ -246,6 +281,18 @@ impl Zypp {
         unsafe { zypp_agama_sys::packages_to_install(self.ptr) }
     }
 
+    /// Selects all needed patches for installation and returns how many were selected.
+    ///
+    /// The solver must run before to find out which patches are needed.
+    pub fn select_needed_patches(&self) -> ZyppResult<u32> {
+        let mut status: Status = Status::default();
+        let status_ptr = &mut status as *mut _;
+        unsafe {
+            let count = zypp_agama_sys::select_needed_patches(self.ptr, status_ptr);
+            helpers::status_to_result(status, count)
+        }
+    }
+
     pub fn list_repositories(&self) -> ZyppResult<Vec<Repository>> {
         let mut repos_v = vec![];
 
@@ -308,6 +355,76 @@ impl Zypp {
         }
     }
 
+    /// Lists the packages selected for installation, including the ones selected by the solver.
+    pub fn list_packages_to_install(&self) -> ZyppResult<Vec<Package>> {
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+
+            let packages = zypp_agama_sys::get_packages_to_install(self.ptr, status_ptr);
+            helpers::status_to_result_void(status)?;
+
+            let mut r_packages = Vec::with_capacity(packages.size as usize);
+            for i in 0..packages.size as usize {
+                let c_package = *(packages.list.add(i));
+                let checksum = string_from_ptr(c_package.checksum);
+                let r_package = Package {
+                    name: string_from_ptr(c_package.name),
+                    edition: string_from_ptr(c_package.edition),
+                    arch: string_from_ptr(c_package.arch),
+                    repo_alias: string_from_ptr(c_package.repo_alias),
+                    checksum: if checksum.is_empty() {
+                        None
+                    } else {
+                        Some(checksum)
+                    },
+                    download_size: c_package.download_size,
+                    installed_size: c_package.installed_size,
+                };
+                r_packages.push(r_package);
+            }
+            zypp_agama_sys::free_packages(&packages);
+            Ok(r_packages)
+        }
+    }
+
+    /// Selects an exact package build for installation.
+    ///
+    /// It fails if no such build is available or if its checksum does not match.
+    ///
+    /// * `repo_alias`: repository providing the package, `None` means any repository
+    /// * `checksum`: expected checksum in the "type:value" format, `None` skips the check
+    pub fn select_package_build(
+        &self,
+        name: &str,
+        edition: &str,
+        arch: &str,
+        repo_alias: Option<&str>,
+        checksum: Option<&str>,
+    ) -> ZyppResult<()> {
+        let c_name = to_c_string("package name", name)?;
+        let c_edition = to_c_string("package version", edition)?;
+        let c_arch = to_c_string("package architecture", arch)?;
+        let c_repo_alias = to_c_string("repository alias", repo_alias.unwrap_or_default())?;
+        let c_checksum = to_c_string("checksum", checksum.unwrap_or_default())?;
+
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+            zypp_agama_sys::select_package_build(
+                self.ptr,
+                c_name.as_ptr(),
+                c_edition.as_ptr(),
+                c_arch.as_ptr(),
+                c_repo_alias.as_ptr(),
+                c_checksum.as_ptr(),
+                status_ptr,
+            );
+
+            helpers::status_to_result_void(status)
+        }
+    }
+
     pub fn import_gpg_key(&self, file_path: &str) -> ZyppResult<()> {
         unsafe {
             let mut status: Status = Status::default();
@@ -318,6 +435,79 @@ impl Zypp {
         }
     }
 
+    /// Lists the GPG keys from the trusted and the general keyrings.
+    pub fn list_gpg_keys(&self) -> ZyppResult<Vec<GpgKey>> {
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+
+            let keys = zypp_agama_sys::list_gpg_keys(self.ptr, status_ptr);
+            helpers::status_to_result_void(status)?;
+
+            let mut r_keys = Vec::with_capacity(keys.size as usize);
+            for i in 0..keys.size as usize {
+                let c_key = *(keys.list.add(i));
+                let r_key = GpgKey {
+                    id: string_from_ptr(c_key.id),
+                    fingerprint: string_from_ptr(c_key.fingerprint),
+                    name: string_from_ptr(c_key.name),
+                    expires: (c_key.expires != 0).then_some(c_key.expires),
+                    trusted: c_key.trusted,
+                };
+                r_keys.push(r_key);
+            }
+            zypp_agama_sys::free_gpg_keys(&keys);
+            Ok(r_keys)
+        }
+    }
+
+    /// Removes a GPG key from the keyrings.
+    ///
+    /// * `id`: key id.
+    pub fn remove_gpg_key(&self, id: &str) -> ZyppResult<()> {
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+            let c_id = CString::new(id).expect("CString must not contain internal NUL");
+            zypp_agama_sys::remove_gpg_key(self.ptr, c_id.as_ptr(), status_ptr);
+            status_to_result_void(status)
+        }
+    }
+
+    /// Finds the available packages matching an expression.
+    ///
+    /// It returns the sorted list of unique package names.
+    ///
+    /// * `expression`: capability, glob or regular expression.
+    /// * `r#match`: how to interpret the expression.
+    pub fn find_packages(
+        &self,
+        expression: &str,
+        r#match: PackageMatch,
+    ) -> ZyppResult<Vec<String>> {
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+            let c_expression =
+                CString::new(expression).expect("CString must not contain internal NUL");
+
+            let names = zypp_agama_sys::find_packages(
+                self.ptr,
+                c_expression.as_ptr(),
+                r#match.into(),
+                status_ptr,
+            );
+            helpers::status_to_result_void(status)?;
+
+            let mut r_names = Vec::with_capacity(names.size as usize);
+            for i in 0..names.size as usize {
+                r_names.push(string_from_ptr(*(names.list.add(i))));
+            }
+            zypp_agama_sys::free_package_names(&names);
+            Ok(r_names)
+        }
+    }
+
     pub fn select_resolvable(
         &self,
         name: &str,
@@ -503,6 +693,17 @@ impl Zypp {
         }
     }
 
+    pub fn enable_repository(&self, alias: &str) -> ZyppResult<()> {
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+            let c_alias = CString::new(alias).unwrap();
+            zypp_agama_sys::enable_repository(self.ptr, c_alias.as_ptr(), status_ptr);
+
+            helpers::status_to_result_void(status)
+        }
+    }
+
     pub fn set_repository_url(&self, alias: &str, url: &str) -> ZyppResult<()> {
         unsafe {
             let mut status: Status = Status::default();
@@ -565,6 +766,18 @@ impl Zypp {
         }
     }
 
+    /// Removes the service and all its repositories.
+    pub fn remove_service(&self, alias: &str) -> ZyppResult<()> {
+        unsafe {
+            let mut status: Status = Status::default();
+            let status_ptr = &mut status as *mut _;
+            let c_alias = CString::new(alias).unwrap();
+            zypp_agama_sys::remove_service(self.ptr, c_alias.as_ptr(), status_ptr);
+
+            helpers::status_to_result_void(status)
+        }
+    }
+
     pub fn create_repo_cache<F>(&self, alias: &str, progress: F) -> ZyppResult<()>
     where
         F: FnMut(i64, String) -> bool,
@@ -630,6 +843,23 @@ impl Zypp {
         progress: F,
         security: &mut impl callbacks::security::Callback,
     ) -> ZyppResult<()>
+    where
+        F: Fn(i64, String) -> bool,
+    {
+        self.load_source_skipping_refresh(progress, security, &[])
+    }
+
+    /// Loads the enabled repositories, skipping the refresh of the given ones.
+    ///
+    /// * `progress`: progress callback.
+    /// * `security`: security callback used when refreshing the repositories.
+    /// * `refreshed`: aliases of the repositories which are already refreshed.
+    pub fn load_source_skipping_refresh<F>(
+        &self,
+        progress: F,
+        security: &mut impl callbacks::security::Callback,
+        refreshed: &[String],
+    ) -> ZyppResult<()>
     where
         F: Fn(i64, String) -> bool,
     {
@@ -650,12 +880,16 @@ impl Zypp {
                 return abort_err;
             }
 
-            info!("Refreshing repository {}", &i.alias);
-            self.refresh_repository(
-                &i.alias,
-                &callbacks::download_progress::EmptyCallback,
-                security,
-            )?;
+            if refreshed.contains(&i.alias) {
+                info!("Repository {} is already refreshed", &i.alias);
+            } else {
+                info!("Refreshing repository {}", &i.alias);
+                self.refresh_repository(
+                    &i.alias,
+                    &callbacks::download_progress::EmptyCallback,
+                    security,
+                )?;
+            }
             percent += percent_step;
             cont = progress(
                 percent.floor() as i64,
@@ -733,7 +967,28 @@ impl From<ResolvableKind> for zypp_agama_sys::RESOLVABLE_KIND {
     }
 }
 
-#[derive(Debug)]
+/// How to match the packages in the pool.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PackageMatch {
+    /// Packages providing a capability.
+    Provides,
+    /// Packages whose name matches a glob.
+    Glob,
+    /// Packages whose name matches a regular expression.
+    Regex,
+}
+
+impl From<PackageMatch> for zypp_agama_sys::PACKAGE_MATCH {
+    fn from(value: PackageMatch) -> Self {
+        match value {
+            PackageMatch::Provides => zypp_agama_sys::PACKAGE_MATCH_PACKAGE_MATCH_PROVIDES,
+            PackageMatch::Glob => zypp_agama_sys::PACKAGE_MATCH_PACKAGE_MATCH_GLOB,
+            PackageMatch::Regex => zypp_agama_sys::PACKAGE_MATCH_PACKAGE_MATCH_REGEX,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
 pub enum ResolvableSelected {
     Not,
     User,
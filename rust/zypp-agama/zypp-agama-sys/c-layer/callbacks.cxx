@@ -557,6 +557,8 @@ struct InstallResolvableReport
       return;
     }
     callbacks->package_finish(resolvable->name().c_str(),
+                              resolvable->edition().asString().c_str(),
+                              resolvable->arch().asString().c_str(),
                               callbacks->package_finish_data);
   }
 
@@ -292,9 +292,14 @@ typedef enum PROBLEM_RESPONSE (*ZyppInstallPackageProblemCallback)(
  * @brief Callback invoked when the installation of a package finishes.
  * @note We could add a finish message after package install, but YaST does not.
  * Should we do it with Agama?
+ * @param package_name The name of the package.
+ * @param edition The edition of the package ("[epoch:]version-release").
+ * @param arch The architecture of the package.
  * @param user_data User-defined data.
  */
 typedef void (*ZyppInstallPackageFinishCallback)(const char *package_name,
+                                                 const char *edition,
+                                                 const char *arch,
                                                  void *user_data);
 /**
  * @brief Callback for handling problems in package installation scripts (e.g.,
@@ -65,10 +65,13 @@ void switch_target(struct Zypp *zypp, const char *root,
 /// @param zypp
 /// @param status
 /// @param download_callbacks
+/// @param progress_callbacks reports the progress of the individual file
+/// downloads
 /// @param security_callbacks
 /// @return true if there is no error
 bool commit(struct Zypp *zypp, struct Status *status,
             struct DownloadResolvableCallbacks *download_callbacks,
+            struct DownloadProgressCallbacks *progress_callbacks,
             struct SecurityCallbacks *security_callbacks,
             struct InstallCallbacks *install_callbacks) noexcept;
 
@@ -152,6 +155,15 @@ void resolvable_reset_all(struct Zypp *_zypp) noexcept;
 /// @return count of packages
 unsigned packages_to_install(struct Zypp *_zypp) noexcept;
 
+/// Selects all needed patches for installation. A patch is needed when it fixes
+/// an installed or a selected package. The solver must run before calling this
+/// function to evaluate the patches.
+/// @param zypp see \ref init_target
+/// @param[out] status (will overwrite existing contents)
+/// @return count of the selected patches
+unsigned select_needed_patches(struct Zypp *zypp,
+                               struct Status *status) noexcept;
+
 /// Representation of zypp::Pattern.
 /// https://doc.opensuse.org/projects/libzypp/HEAD/classzypp_1_1Pattern.html
 struct Pattern {
@@ -195,6 +207,44 @@ struct Products get_products(struct Zypp *_zypp,
                              struct Status *status) noexcept;
 void free_products(const struct Products *products) noexcept;
 
+/// Representation of a zypp::Package selected for installation
+struct Package {
+  const char *name;       ///< owned
+  const char *edition;    ///< owned, "[epoch:]version-release"
+  const char *arch;       ///< owned
+  const char *repo_alias; ///< owned
+  const char *checksum;   ///< owned, "type:value" or empty string if unknown
+  unsigned long long download_size;  ///< size of the RPM file in bytes
+  unsigned long long installed_size; ///< size of the installed files in bytes
+};
+
+struct Packages {
+  struct Package *list; ///< owned, *size* items
+  unsigned size;
+};
+
+/// Get details of all packages selected for installation (by user, application
+/// or solver).
+struct Packages get_packages_to_install(struct Zypp *_zypp,
+                                        struct Status *status) noexcept;
+void free_packages(const struct Packages *packages) noexcept;
+
+/// Marks an exact package build for installation. Unlike \ref
+/// resolvable_select it does not let libzypp pick the best candidate.
+/// @param zypp see \ref init_target
+/// @param name package name
+/// @param edition package edition ("[epoch:]version-release")
+/// @param arch package architecture
+/// @param repo_alias alias of the repository providing the package, empty
+/// string means any repository
+/// @param checksum expected package checksum ("type:value"), empty string
+/// skips the check
+/// @param[out] status (will overwrite existing contents)
+void select_package_build(struct Zypp *zypp, const char *name,
+                          const char *edition, const char *arch,
+                          const char *repo_alias, const char *checksum,
+                          struct Status *status) noexcept;
+
 /// Select locale packages to install
 void select_locale(struct Zypp *_zypp, const char *language,
                    const char *country) noexcept;
@@ -202,6 +252,35 @@ void select_locale(struct Zypp *_zypp, const char *language,
 void import_gpg_key(struct Zypp *zypp, const char *const pathname,
                     struct Status *status) noexcept;
 
+/// Representation of a GPG key known by the zypp keyring
+struct GpgKey {
+  const char *id;          ///< owned
+  const char *fingerprint; ///< owned
+  const char *name;        ///< owned
+  long long expires;       ///< expiration as a UNIX timestamp, 0 if it never
+                           ///< expires
+  bool trusted;            ///< whether the key is in the trusted keyring
+};
+
+struct GpgKeys {
+  struct GpgKey *list; ///< owned, *size* items
+  unsigned size;
+};
+
+/// Get the GPG keys from the trusted and the general keyrings.
+/// @param zypp see \ref init_target
+/// @param[out] status (will overwrite existing contents)
+struct GpgKeys list_gpg_keys(struct Zypp *zypp,
+                             struct Status *status) noexcept;
+void free_gpg_keys(const struct GpgKeys *keys) noexcept;
+
+/// Remove a GPG key from the keyrings.
+/// @param zypp see \ref init_target
+/// @param id key id
+/// @param[out] status (will overwrite existing contents)
+void remove_gpg_key(struct Zypp *zypp, const char *id,
+                    struct Status *status) noexcept;
+
 /// check if url has local schema
 /// @param url url to check
 /// @param[out] status (will overwrite existing contents)
@@ -226,6 +305,31 @@ bool is_package_available(struct Zypp *zypp, const char *tag,
 bool is_package_selected(struct Zypp *zypp, const char *tag,
                          struct Status *status) noexcept;
 
+enum PACKAGE_MATCH {
+  /// packages providing the capability (e.g., "webserver")
+  PACKAGE_MATCH_PROVIDES,
+  /// packages whose name matches the glob (e.g., "kernel-firmware-*")
+  PACKAGE_MATCH_GLOB,
+  /// packages whose name matches the regular expression
+  PACKAGE_MATCH_REGEX,
+};
+
+struct PackageNames {
+  const char **list; ///< owned, *size* items, each item is owned too
+  unsigned size;
+};
+
+/// Find the available packages matching an expression
+/// @param zypp see \ref init_target
+/// @param expression capability, glob or regular expression
+/// @param match how to interpret the expression
+/// @param[out] status (will overwrite existing contents)
+/// @return sorted list of unique package names
+struct PackageNames find_packages(struct Zypp *zypp, const char *expression,
+                                  enum PACKAGE_MATCH match,
+                                  struct Status *status) noexcept;
+void free_package_names(const struct PackageNames *names) noexcept;
+
 /// Runs solver
 /// @param zypp see \ref init_target
 /// @param only_required if true, only required packages are installed (ignoring
@@ -50,6 +50,12 @@ void add_repository(struct Zypp *zypp, const char *alias, const char *url,
 void disable_repository(struct Zypp *zypp, const char *alias,
                         struct Status *status) noexcept;
 
+/// Enable repository in repo manager
+/// @param zypp see \ref init_target
+/// @param alias identifier of repository
+void enable_repository(struct Zypp *zypp, const char *alias,
+                       struct Status *status) noexcept;
+
 /// Changes url of given repository
 /// @param zypp see \ref init_target
 /// @param alias identifier of repository
@@ -94,6 +100,13 @@ void refresh_repository(struct Zypp *zypp, const char *alias,
 void refresh_service(struct Zypp *zypp, const char *alias,
                      struct Status *status) noexcept;
 
+/// Removes service and all its repositories from repo manager
+/// @param zypp see \ref init_target
+/// @param alias alias of service to remove
+/// @param[out] status (will overwrite existing contents)
+void remove_service(struct Zypp *zypp, const char *alias,
+                    struct Status *status) noexcept;
+
 void build_repository_cache(struct Zypp *zypp, const char *alias,
                             struct Status *status,
                             ZyppProgressCallback callback,
@@ -7,10 +7,14 @@
 #include <cstddef>
 #include <cstdlib>
 #include <exception>
+#include <set>
+#include <vector>
 #include <zypp-core/Pathname.h>
 #include <zypp-core/Url.h>
 #include <zypp/DiskUsageCounter.h>
+#include <zypp/Package.h>
 #include <zypp/Pattern.h>
+#include <zypp/PoolQuery.h>
 #include <zypp/Product.h>
 #include <zypp/PublicKey.h>
 #include <zypp/RepoInfo.h>
@@ -27,6 +31,7 @@
 #include <zypp/base/LogControl.h>
 #include <zypp/base/Logger.h>
 
+#include <zypp/sat/WhatProvides.h>
 #include <zypp/ui/Selectable.h>
 
 extern "C" {
@@ -207,12 +212,14 @@ void switch_target(struct Zypp *zypp, const char *root,
 
 bool commit(struct Zypp *zypp, struct Status *status,
             struct DownloadResolvableCallbacks *download_callbacks,
+            struct DownloadProgressCallbacks *progress_callbacks,
             struct SecurityCallbacks *security_callbacks,
             struct InstallCallbacks *install_callbacks) noexcept {
   LOG_LOCATION("Starting package installation");
 
   try {
     set_zypp_resolvable_download_callbacks(download_callbacks);
+    set_zypp_download_callbacks(progress_callbacks);
     set_zypp_security_callbacks(security_callbacks);
     set_zypp_install_callbacks(install_callbacks);
     zypp::ZYppCommitPolicy policy;
@@ -222,12 +229,14 @@ bool commit(struct Zypp *zypp, struct Status *status,
     zypp::ZYppCommitResult result = zypp->zypp_pointer->commit(policy);
     STATUS_OK(status);
     unset_zypp_resolvable_download_callbacks();
+    unset_zypp_download_callbacks();
     unset_zypp_security_callbacks();
     unset_zypp_install_callbacks();
     return result.allDone();
   } catch (zypp::Exception &excpt) {
     STATUS_EXCEPT(status, excpt);
     unset_zypp_resolvable_download_callbacks();
+    unset_zypp_download_callbacks();
     unset_zypp_security_callbacks();
     unset_zypp_install_callbacks();
     return false;
@@ -520,6 +529,120 @@ void free_products(const struct Products *products) noexcept {
   free((void *)products->list);
 }
 
+static std::string checksum_string(const zypp::CheckSum &checksum) {
+  if (checksum.empty())
+    return "";
+
+  return checksum.type() + ":" + checksum.checksum();
+}
+
+struct Packages get_packages_to_install(struct Zypp *zypp,
+                                        struct Status *status) noexcept {
+  LOG_LOCATION("Getting packages to install");
+
+  try {
+    std::vector<zypp::PoolItem> items;
+    for (const auto &item :
+         zypp->zypp_pointer->pool().byKind(zypp::ResKind::package)) {
+      if (item.status().isToBeInstalled())
+        items.push_back(item);
+    }
+
+    Packages result = {
+        (struct Package *)malloc(items.size() * sizeof(Package)),
+        0 // initialize with zero and increase after each successful add of
+          // package info
+    };
+
+    for (const auto &item : items) {
+      Package &package = result.list[result.size];
+      auto zypp_package = zypp::asKind<zypp::Package>(item.resolvable());
+      package.name = strdup(item->name().c_str());
+      package.edition = strdup(item->edition().asString().c_str());
+      package.arch = strdup(item->arch().asString().c_str());
+      package.repo_alias = strdup(item->repoInfo().alias().c_str());
+      package.checksum =
+          strdup(checksum_string(zypp_package->checksum()).c_str());
+      package.download_size = zypp_package->downloadSize();
+      package.installed_size = zypp_package->installSize();
+      result.size++;
+    }
+
+    STATUS_OK(status);
+    return result;
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+    return {NULL, 0};
+  }
+}
+
+void free_packages(const struct Packages *packages) noexcept {
+  for (unsigned i = 0; i < packages->size; ++i) {
+    free((void *)packages->list[i].name);
+    free((void *)packages->list[i].edition);
+    free((void *)packages->list[i].arch);
+    free((void *)packages->list[i].repo_alias);
+    free((void *)packages->list[i].checksum);
+  }
+
+  free((void *)packages->list);
+}
+
+void select_package_build(struct Zypp *zypp, const char *name,
+                          const char *edition, const char *arch,
+                          const char *repo_alias, const char *checksum,
+                          struct Status *status) noexcept {
+  std::string message("Selecting package build: ");
+  message.append(name);
+  message.append("-");
+  message.append(edition);
+  message.append(".");
+  message.append(arch);
+  LOG_LOCATION(message.c_str());
+
+  try {
+    auto selectable = zypp::ui::Selectable::get(zypp::ResKind::package, name);
+    if (!selectable) {
+      STATUS_ERROR(status, "Failed to find package with name '%s'", name);
+      return;
+    }
+
+    zypp::Edition z_edition(edition);
+    zypp::Arch z_arch(arch);
+    std::string s_repo_alias(repo_alias);
+    std::string s_checksum(checksum);
+
+    for (const auto &item : selectable->available()) {
+      if (item->edition() != z_edition || item->arch() != z_arch)
+        continue;
+      if (!s_repo_alias.empty() && item->repoInfo().alias() != s_repo_alias)
+        continue;
+
+      if (!s_checksum.empty()) {
+        auto zypp_package = zypp::asKind<zypp::Package>(item.resolvable());
+        std::string found = checksum_string(zypp_package->checksum());
+        if (found != s_checksum) {
+          STATUS_ERROR(status,
+                       "Checksum mismatch for package %s-%s.%s: expected %s, "
+                       "found %s",
+                       name, edition, arch, checksum, found.c_str());
+          return;
+        }
+      }
+
+      selectable->setCandidate(item, zypp::ResStatus::USER);
+      selectable->setToInstall(zypp::ResStatus::USER);
+      STATUS_OK(status);
+      return;
+    }
+
+    STATUS_ERROR(status, "Package %s-%s.%s not found in repository '%s'", name,
+                 edition, arch, repo_alias);
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+  }
+}
+
 void select_locale(struct Zypp *_zypp, const char *language,
                    const char *country) noexcept {
   LOG_LOCATION("Selecting locale packages");
@@ -634,6 +757,32 @@ void refresh_service(struct Zypp *zypp, const char *alias,
   }
 }
 
+void remove_service(struct Zypp *zypp, const char *alias,
+                    struct Status *status) noexcept {
+  std::string message("Removing service: ");
+  message.append(alias);
+  LOG_LOCATION(message.c_str());
+
+  if (zypp->repo_manager == NULL) {
+    STATUS_ERROR(status, "Internal Error: Repo manager is not initialized.");
+    return;
+  }
+  try {
+    zypp::ServiceInfo service = zypp->repo_manager->getService(alias);
+    if (service == zypp::ServiceInfo::noService) {
+      STATUS_ERROR(status,
+                   "Cannot remove service with alias %s. Service not found.",
+                   alias);
+      return;
+    }
+    // it removes also the repositories belonging to the service
+    zypp->repo_manager->removeService(service);
+    STATUS_OK(status);
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+  }
+}
+
 void refresh_repository(struct Zypp *zypp, const char *alias,
                         struct Status *status,
                         struct DownloadProgressCallbacks *callbacks,
@@ -688,6 +837,28 @@ unsigned packages_to_install(struct Zypp *zypp) noexcept {
       .size();
 }
 
+unsigned select_needed_patches(struct Zypp *zypp,
+                               struct Status *status) noexcept {
+  LOG_LOCATION("Selecting needed patches");
+
+  try {
+    unsigned count = 0;
+    for (const auto &item :
+         zypp->zypp_pointer->pool().byKind(zypp::ResKind::patch)) {
+      if (item.isNeeded() && !item.status().isLocked()) {
+        item.status().setToBeInstalled(zypp::ResStatus::USER);
+        count++;
+      }
+    }
+
+    STATUS_OK(status);
+    return count;
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+    return 0;
+  }
+}
+
 static bool package_check(Zypp *zypp, const char *tag, bool selected,
                           Status *status) noexcept {
   std::string message("Checking package: ");
@@ -737,6 +908,58 @@ bool is_package_selected(Zypp *zypp, const char *tag, Status *status) noexcept {
   return package_check(zypp, tag, true, status);
 }
 
+struct PackageNames find_packages(struct Zypp *zypp, const char *expression,
+                                  enum PACKAGE_MATCH match,
+                                  struct Status *status) noexcept {
+  std::string message("Finding packages matching: ");
+  message.append(expression);
+  LOG_LOCATION(message.c_str());
+
+  try {
+    std::set<std::string> names;
+    if (match == PACKAGE_MATCH_PROVIDES) {
+      zypp::Capability capability(expression);
+      for (const auto &solvable : zypp::sat::WhatProvides(capability)) {
+        if (solvable.isKind(zypp::ResKind::package))
+          names.insert(solvable.name());
+      }
+    } else {
+      zypp::PoolQuery query;
+      query.addKind(zypp::ResKind::package);
+      query.addAttribute(zypp::sat::SolvAttr::name, expression);
+      if (match == PACKAGE_MATCH_GLOB)
+        query.setMatchGlob();
+      else
+        query.setMatchRegex();
+      query.setCaseSensitive(true);
+
+      for (const auto &solvable : query)
+        names.insert(solvable.name());
+    }
+
+    PackageNames result = {
+        (const char **)malloc(names.size() * sizeof(const char *)), 0};
+    for (const auto &name : names) {
+      result.list[result.size] = strdup(name.c_str());
+      result.size++;
+    }
+
+    STATUS_OK(status);
+    return result;
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+    return {NULL, 0};
+  }
+}
+
+void free_package_names(const struct PackageNames *names) noexcept {
+  for (unsigned i = 0; i < names->size; ++i) {
+    free((void *)names->list[i]);
+  }
+
+  free((void *)names->list);
+}
+
 void add_repository(struct Zypp *zypp, const char *alias, const char *url,
                     unsigned priority, struct Status *status,
                     ZyppProgressCallback callback, void *user_data) noexcept {
@@ -785,6 +1008,26 @@ void disable_repository(struct Zypp *zypp, const char *alias,
   }
 }
 
+void enable_repository(struct Zypp *zypp, const char *alias,
+                       struct Status *status) noexcept {
+  std::string message("Enabling repository: ");
+  message.append(alias);
+  LOG_LOCATION(message.c_str());
+
+  if (zypp->repo_manager == NULL) {
+    STATUS_ERROR(status, "Internal Error: Repo manager is not initialized.");
+    return;
+  }
+  try {
+    zypp::RepoInfo r_info = zypp->repo_manager->getRepo(alias);
+    r_info.setEnabled(true);
+    zypp->repo_manager->modifyRepository(r_info);
+    STATUS_OK(status);
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+  }
+}
+
 void set_repository_url(struct Zypp *zypp, const char *alias, const char *url,
                         struct Status *status) noexcept {
 
@@ -939,6 +1182,70 @@ void import_gpg_key(struct Zypp *zypp, const char *const pathname,
   }
 }
 
+struct GpgKeys list_gpg_keys(struct Zypp *zypp,
+                             struct Status *status) noexcept {
+  LOG_LOCATION("Listing GPG keys");
+
+  try {
+    auto key_ring = zypp->zypp_pointer->keyRing();
+    std::vector<std::pair<zypp::PublicKeyData, bool>> keys;
+    for (const auto &key : key_ring->trustedPublicKeyData())
+      keys.push_back({key, true});
+    for (const auto &key : key_ring->publicKeyData())
+      keys.push_back({key, false});
+
+    GpgKeys result = {
+        (struct GpgKey *)malloc(keys.size() * sizeof(GpgKey)), 0};
+
+    for (const auto &[key, trusted] : keys) {
+      GpgKey &gpg_key = result.list[result.size];
+      gpg_key.id = strdup(key.id().c_str());
+      gpg_key.fingerprint = strdup(key.fingerprint().c_str());
+      gpg_key.name = strdup(key.name().c_str());
+      gpg_key.expires = key.expires();
+      gpg_key.trusted = trusted;
+      result.size++;
+    }
+
+    STATUS_OK(status);
+    return result;
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+    return {NULL, 0};
+  }
+}
+
+void free_gpg_keys(const struct GpgKeys *keys) noexcept {
+  for (unsigned i = 0; i < keys->size; ++i) {
+    free((void *)keys->list[i].id);
+    free((void *)keys->list[i].fingerprint);
+    free((void *)keys->list[i].name);
+  }
+
+  free((void *)keys->list);
+}
+
+void remove_gpg_key(struct Zypp *zypp, const char *id,
+                    struct Status *status) noexcept {
+  std::string message("Removing GPG key: ");
+  message.append(id);
+  LOG_LOCATION(message.c_str());
+
+  try {
+    auto key_ring = zypp->zypp_pointer->keyRing();
+    std::string key_id(id);
+    if (!key_ring->isKeyKnown(key_id)) {
+      STATUS_ERROR(status, "GPG key '%s' not found", id);
+      return;
+    }
+
+    key_ring->deleteKey(key_id, key_ring->isKeyTrusted(key_id));
+    STATUS_OK(status);
+  } catch (zypp::Exception &excpt) {
+    STATUS_EXCEPT(status, excpt);
+  }
+}
+
 void get_space_usage(struct Zypp *zypp, struct Status *status,
                      struct MountPoint *mount_points,
                      unsigned mount_points_size) noexcept {
@@ -965,7 +1272,7 @@ void get_space_usage(struct Zypp *zypp, struct Status *status,
                        [mount_points, i](zypp::DiskUsageCounter::MountPoint m) {
                          return m.dir == mount_points[i].directory;
                        });
-      if (mp == mount_points_set.end()) {
+      if (mp == computed_set.end()) {
         // mount point not found. Should not happen.
         STATUS_ERROR(status, "Internal Error:Mount point not found.");
         return;
@@ -350,10 +350,12 @@ pub type ZyppInstallPackageProblemCallback = ::std::option::Option<
         user_data: *mut ::std::os::raw::c_void,
     ) -> PROBLEM_RESPONSE,
 >;
-#[doc = " @brief Callback invoked when the installation of a package finishes.\n @note We could add a finish message after package install, but YaST does not.\n Should we do it with Agama?\n @param user_data User-defined data."]
+#[doc = " @brief Callback invoked when the installation of a package finishes.\n @note We could add a finish message after package install, but YaST does not.\n Should we do it with Agama?\n @param package_name The name of the package.\n @param edition The edition of the package (\"[epoch:]version-release\").\n @param arch The architecture of the package.\n @param user_data User-defined data."]
 pub type ZyppInstallPackageFinishCallback = ::std::option::Option<
     unsafe extern "C" fn(
         package_name: *const ::std::os::raw::c_char,
+        edition: *const ::std::os::raw::c_char,
+        arch: *const ::std::os::raw::c_char,
         user_data: *mut ::std::os::raw::c_void,
     ),
 >;
@@ -555,6 +557,113 @@ const _: () = {
     ["Offset of field: Products::list"][::std::mem::offset_of!(Products, list) - 0usize];
     ["Offset of field: Products::size"][::std::mem::offset_of!(Products, size) - 8usize];
 };
+#[doc = " Representation of a zypp::Package selected for installation"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Package {
+    #[doc = "< owned"]
+    pub name: *const ::std::os::raw::c_char,
+    #[doc = "< owned, \"[epoch:]version-release\""]
+    pub edition: *const ::std::os::raw::c_char,
+    #[doc = "< owned"]
+    pub arch: *const ::std::os::raw::c_char,
+    #[doc = "< owned"]
+    pub repo_alias: *const ::std::os::raw::c_char,
+    #[doc = "< owned, \"type:value\" or empty string if unknown"]
+    pub checksum: *const ::std::os::raw::c_char,
+    #[doc = "< size of the RPM file in bytes"]
+    pub download_size: ::std::os::raw::c_ulonglong,
+    #[doc = "< size of the installed files in bytes"]
+    pub installed_size: ::std::os::raw::c_ulonglong,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of Package"][::std::mem::size_of::<Package>() - 56usize];
+    ["Alignment of Package"][::std::mem::align_of::<Package>() - 8usize];
+    ["Offset of field: Package::name"][::std::mem::offset_of!(Package, name) - 0usize];
+    ["Offset of field: Package::edition"][::std::mem::offset_of!(Package, edition) - 8usize];
+    ["Offset of field: Package::arch"][::std::mem::offset_of!(Package, arch) - 16usize];
+    ["Offset of field: Package::repo_alias"][::std::mem::offset_of!(Package, repo_alias) - 24usize];
+    ["Offset of field: Package::checksum"][::std::mem::offset_of!(Package, checksum) - 32usize];
+    ["Offset of field: Package::download_size"]
+        [::std::mem::offset_of!(Package, download_size) - 40usize];
+    ["Offset of field: Package::installed_size"]
+        [::std::mem::offset_of!(Package, installed_size) - 48usize];
+};
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Packages {
+    #[doc = "< owned, *size* items"]
+    pub list: *mut Package,
+    pub size: ::std::os::raw::c_uint,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of Packages"][::std::mem::size_of::<Packages>() - 16usize];
+    ["Alignment of Packages"][::std::mem::align_of::<Packages>() - 8usize];
+    ["Offset of field: Packages::list"][::std::mem::offset_of!(Packages, list) - 0usize];
+    ["Offset of field: Packages::size"][::std::mem::offset_of!(Packages, size) - 8usize];
+};
+#[doc = " Representation of a GPG key known by the zypp keyring"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GpgKey {
+    #[doc = "< owned"]
+    pub id: *const ::std::os::raw::c_char,
+    #[doc = "< owned"]
+    pub fingerprint: *const ::std::os::raw::c_char,
+    #[doc = "< owned"]
+    pub name: *const ::std::os::raw::c_char,
+    #[doc = "< expiration as a UNIX timestamp, 0 if it never\n< expires"]
+    pub expires: ::std::os::raw::c_longlong,
+    #[doc = "< whether the key is in the trusted keyring"]
+    pub trusted: bool,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of GpgKey"][::std::mem::size_of::<GpgKey>() - 40usize];
+    ["Alignment of GpgKey"][::std::mem::align_of::<GpgKey>() - 8usize];
+    ["Offset of field: GpgKey::id"][::std::mem::offset_of!(GpgKey, id) - 0usize];
+    ["Offset of field: GpgKey::fingerprint"][::std::mem::offset_of!(GpgKey, fingerprint) - 8usize];
+    ["Offset of field: GpgKey::name"][::std::mem::offset_of!(GpgKey, name) - 16usize];
+    ["Offset of field: GpgKey::expires"][::std::mem::offset_of!(GpgKey, expires) - 24usize];
+    ["Offset of field: GpgKey::trusted"][::std::mem::offset_of!(GpgKey, trusted) - 32usize];
+};
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct GpgKeys {
+    #[doc = "< owned, *size* items"]
+    pub list: *mut GpgKey,
+    pub size: ::std::os::raw::c_uint,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of GpgKeys"][::std::mem::size_of::<GpgKeys>() - 16usize];
+    ["Alignment of GpgKeys"][::std::mem::align_of::<GpgKeys>() - 8usize];
+    ["Offset of field: GpgKeys::list"][::std::mem::offset_of!(GpgKeys, list) - 0usize];
+    ["Offset of field: GpgKeys::size"][::std::mem::offset_of!(GpgKeys, size) - 8usize];
+};
+#[doc = " packages providing the capability (e.g., \"webserver\")"]
+pub const PACKAGE_MATCH_PACKAGE_MATCH_PROVIDES: PACKAGE_MATCH = 0;
+#[doc = " packages whose name matches the glob (e.g., \"kernel-firmware-*\")"]
+pub const PACKAGE_MATCH_PACKAGE_MATCH_GLOB: PACKAGE_MATCH = 1;
+#[doc = " packages whose name matches the regular expression"]
+pub const PACKAGE_MATCH_PACKAGE_MATCH_REGEX: PACKAGE_MATCH = 2;
+pub type PACKAGE_MATCH = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct PackageNames {
+    #[doc = "< owned, *size* items, each item is owned too"]
+    pub list: *mut *const ::std::os::raw::c_char,
+    pub size: ::std::os::raw::c_uint,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of PackageNames"][::std::mem::size_of::<PackageNames>() - 16usize];
+    ["Alignment of PackageNames"][::std::mem::align_of::<PackageNames>() - 8usize];
+    ["Offset of field: PackageNames::list"][::std::mem::offset_of!(PackageNames, list) - 0usize];
+    ["Offset of field: PackageNames::size"][::std::mem::offset_of!(PackageNames, size) - 8usize];
+};
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Repository {
@@ -612,11 +721,12 @@ unsafe extern "C" {
     ) -> *mut Zypp;
     #[doc = " Switch Zypp target (where to install packages to).\n @param root\n @param[out] status"]
     pub fn switch_target(zypp: *mut Zypp, root: *const ::std::os::raw::c_char, status: *mut Status);
-    #[doc = " Commit zypp settings and install\n TODO: install callbacks\n @param zypp\n @param status\n @param download_callbacks\n @param security_callbacks\n @return true if there is no error"]
+    #[doc = " Commit zypp settings and install\n TODO: install callbacks\n @param zypp\n @param status\n @param download_callbacks\n @param progress_callbacks reports the progress of the individual file\n downloads\n @param security_callbacks\n @return true if there is no error"]
     pub fn commit(
         zypp: *mut Zypp,
         status: *mut Status,
         download_callbacks: *mut DownloadResolvableCallbacks,
+        progress_callbacks: *mut DownloadProgressCallbacks,
         security_callbacks: *mut SecurityCallbacks,
         install_callbacks: *mut InstallCallbacks,
     ) -> bool;
@@ -647,12 +757,27 @@ unsafe extern "C" {
     pub fn resolvable_reset_all(_zypp: *mut Zypp);
     #[doc = " @brief Amount of packages selected for installation\n @param _zypp see \\ref init_target\n @return count of packages"]
     pub fn packages_to_install(_zypp: *mut Zypp) -> ::std::os::raw::c_uint;
+    #[doc = " Selects all needed patches for installation. A patch is needed when it fixes\n an installed or a selected package. The solver must run before calling this\n function to evaluate the patches.\n @param zypp see \\ref init_target\n @param[out] status (will overwrite existing contents)\n @return count of the selected patches"]
+    pub fn select_needed_patches(zypp: *mut Zypp, status: *mut Status) -> ::std::os::raw::c_uint;
     #[doc = " Get Pattern details.\n Unknown patterns are simply omitted from the result. Match by\n PatternInfo.name, not by index."]
     pub fn get_patterns(_zypp: *mut Zypp, status: *mut Status) -> Patterns;
     pub fn free_patterns(patterns: *const Patterns);
     #[doc = " Get Product details."]
     pub fn get_products(_zypp: *mut Zypp, status: *mut Status) -> Products;
     pub fn free_products(products: *const Products);
+    #[doc = " Get details of all packages selected for installation (by user, application\n or solver)."]
+    pub fn get_packages_to_install(_zypp: *mut Zypp, status: *mut Status) -> Packages;
+    pub fn free_packages(packages: *const Packages);
+    #[doc = " Marks an exact package build for installation. Unlike \\ref\n resolvable_select it does not let libzypp pick the best candidate.\n @param zypp see \\ref init_target\n @param name package name\n @param edition package edition (\"[epoch:]version-release\")\n @param arch package architecture\n @param repo_alias alias of the repository providing the package, empty\n string means any repository\n @param checksum expected package checksum (\"type:value\"), empty string\n skips the check\n @param[out] status (will overwrite existing contents)"]
+    pub fn select_package_build(
+        zypp: *mut Zypp,
+        name: *const ::std::os::raw::c_char,
+        edition: *const ::std::os::raw::c_char,
+        arch: *const ::std::os::raw::c_char,
+        repo_alias: *const ::std::os::raw::c_char,
+        checksum: *const ::std::os::raw::c_char,
+        status: *mut Status,
+    );
     #[doc = " Select locale packages to install"]
     pub fn select_locale(
         _zypp: *mut Zypp,
@@ -664,6 +789,19 @@ unsafe extern "C" {
         pathname: *const ::std::os::raw::c_char,
         status: *mut Status,
     );
+    #[doc = " Get the GPG keys from the trusted and the general keyrings.\n @param zypp see \\ref init_target\n @param[out] status (will overwrite existing contents)"]
+    pub fn list_gpg_keys(zypp: *mut Zypp, status: *mut Status) -> GpgKeys;
+    pub fn free_gpg_keys(keys: *const GpgKeys);
+    #[doc = " Find the available packages matching an expression\n @param zypp see \\ref init_target\n @param expression capability, glob or regular expression\n @param match how to interpret the expression\n @param[out] status (will overwrite existing contents)\n @return sorted list of unique package names"]
+    pub fn find_packages(
+        zypp: *mut Zypp,
+        expression: *const ::std::os::raw::c_char,
+        match_: PACKAGE_MATCH,
+        status: *mut Status,
+    ) -> PackageNames;
+    pub fn free_package_names(names: *const PackageNames);
+    #[doc = " Remove a GPG key from the keyrings.\n @param zypp see \\ref init_target\n @param id key id\n @param[out] status (will overwrite existing contents)"]
+    pub fn remove_gpg_key(zypp: *mut Zypp, id: *const ::std::os::raw::c_char, status: *mut Status);
     #[doc = " check if url has local schema\n @param url url to check\n @param[out] status (will overwrite existing contents)\n @return true if url is local, for invalid url status is set to error"]
     pub fn is_local_url(url: *const ::std::os::raw::c_char, status: *mut Status) -> bool;
     #[doc = " check if package is available\n @param zypp see \\ref init_target\n @param tag package name, provides or file path\n @param[out] status (will overwrite existing contents)\n @return true if package is available. In case of error it fills status and\n return value is undefined"]
@@ -703,6 +841,12 @@ unsafe extern "C" {
         alias: *const ::std::os::raw::c_char,
         status: *mut Status,
     );
+    #[doc = " Enable repository in repo manager\n @param zypp see \\ref init_target\n @param alias identifier of repository"]
+    pub fn enable_repository(
+        zypp: *mut Zypp,
+        alias: *const ::std::os::raw::c_char,
+        status: *mut Status,
+    );
     #[doc = " Changes url of given repository\n @param zypp see \\ref init_target\n @param alias identifier of repository\n @param alias have to be unique"]
     pub fn set_repository_url(
         zypp: *mut Zypp,
@@ -739,6 +883,12 @@ unsafe extern "C" {
         alias: *const ::std::os::raw::c_char,
         status: *mut Status,
     );
+    #[doc = " Removes service and all its repositories from repo manager\n @param zypp see \\ref init_target\n @param alias alias of service to remove\n @param[out] status (will overwrite existing contents)"]
+    pub fn remove_service(
+        zypp: *mut Zypp,
+        alias: *const ::std::os::raw::c_char,
+        status: *mut Status,
+    );
     pub fn build_repository_cache(
         zypp: *mut Zypp,
         alias: *const ::std::os::raw::c_char,
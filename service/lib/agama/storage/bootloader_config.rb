@@ -60,6 +60,13 @@ module Agama
       # @return [Hash<String, String>]
       attr_accessor :scoped_kernel_params
 
+      # Flavour of the installed kernel (e.g., "rt"), used to select the default boot entry.
+      #
+      # It is an internal setting, so it is not exported.
+      #
+      # @return [String, nil] if nil, the default boot entry is kept
+      attr_accessor :kernel_flavor
+
       # Keys to export to JSON.
       #
       # As both previous keys are conflicting, remember which one to set or none. It can be empty
@@ -105,6 +112,7 @@ module Agama
         update_attribute(hsh, :updateNvram, :update_nvram)
 
         self.scoped_kernel_params = hsh[:kernelArgs]
+        self.kernel_flavor = hsh[:kernelFlavor]
 
         self
       end
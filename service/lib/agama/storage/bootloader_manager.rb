@@ -23,6 +23,8 @@ require "agama/storage/bootloader_config"
 require "agama/storage/bootloader_config_solver"
 require "agama/storage/bootloader_prober"
 require "bootloader/bootloader_factory"
+require "bootloader/sections"
+require "cfa/grub2/grub_cfg"
 require "yast"
 
 Yast.import "BootStorage"
@@ -83,6 +85,7 @@ module Agama
       # It writes the bootloader configuration to the system.
       def install
         Yast::WFM.CallFunction("inst_bootloader", [])
+        write_default_section
       end
 
       # Required packages.
@@ -111,6 +114,38 @@ module Agama
         bootloader
       end
 
+      # Bootloaders supporting a default boot entry.
+      SECTIONS_BOOTLOADERS = ["grub2", "grub2-efi"].freeze
+      private_constant :SECTIONS_BOOTLOADERS
+
+      # Makes the boot entry of the selected kernel flavour the default one.
+      #
+      # The boot entries are only known once the kernel is installed and the bootloader
+      # configuration is written, so it cannot be done as part of the proposal.
+      def write_default_section
+        flavor = @config.kernel_flavor
+        return if flavor.nil? || flavor == "default"
+
+        bootloader = ::Bootloader::BootloaderFactory.current
+        unless SECTIONS_BOOTLOADERS.include?(bootloader.name)
+          @logger.info "bootloader #{bootloader.name} does not support a default entry"
+          return
+        end
+
+        grub_cfg = ::CFA::Grub2::GrubCfg.new
+        grub_cfg.load
+        sections = ::Bootloader::Sections.new(grub_cfg)
+        section = sections.all.find { |s| s.match?(/-#{Regexp.escape(flavor)}\b/) }
+        if section.nil?
+          @logger.warn "no boot entry found for the #{flavor} kernel"
+          return
+        end
+
+        @logger.info "default boot entry: #{section}"
+        sections.default = section
+        sections.write
+      end
+
       def write_nvram(bootloader)
         return if @config.update_nvram.nil?
 
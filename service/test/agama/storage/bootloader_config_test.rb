@@ -63,5 +63,11 @@ describe Agama::Storage::BootloaderConfig do
       config.load_json(content)
       expect(config.keys_to_export).to eq([:timeout])
     end
+
+    it "loads the kernel flavor without exporting it" do
+      config.load_json({ kernelFlavor: "rt" })
+      expect(config.kernel_flavor).to eq "rt"
+      expect(config.to_json).to eq({})
+    end
   end
 end
@@ -324,4 +324,38 @@ describe Agama::Storage::BootloaderManager do
       end
     end
   end
+
+  describe "#write_default_section" do
+    let(:grub_cfg) { instance_double(::CFA::Grub2::GrubCfg, load: nil) }
+    let(:sections) do
+      instance_double(
+        ::Bootloader::Sections,
+        all: ["openSUSE Tumbleweed", "openSUSE Tumbleweed, with Linux 6.12.0-1-rt"]
+      )
+    end
+
+    before do
+      allow(::CFA::Grub2::GrubCfg).to receive(:new).and_return(grub_cfg)
+      allow(::Bootloader::Sections).to receive(:new).with(grub_cfg).and_return(sections)
+    end
+
+    context "when a kernel flavor is set" do
+      before do
+        agama_bootloader.config.kernel_flavor = "rt"
+      end
+
+      it "sets the entry of that kernel as the default one" do
+        expect(sections).to receive(:default=).with("openSUSE Tumbleweed, with Linux 6.12.0-1-rt")
+        expect(sections).to receive(:write)
+        agama_bootloader.send(:write_default_section)
+      end
+    end
+
+    context "when no kernel flavor is set" do
+      it "does not change the default entry" do
+        expect(sections).to_not receive(:default=)
+        agama_bootloader.send(:write_default_section)
+      end
+    end
+  end
 end
@@ -84,6 +84,13 @@ const probeAction = (only?: Scope[]) => postAction({ probe: { only } });
 
 const discoverISCSIAction = (config: DiscoverISCSIConfig) => postAction({ discoverISCSI: config });
 
+const deregisterAction = () => postAction({ deregister: null });
+
+const deregisterAddonAction = (id: string) => postAction({ deregisterAddon: { id } });
+
+const switchRegistrationServerAction = (url?: string, code?: string) =>
+  postAction({ switchRegistrationServer: { url, code } });
+
 const startInstallation = () => postAction({ install: null });
 
 const finishInstallation = () => postAction({ finish: "reboot" });
@@ -119,6 +126,9 @@ export {
   activateStorageAction,
   probeAction,
   discoverISCSIAction,
+  deregisterAction,
+  deregisterAddonAction,
+  switchRegistrationServerAction,
   startInstallation,
   finishInstallation,
   passwordCheck,
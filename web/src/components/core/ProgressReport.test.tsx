@@ -90,4 +90,35 @@ describe("ProgressReport", () => {
       expect(screen.getByText(/Step 5 of 200/)).toBeInTheDocument();
     });
   });
+
+  describe("when the software progress includes the processed bytes", () => {
+    beforeEach(() => {
+      mockProgresses([
+        {
+          scope: "manager",
+          step: "Installing software",
+          index: 2,
+          size: 3,
+          steps: ["Partition disks", "Install software", "Install bootloader"],
+        },
+        {
+          scope: "software",
+          step: "Installing vim",
+          index: 5,
+          size: 200,
+          steps: ["Installing vim"],
+          totalBytes: 2048,
+          doneBytes: 1024,
+          eta: 90,
+        },
+      ]);
+    });
+
+    it("shows the processed bytes and the estimated remaining time", () => {
+      plainRender(<ProgressReport />);
+
+      expect(screen.getByText(/1.00 KiB of 2.00 KiB/)).toBeInTheDocument();
+      expect(screen.getByText(/about 2 minutes left/)).toBeInTheDocument();
+    });
+  });
 });
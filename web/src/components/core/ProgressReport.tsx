@@ -22,6 +22,7 @@
 
 import React from "react";
 import { sprintf } from "sprintf-js";
+import xbytes from "xbytes";
 import {
   Flex,
   ProgressStep,
@@ -35,6 +36,29 @@ import { _ } from "~/i18n";
 import { useStatus } from "~/hooks/model/status";
 import type { Progress as ProgressType } from "~/model/status";
 
+/**
+ * Returns a description of the processed bytes and the estimated remaining time, if known
+ */
+const bytesDescription = ({ totalBytes, doneBytes, eta }: ProgressType): string | undefined => {
+  if (totalBytes === undefined) return;
+
+  const done = xbytes(doneBytes || 0, { iec: true });
+  const total = xbytes(totalBytes, { iec: true });
+  // TRANSLATORS: processed and total sizes, e.g. "120 MiB of 1.5 GiB"
+  const sizes = sprintf(_("%1$s of %2$s"), done, total);
+  if (eta === undefined) return sizes;
+
+  const minutes = Math.ceil(eta / 60);
+  const remaining =
+    eta < 60
+      ? // TRANSLATORS: %d is replaced by a number of seconds
+        sprintf(_("about %d seconds left"), eta)
+      : // TRANSLATORS: %d is replaced by a number of minutes
+        sprintf(_("about %d minutes left"), minutes);
+
+  return `${sizes} (${remaining})`;
+};
+
 const Progress = ({
   steps,
   step,
@@ -60,10 +84,12 @@ const Progress = ({
       properties.icon = <Spinner size="sm" />;
       if (detail && detail.step !== "") {
         const { step: message, index, size } = detail;
+        const bytes = bytesDescription(detail);
         properties.description = (
           <Flex direction={{ default: "column" }} rowGap={{ default: "rowGapXs" }}>
             <Truncate content={message} trailingNumChars={12} position="middle" />
             <Text component="small">{sprintf(_("Step %1$d of %2$d"), index, size)}</Text>
+            {bytes && <Text component="small">{bytes}</Text>}
           </Flex>
         );
       }
@@ -22,7 +22,16 @@
 
 import type { Scope } from "~/model/status";
 
-type Action = ConfigureL10n | ActivateStorage | Probe | DiscoverISCSI | Install | Finish;
+type Action =
+  | ConfigureL10n
+  | ActivateStorage
+  | Probe
+  | DiscoverISCSI
+  | Deregister
+  | DeregisterAddon
+  | SwitchRegistrationServer
+  | Install
+  | Finish;
 
 type ConfigureL10n = {
   configureL10n: L10nSystemConfig;
@@ -54,6 +63,18 @@ type DiscoverISCSIConfig = {
   initiatorPassword?: string;
 };
 
+type Deregister = {
+  deregister: null;
+};
+
+type DeregisterAddon = {
+  deregisterAddon: { id: string };
+};
+
+type SwitchRegistrationServer = {
+  switchRegistrationServer: { url?: string | null; code?: string | null };
+};
+
 type Install = {
   install: null;
 };
@@ -40,6 +40,10 @@ type Progress = {
   size: number;
   steps: string[];
   step: string;
+  totalBytes?: number;
+  doneBytes?: number;
+  bytesPerSecond?: number;
+  eta?: number;
 };
 
 type Task = {